@@ -1,3 +1,4 @@
+mod failure;
 pub mod lexer;
 pub mod parser_compnat1;
 pub mod parser_compnat2;
@@ -14,6 +15,8 @@ use parser_compnat3::RuleNode as CompNat3;
 use parser_evalnatexp::RuleNode as EvalNatExp;
 use parser_nat::RuleNode as Nat;
 use parser_reducenatexp::RuleNode as ReduceNatExp;
+use parser_reducenatexp::{GraphFormat, ReductionGraph};
+use util::NumFormat;
 
 use std::io::{self, Write};
 
@@ -29,35 +32,56 @@ pub enum DerivationRules {
 pub fn derive<W: Write>(
     judgement: &str,
     derivation_rules: DerivationRules,
+    num_format: NumFormat,
     w: &mut W,
 ) -> io::Result<()> {
     let lexer = Lexer::new();
     let mut tokens = lexer.lex(judgement.to_string());
-    // dbg!(&tokens);
+    if let Err(message) = tokens.check_invalid() {
+        return writeln!(w, "{}", message);
+    }
     match derivation_rules {
-        DerivationRules::Nat => {
-            let node = Nat::new(&mut tokens);
-            node.show(w, 0, true)
-        }
-        DerivationRules::CompNat1 => {
-            let node = CompNat1::new(&mut tokens);
-            node.show(w, 0, true)
-        }
-        DerivationRules::CompNat2 => {
-            let node = CompNat2::new(&mut tokens);
-            node.show(w, 0, true)
-        }
-        DerivationRules::CompNat3 => {
-            let node = CompNat3::new(&mut tokens);
-            node.show(w, 0, true)
-        }
-        DerivationRules::EvalNatExp => {
-            let node = EvalNatExp::new(&mut tokens);
-            node.show(w, 0, true)
-        }
-        DerivationRules::ReduceNatExp => {
-            let node = ReduceNatExp::new(&mut tokens);
-            node.show(w, 0, true)
-        }
+        DerivationRules::Nat => match Nat::new(&mut tokens, num_format) {
+            Ok(node) => node.show(w, 0, true, num_format),
+            Err(message) => writeln!(w, "{}", message),
+        },
+        DerivationRules::CompNat1 => match CompNat1::new(&mut tokens, num_format) {
+            Ok(node) => node.show(w, 0, true, num_format),
+            Err(message) => writeln!(w, "{}", message),
+        },
+        DerivationRules::CompNat2 => match CompNat2::new(&mut tokens, num_format) {
+            Ok(node) => node.show(w, 0, true, num_format),
+            Err(message) => writeln!(w, "{}", message),
+        },
+        DerivationRules::CompNat3 => match CompNat3::new(&mut tokens, num_format) {
+            Ok(node) => node.show(w, 0, true, num_format),
+            Err(message) => writeln!(w, "{}", message),
+        },
+        DerivationRules::EvalNatExp => match EvalNatExp::new(&mut tokens, num_format) {
+            Ok(node) => node.show(w, 0, true, num_format),
+            Err(message) => writeln!(w, "{}", message),
+        },
+        DerivationRules::ReduceNatExp => match ReduceNatExp::new(&mut tokens, num_format) {
+            Ok(node) => node.show(w, 0, true, num_format),
+            Err(message) => writeln!(w, "{}", message),
+        },
+    }
+}
+
+// the graph of every one-step reduction from a NatExp term down to its normal form
+pub fn reduction_graph<W: Write>(
+    term: &str,
+    format: GraphFormat,
+    num_format: NumFormat,
+    w: &mut W,
+) -> io::Result<()> {
+    let lexer = Lexer::new();
+    let mut tokens = lexer.lex(term.to_string());
+    if let Err(message) = tokens.check_invalid() {
+        return writeln!(w, "{}", message);
+    }
+    match ReductionGraph::new(&mut tokens) {
+        Ok(graph) => graph.show(w, format, num_format),
+        Err(message) => writeln!(w, "{}", message),
     }
 }
@@ -0,0 +1,69 @@
+use super::util::get_depth_space;
+
+// the number of premises listed in a chain of failures before the rest is summarised
+const MAX_CHAIN_DEPTH: usize = 8;
+
+// why a rule cannot derive a judgement
+#[derive(Debug, PartialEq, Clone)]
+pub enum Failure {
+    // the conclusion of the rule never has the form of the judgement
+    Conclusion(String),
+    // the rule, its premise that is not derivable and why the premise fails
+    Premise(String, String, Vec<Failure>),
+}
+
+// `summary` followed by the rules tried for the judgement. an explicit stack is used as in
+// show_derivation so that failures nested deeper than the call stack can be written
+pub fn get_failure_message(summary: &str, judgement: &str, failures: &[Failure]) -> String {
+    let mut s = format!("{}\n`{}` fails:", summary, judgement);
+    let mut stack: Vec<(&Failure, usize)> = failures.iter().rev().map(|f| (f, 2)).collect();
+    while let Some((failure, depth)) = stack.pop() {
+        s += "\n";
+        s += &get_depth_space(depth);
+        match failure {
+            Failure::Conclusion(reason) => s += reason,
+            Failure::Premise(rule, premise, failures) => {
+                s += &format!("{} requires `{}`, which fails:", rule, premise);
+                stack.extend(failures.iter().rev().map(|f| (f, depth + 2)));
+            }
+        }
+    }
+    s
+}
+
+pub fn only_concludes(rule: &str, form: &str) -> Failure {
+    Failure::Conclusion(format!("{} only concludes `{}`", rule, form))
+}
+
+// the failures of a judgement that `rule` concludes from a premise of the same form, which
+// fails the same way, `length` times over. `other` is why the other rule fails at each step,
+// `get_premise(k)` the k-th premise from the judgement and `last` why the last one fails, with
+// no rule concluding it. the chain is cut after MAX_CHAIN_DEPTH premises to keep it short
+pub fn get_chain_failures(
+    other: Failure,
+    rule: &str,
+    length: usize,
+    get_premise: impl Fn(usize) -> String,
+    last: Vec<Failure>,
+) -> Vec<Failure> {
+    let depth = length.min(MAX_CHAIN_DEPTH);
+    let mut failures = if depth < length {
+        let rest = format!(
+            "and so on through {} more premises of {} down to `{}`, which no rule concludes",
+            length - depth,
+            rule,
+            get_premise(length)
+        );
+        vec![Failure::Conclusion(rest)]
+    } else {
+        last
+    };
+    // built from the innermost premise out, so that no recursion is needed
+    for k in (1..=depth).rev() {
+        failures = vec![
+            other.clone(),
+            Failure::Premise(rule.to_string(), get_premise(k), failures),
+        ];
+    }
+    failures
+}
@@ -1,4 +1,5 @@
 use regex::Regex;
+use std::collections::VecDeque;
 use std::fs::File;
 use std::io::prelude::*;
 
@@ -12,6 +13,8 @@ pub struct DebugInfo {
 #[derive(Debug, PartialEq, Clone)]
 pub enum Token {
     Zero(DebugInfo),
+    // #n, the shorthand for S(...S(Z)...) with n S's
+    Num(usize, DebugInfo),
     Equal(DebugInfo),
     ParenS(DebugInfo),
     Ps(DebugInfo),
@@ -23,22 +26,31 @@ pub enum Token {
     EvalMR(DebugInfo),
     EvalDR(DebugInfo),
     EvalONE(DebugInfo),
+    Unknown(DebugInfo),
+    // text that no other token matches
+    Invalid(DebugInfo),
 }
 impl Token {
-    pub fn get_debug_info(self, filename: &str) -> String {
-        let debug_info = match self.clone() {
+    fn get_location(&self) -> &DebugInfo {
+        match self {
             Token::Lt(d)
             | Token::Eval(d)
             | Token::EvalMR(d)
             | Token::EvalDR(d)
             | Token::EvalONE(d)
+            | Token::Unknown(d)
+            | Token::Invalid(d)
             | Token::Zero(d)
             | Token::Equal(d)
             | Token::ParenS(d)
             | Token::Pe(d)
             | Token::Ps(d) => d,
             Token::Op(_, d) | Token::OpC(_, d) => d,
-        };
+            Token::Num(_, d) => d,
+        }
+    }
+    pub fn get_debug_info(self, filename: &str) -> String {
+        let debug_info = self.get_location().clone();
         let mut file = File::open(filename).unwrap();
         let mut contents = String::new();
         file.read_to_string(&mut contents).unwrap();
@@ -51,50 +63,104 @@ impl Token {
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Tokens {
-    pub tokens: Vec<Token>,
+    pub tokens: VecDeque<Token>,
 }
 impl Tokens {
     pub fn pop(&mut self) -> Option<Token> {
-        self.tokens.reverse();
-        let token = self.tokens.pop();
-        self.tokens.reverse();
-        token
+        self.tokens.pop_front()
     }
     pub fn len(&self) -> usize {
         self.tokens.len()
     }
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
     pub fn peek(&self) -> Option<Token> {
-        let mut tokens = self.clone();
-        tokens.pop()
+        self.tokens.front().cloned()
     }
     pub fn push(&mut self, token: Token) {
-        self.tokens.push(token);
+        self.tokens.push_back(token);
     }
     pub fn reverse(&mut self) {
-        self.tokens.reverse()
+        self.tokens.make_contiguous().reverse()
     }
-    pub fn consume_peano_num(&mut self) -> usize {
-        let mut now_cnt = 0;
+    pub fn consume_peano_num(&mut self) -> Result<usize, String> {
+        match self.peek() {
+            Some(Token::Num(n, _)) => {
+                self.pop(); // consume #n
+                return Ok(n);
+            }
+            Some(Token::Zero(_)) | Some(Token::Ps(_)) => (),
+            Some(token) => {
+                let s = &token.get_location().s;
+                return Err(format!("expects a natural number, found `{}`", s));
+            }
+            None => return Err("expects a natural number at the end".to_string()),
+        }
+        // S(...S(Z)...), closed by as many `)` as `S(` it opens
         let mut cnt = 0;
-        while let Some(token) = self.pop() {
-            match token {
-                Token::Ps(_) => {
-                    now_cnt += 1;
-                    cnt += 1;
-                }
-                Token::Pe(_) => now_cnt -= 1,
-                Token::Zero(_) => {
-                    if cnt == 0 {
-                        break;
-                    }
-                }
-                _ => (),
+        while let Some(Token::Ps(_)) = self.peek() {
+            self.pop(); // consume S(
+            cnt += 1;
+        }
+        self.consume(|token| matches!(token, Token::Zero(_)), "`Z`")?;
+        for _ in 0..cnt {
+            self.consume_paren_end()?;
+        }
+        Ok(cnt)
+    }
+    // None for `?`
+    pub fn consume_peano_num_or_unknown(&mut self) -> Result<Option<usize>, String> {
+        match self.peek() {
+            Some(Token::Unknown(_)) => {
+                self.pop(); // consume ?
+                Ok(None)
             }
-            if now_cnt == 0 {
-                break;
+            _ => self.consume_peano_num().map(Some),
+        }
+    }
+    // `)` closing a parenthesized expression
+    pub fn consume_paren_end(&mut self) -> Result<(), String> {
+        self.consume(|token| matches!(token, Token::Pe(_)), "`)`")
+            .map(|_| ())
+    }
+    // the token the syntax requires next, e.g. `is` after `n1 plus n2`
+    pub fn consume(
+        &mut self,
+        is_expected: impl Fn(&Token) -> bool,
+        expected: &str,
+    ) -> Result<Token, String> {
+        match self.peek() {
+            Some(token) if is_expected(&token) => {
+                self.pop();
+                Ok(token)
             }
+            _ => Err(self.get_unexpected_message(expected)),
+        }
+    }
+    // the message for the next token, which is not the expected one
+    pub fn get_unexpected_message(&self, expected: &str) -> String {
+        match self.peek() {
+            Some(token) => format!("expects {}, found `{}`", expected, token.get_location().s),
+            None => format!("expects {} at the end", expected),
+        }
+    }
+    // the judgement is over, so text left after it is an error
+    pub fn expect_end(&self) -> Result<(), String> {
+        match self.peek() {
+            Some(token) => Err(format!("unexpected `{}`", token.get_location().s)),
+            None => Ok(()),
+        }
+    }
+    pub fn check_invalid(&self) -> Result<(), String> {
+        match self
+            .tokens
+            .iter()
+            .find(|token| matches!(token, Token::Invalid(_)))
+        {
+            Some(token) => Err(format!("unexpected `{}`", token.get_location().s)),
+            None => Ok(()),
         }
-        cnt
     }
     pub fn consume_operator(&mut self) -> String {
         if let Some(Token::Op(op, _)) = self.pop() {
@@ -110,11 +176,18 @@ pub struct Lexer {
     names: Vec<&'static str>,
 }
 
+impl Default for Lexer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Lexer {
     // static constructor
     pub fn new() -> Lexer {
         let token_patterns = vec![
             ("ZERO", r"Z"),
+            ("NUM", r"#[0-9]+"),
             ("OP", r"(plus)|(times)"),
             ("OPC", r"\+|\*"),
             ("LT", r"is less than"),
@@ -126,6 +199,8 @@ impl Lexer {
             ("EVALMR", r"-\*->"),
             ("EVALDR", r"-d->"),
             ("EVALONE", r"--->"),
+            ("UNKNOWN", r"\?"),
+            ("INVALID", r"\S+"),
         ];
         let re = make_regex(&token_patterns);
         let names = get_names(&token_patterns);
@@ -133,16 +208,14 @@ impl Lexer {
         Lexer { re, names }
     }
     pub fn lex(&self, code: String) -> Tokens {
-        let mut code = code;
-        let tokens = self.tokenize(&mut code);
-        tokens
+        self.tokenize(&code)
     }
-    fn tokenize(&self, code: &mut String) -> Tokens {
-        let mut tokens: Vec<Token> = Vec::new();
+    fn tokenize(&self, code: &str) -> Tokens {
+        let mut tokens: VecDeque<Token> = VecDeque::new();
 
         // get token's location for setting debugging info
         let mut locations: Vec<DebugInfo> = Vec::new();
-        for mat in self.re.find_iter(&code) {
+        for mat in self.re.find_iter(code) {
             let location = DebugInfo {
                 start: mat.start(),
                 end: mat.end(),
@@ -150,7 +223,7 @@ impl Lexer {
             };
             locations.push(location);
         }
-        for (i, caps) in self.re.captures_iter(&code).enumerate() {
+        for (i, caps) in self.re.captures_iter(code).enumerate() {
             let mut typ = String::from("nil");
             let val = String::from(&caps[0]);
             for name in &self.names {
@@ -158,20 +231,27 @@ impl Lexer {
                     typ = name.to_string();
                 }
             }
-            let debug_info = locations.clone().remove(i);
+            let debug_info = locations[i].clone();
             match typ.as_ref() {
-                "ZERO" => tokens.push(Token::Zero(debug_info)),
-                "OP" => tokens.push(Token::Op(val, debug_info)),
-                "OPC" => tokens.push(Token::OpC(val, debug_info)),
-                "LT" => tokens.push(Token::Lt(debug_info)),
-                "EQ" => tokens.push(Token::Equal(debug_info)),
-                "PARENS" => tokens.push(Token::ParenS(debug_info)),
-                "PS" => tokens.push(Token::Ps(debug_info)),
-                "PE" => tokens.push(Token::Pe(debug_info)),
-                "EVAL" => tokens.push(Token::Eval(debug_info)),
-                "EVALMR" => tokens.push(Token::EvalMR(debug_info)),
-                "EVALDR" => tokens.push(Token::EvalDR(debug_info)),
-                "EVALONE" => tokens.push(Token::EvalONE(debug_info)),
+                "ZERO" => tokens.push_back(Token::Zero(debug_info)),
+                // a number too large for usize is reported as invalid text
+                "NUM" => match val[1..].parse() {
+                    Ok(n) => tokens.push_back(Token::Num(n, debug_info)),
+                    Err(_) => tokens.push_back(Token::Invalid(debug_info)),
+                },
+                "OP" => tokens.push_back(Token::Op(val, debug_info)),
+                "OPC" => tokens.push_back(Token::OpC(val, debug_info)),
+                "LT" => tokens.push_back(Token::Lt(debug_info)),
+                "EQ" => tokens.push_back(Token::Equal(debug_info)),
+                "PARENS" => tokens.push_back(Token::ParenS(debug_info)),
+                "PS" => tokens.push_back(Token::Ps(debug_info)),
+                "PE" => tokens.push_back(Token::Pe(debug_info)),
+                "EVAL" => tokens.push_back(Token::Eval(debug_info)),
+                "EVALMR" => tokens.push_back(Token::EvalMR(debug_info)),
+                "EVALDR" => tokens.push_back(Token::EvalDR(debug_info)),
+                "EVALONE" => tokens.push_back(Token::EvalONE(debug_info)),
+                "UNKNOWN" => tokens.push_back(Token::Unknown(debug_info)),
+                "INVALID" => tokens.push_back(Token::Invalid(debug_info)),
                 _ => panic!("unexpected type token"),
             }
         }
@@ -180,16 +260,13 @@ impl Lexer {
 }
 fn make_regex(token_patterns: &Vec<(&str, &str)>) -> String {
     token_patterns
-        .into_iter()
+        .iter()
         .map(|pattern| format!("(?P<{}>{})", pattern.0, pattern.1))
         .collect::<Vec<String>>()
         .join("|")
 }
-fn get_names<'a, 'b>(token_patterns: &Vec<(&'a str, &'b str)>) -> Vec<&'a str> {
-    token_patterns
-        .into_iter()
-        .map(|pattern| pattern.0)
-        .collect()
+fn get_names<'a>(token_patterns: &Vec<(&'a str, &str)>) -> Vec<&'a str> {
+    token_patterns.iter().map(|pattern| pattern.0).collect()
 }
 
 #[cfg(test)]
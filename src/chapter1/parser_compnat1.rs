@@ -1,5 +1,6 @@
+use super::failure::{get_failure_message, only_concludes, Failure};
 use super::lexer::Tokens;
-use super::util::{get_depth_space, get_peano_num};
+use super::util::{show_derivation, NumFormat};
 use std::io::{self, Write};
 
 #[derive(Debug, PartialEq, Clone)]
@@ -8,33 +9,61 @@ pub enum RuleNode {
     LTrans(LTransNode),
 }
 impl RuleNode {
-    pub fn new(tokens: &mut Tokens) -> RuleNode {
-        let n1 = tokens.consume_peano_num();
+    pub fn new(tokens: &mut Tokens, format: NumFormat) -> Result<RuleNode, String> {
+        let n1 = tokens.consume_peano_num()?;
         tokens.pop(); // consume "is less than"
-        let n2 = tokens.consume_peano_num();
-        get_rule_lt(n1, n2)
+        let n2 = tokens.consume_peano_num()?;
+        tokens.expect_end()?;
+        if n1 >= n2 {
+            let summary = format!(
+                "judgement does not hold: {} is not less than {}",
+                format.get_num(n1),
+                format.get_num(n2)
+            );
+            let judgement = format!("{} is less than {}", format.get_num(n1), format.get_num(n2));
+            let failures = get_failures_lt(n1, n2, format);
+            return Err(get_failure_message(&summary, &judgement, &failures));
+        }
+        Ok(get_rule_lt(n1, n2))
+    }
+
+    pub fn show<W: Write>(
+        self,
+        w: &mut W,
+        depth: usize,
+        with_newline: bool,
+        format: NumFormat,
+    ) -> io::Result<()> {
+        show_derivation(w, self, depth, with_newline, |node| node.get_step(format))
     }
 
-    pub fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
         match self {
-            RuleNode::LSucc(node) => node.show(w, depth, with_newline),
-            RuleNode::LTrans(node) => node.show(w, depth, with_newline),
+            RuleNode::LSucc(node) => node.get_step(format),
+            RuleNode::LTrans(node) => node.get_step(format),
         }
     }
 }
 
+// why `n1 is less than n2` is not derivable, given n1 >= n2
+fn get_failures_lt(n1: usize, n2: usize, format: NumFormat) -> Vec<Failure> {
+    // a premise of L-Trans is not derivable for any choice of n, so they aren't listed
+    let trans = format!(
+        "L-Trans requires `{} is less than n` and `n is less than {}` for some n, but no such n exists",
+        format.get_num(n1),
+        format.get_num(n2)
+    );
+    vec![
+        only_concludes("L-Succ", "n is less than S(n)"),
+        Failure::Conclusion(trans),
+    ]
+}
+
 fn get_rule_lt(n1: usize, n2: usize) -> RuleNode {
     if n1 + 1 == n2 {
         RuleNode::LSucc(LSuccNode { n: n1 })
     } else if n1 + 1 < n2 {
-        let premise1 = Box::new(get_rule_lt(n1, n2 - 1));
-        let premise2 = Box::new(get_rule_lt(n2 - 1, n2));
-        RuleNode::LTrans(LTransNode {
-            n1,
-            n2,
-            premise1,
-            premise2,
-        })
+        RuleNode::LTrans(LTransNode { n1, n2 })
     } else {
         panic!("unexpected number")
     }
@@ -45,43 +74,31 @@ pub struct LSuccNode {
     n: usize,
 }
 impl LSuccNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let n1 = get_peano_num(self.n);
-        let n2 = get_peano_num(self.n + 1);
-        let nl = if with_newline { "\n" } else { "" };
-        write!(
-            w,
-            "{}{} is less than {} by L-Succ {{}}{}",
-            get_depth_space(depth),
-            n1,
-            n2,
-            nl
-        )
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let conclusion = format!(
+            "{} is less than {} by L-Succ",
+            format.get_num(self.n),
+            format.get_num(self.n + 1)
+        );
+        (conclusion, vec![])
     }
 }
 
+// the premises are made when the node is shown, so a node only holds its numbers
 #[derive(Debug, PartialEq, Clone)]
 pub struct LTransNode {
     n1: usize,
     n2: usize,
-    premise1: Box<RuleNode>,
-    premise2: Box<RuleNode>,
 }
 impl LTransNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let n1 = get_peano_num(self.n1);
-        let n2 = get_peano_num(self.n2);
-        let _ = write!(
-            w,
-            "{}{} is less than {} by L-Trans {{\n",
-            get_depth_space(depth),
-            n1,
-            n2,
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let conclusion = format!(
+            "{} is less than {} by L-Trans",
+            format.get_num(self.n1),
+            format.get_num(self.n2)
         );
-        let _ = self.premise1.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
-        let _ = self.premise2.show(w, depth + 2, true);
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+        let premise1 = get_rule_lt(self.n1, self.n2 - 1);
+        let premise2 = get_rule_lt(self.n2 - 1, self.n2);
+        (conclusion, vec![premise1, premise2])
     }
 }
@@ -1,3 +1,4 @@
+use super::failure::{get_chain_failures, get_failure_message, only_concludes, Failure};
 use super::lexer::Tokens;
 use super::util::*;
 use std::io::{self, Write};
@@ -8,27 +9,66 @@ pub enum RuleNode {
     LSuccSucc(LSuccSuccNode),
 }
 impl RuleNode {
-    pub fn new(tokens: &mut Tokens) -> RuleNode {
-        let n1 = tokens.consume_peano_num();
+    pub fn new(tokens: &mut Tokens, format: NumFormat) -> Result<RuleNode, String> {
+        let n1 = tokens.consume_peano_num()?;
         tokens.pop(); // consume "is less than"
-        let n2 = tokens.consume_peano_num();
-        get_rule_lt(n1, n2)
+        let n2 = tokens.consume_peano_num()?;
+        tokens.expect_end()?;
+        if n1 >= n2 {
+            let summary = format!(
+                "judgement does not hold: {} is not less than {}",
+                format.get_num(n1),
+                format.get_num(n2)
+            );
+            let judgement = format!("{} is less than {}", format.get_num(n1), format.get_num(n2));
+            let failures = get_failures_lt(n1, n2, format);
+            return Err(get_failure_message(&summary, &judgement, &failures));
+        }
+        Ok(get_rule_lt(n1, n2))
+    }
+
+    pub fn show<W: Write>(
+        self,
+        w: &mut W,
+        depth: usize,
+        with_newline: bool,
+        format: NumFormat,
+    ) -> io::Result<()> {
+        show_derivation(w, self, depth, with_newline, |node| node.get_step(format))
     }
 
-    pub fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
         match self {
-            RuleNode::LZero(node) => node.show(w, depth, with_newline),
-            RuleNode::LSuccSucc(node) => node.show(w, depth, with_newline),
+            RuleNode::LZero(node) => node.get_step(format),
+            RuleNode::LSuccSucc(node) => node.get_step(format),
         }
     }
 }
 
+// why `n1 is less than n2` is not derivable, given n1 >= n2
+fn get_failures_lt(n1: usize, n2: usize, format: NumFormat) -> Vec<Failure> {
+    get_chain_failures(
+        only_concludes("L-Zero", "Z is less than S(n)"),
+        "L-SuccSucc",
+        n2,
+        |k| {
+            format!(
+                "{} is less than {}",
+                format.get_num(n1 - k),
+                format.get_num(n2 - k)
+            )
+        },
+        vec![Failure::Conclusion(
+            "no rule concludes `n is less than Z`".to_string(),
+        )],
+    )
+}
+
 fn get_rule_lt(n1: usize, n2: usize) -> RuleNode {
     if n1 == 0 {
         RuleNode::LZero(LZeroNode { n: n2 })
     } else {
-        let premise = Box::new(get_rule_lt(n1 - 1, n2 - 1));
-        RuleNode::LSuccSucc(LSuccSuccNode { n1, n2, premise })
+        RuleNode::LSuccSucc(LSuccSuccNode { n1, n2 })
     }
 }
 
@@ -37,38 +77,26 @@ pub struct LZeroNode {
     n: usize,
 }
 impl LZeroNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let n = get_peano_num(self.n);
-        let nl = if with_newline { "\n" } else { "" };
-        write!(
-            w,
-            "{}Z is less than {} by L-Zero {{}}{}",
-            get_depth_space(depth),
-            n,
-            nl
-        )
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let conclusion = format!("Z is less than {} by L-Zero", format.get_num(self.n));
+        (conclusion, vec![])
     }
 }
 
+// the premise is made when the node is shown, so a node only holds its numbers
 #[derive(Debug, PartialEq, Clone)]
 pub struct LSuccSuccNode {
     n1: usize,
     n2: usize,
-    premise: Box<RuleNode>,
 }
 impl LSuccSuccNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let n1 = get_peano_num(self.n1);
-        let n2 = get_peano_num(self.n2);
-        let _ = write!(
-            w,
-            "{}{} is less than {} by L-SuccSucc {{\n",
-            get_depth_space(depth),
-            n1,
-            n2
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let conclusion = format!(
+            "{} is less than {} by L-SuccSucc",
+            format.get_num(self.n1),
+            format.get_num(self.n2)
         );
-        let _ = self.premise.show(w, depth + 2, true);
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+        let premise = get_rule_lt(self.n1 - 1, self.n2 - 1);
+        (conclusion, vec![premise])
     }
 }
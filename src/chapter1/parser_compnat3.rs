@@ -1,3 +1,4 @@
+use super::failure::{get_chain_failures, get_failure_message, only_concludes, Failure};
 use super::lexer::Tokens;
 use super::util::*;
 use std::io::{self, Write};
@@ -8,27 +9,66 @@ pub enum RuleNode {
     LSuccR(LSuccRNode),
 }
 impl RuleNode {
-    pub fn new(tokens: &mut Tokens) -> RuleNode {
-        let n1 = tokens.consume_peano_num();
+    pub fn new(tokens: &mut Tokens, format: NumFormat) -> Result<RuleNode, String> {
+        let n1 = tokens.consume_peano_num()?;
         tokens.pop(); // consume "is less than"
-        let n2 = tokens.consume_peano_num();
-        get_rule_lt(n1, n2)
+        let n2 = tokens.consume_peano_num()?;
+        tokens.expect_end()?;
+        if n1 >= n2 {
+            let summary = format!(
+                "judgement does not hold: {} is not less than {}",
+                format.get_num(n1),
+                format.get_num(n2)
+            );
+            let judgement = format!("{} is less than {}", format.get_num(n1), format.get_num(n2));
+            let failures = get_failures_lt(n1, n2, format);
+            return Err(get_failure_message(&summary, &judgement, &failures));
+        }
+        Ok(get_rule_lt(n1, n2))
+    }
+
+    pub fn show<W: Write>(
+        self,
+        w: &mut W,
+        depth: usize,
+        with_newline: bool,
+        format: NumFormat,
+    ) -> io::Result<()> {
+        show_derivation(w, self, depth, with_newline, |node| node.get_step(format))
     }
 
-    pub fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
         match self {
-            RuleNode::LSucc(node) => node.show(w, depth, with_newline),
-            RuleNode::LSuccR(node) => node.show(w, depth, with_newline),
+            RuleNode::LSucc(node) => node.get_step(format),
+            RuleNode::LSuccR(node) => node.get_step(format),
         }
     }
 }
 
+// why `n1 is less than n2` is not derivable, given n1 >= n2
+fn get_failures_lt(n1: usize, n2: usize, format: NumFormat) -> Vec<Failure> {
+    get_chain_failures(
+        only_concludes("L-Succ", "n is less than S(n)"),
+        "L-SuccR",
+        n2,
+        |k| {
+            format!(
+                "{} is less than {}",
+                format.get_num(n1),
+                format.get_num(n2 - k)
+            )
+        },
+        vec![Failure::Conclusion(
+            "no rule concludes `n is less than Z`".to_string(),
+        )],
+    )
+}
+
 fn get_rule_lt(n1: usize, n2: usize) -> RuleNode {
     if n1 + 1 == n2 {
         RuleNode::LSucc(LSuccNode { n: n1 })
     } else if n1 + 1 < n2 {
-        let premise = Box::new(get_rule_lt(n1, n2 - 1));
-        RuleNode::LSuccR(LSuccRNode { n1, n2, premise })
+        RuleNode::LSuccR(LSuccRNode { n1, n2 })
     } else {
         panic!("unexpected number")
     }
@@ -39,40 +79,30 @@ pub struct LSuccNode {
     n: usize,
 }
 impl LSuccNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let n1 = get_peano_num(self.n);
-        let n2 = get_peano_num(self.n + 1);
-        let nl = if with_newline { "\n" } else { "" };
-        write!(
-            w,
-            "{}{} is less than {} by L-Succ {{}}{}",
-            get_depth_space(depth),
-            n1,
-            n2,
-            nl
-        )
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let conclusion = format!(
+            "{} is less than {} by L-Succ",
+            format.get_num(self.n),
+            format.get_num(self.n + 1)
+        );
+        (conclusion, vec![])
     }
 }
 
+// the premise is made when the node is shown, so a node only holds its numbers
 #[derive(Debug, PartialEq, Clone)]
 pub struct LSuccRNode {
     n1: usize,
     n2: usize,
-    premise: Box<RuleNode>,
 }
 impl LSuccRNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let n1 = get_peano_num(self.n1);
-        let n2 = get_peano_num(self.n2);
-        let _ = write!(
-            w,
-            "{}{} is less than {} by L-SuccR {{\n",
-            get_depth_space(depth),
-            n1,
-            n2,
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let conclusion = format!(
+            "{} is less than {} by L-SuccR",
+            format.get_num(self.n1),
+            format.get_num(self.n2)
         );
-        let _ = self.premise.show(w, depth + 2, true);
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+        let premise = get_rule_lt(self.n1, self.n2 - 1);
+        (conclusion, vec![premise])
     }
 }
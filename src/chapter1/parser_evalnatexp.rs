@@ -1,5 +1,7 @@
+use super::failure::{get_failure_message, only_concludes, Failure};
 use super::lexer::{Token, Tokens};
-use super::util::{get_depth_space, get_peano_num};
+use super::parser_nat::{get_failures_plus, get_failures_times};
+use super::util::{get_not_hold_message, show_derivation, NumFormat};
 
 use std::collections::HashMap;
 use std::io::{self, Write};
@@ -16,9 +18,27 @@ pub enum RuleNode {
 }
 
 impl RuleNode {
-    pub fn new(tokens: &mut Tokens) -> RuleNode {
+    pub fn new(tokens: &mut Tokens, format: NumFormat) -> Result<RuleNode, String> {
         let terms = Terms::new(tokens);
-        get_rule_eval(terms)
+        tokens.pop(); // consume evalto
+        let node = get_rule_eval(terms.clone());
+        // the value is computed when it's left as `?` or `evalto` is left off
+        let n = match tokens.peek() {
+            Some(Token::Unknown(_)) => {
+                tokens.pop(); // consume ?
+                node.get_val()
+            }
+            None => node.get_val(),
+            _ => tokens.consume_peano_num()?, // expect a number ( not an expression )
+        };
+        tokens.expect_end()?;
+        if node.get_val() != n {
+            let summary = get_not_hold_message(&format.get_num(n), &format.get_num(node.get_val()));
+            let judgement = format!("{} evalto {}", terms.to_string(format), format.get_num(n));
+            let failures = get_failures_eval(&node, n, format);
+            return Err(get_failure_message(&summary, &judgement, &failures));
+        }
+        Ok(node)
     }
     pub fn get_val(&self) -> usize {
         match self {
@@ -31,15 +51,24 @@ impl RuleNode {
             RuleNode::TSucc(node) => node.get_val(),
         }
     }
-    pub fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
+    pub fn show<W: Write>(
+        self,
+        w: &mut W,
+        depth: usize,
+        with_newline: bool,
+        format: NumFormat,
+    ) -> io::Result<()> {
+        show_derivation(w, self, depth, with_newline, |node| node.get_step(format))
+    }
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
         match self {
-            RuleNode::EConst(node) => node.show(w, depth, with_newline),
-            RuleNode::EPlus(node) => node.show(w, depth, with_newline),
-            RuleNode::ETimes(node) => node.show(w, depth, with_newline),
-            RuleNode::PZero(node) => node.show(w, depth, with_newline),
-            RuleNode::PSucc(node) => node.show(w, depth, with_newline),
-            RuleNode::TZero(node) => node.show(w, depth, with_newline),
-            RuleNode::TSucc(node) => node.show(w, depth, with_newline),
+            RuleNode::EConst(node) => node.get_step(format),
+            RuleNode::EPlus(node) => node.get_step(format),
+            RuleNode::ETimes(node) => node.get_step(format),
+            RuleNode::PZero(node) => node.get_step(format),
+            RuleNode::PSucc(node) => node.get_step(format),
+            RuleNode::TZero(node) => node.get_step(format),
+            RuleNode::TSucc(node) => node.get_step(format),
         }
     }
 }
@@ -58,8 +87,8 @@ impl Term {
                 tokens.pop(); // consume )
                 Term::Node(operator, terms)
             }
-            Token::Ps(_) => {
-                let num = tokens.consume_peano_num();
+            Token::Ps(_) | Token::Num(_, _) => {
+                let num = tokens.consume_peano_num().expect("a number");
                 Term::Leaf(operator, num)
             }
             Token::Zero(_) => {
@@ -87,20 +116,15 @@ impl Terms {
         let mut terms = Terms { terms };
         terms.push(Term::new(tokens, "".to_string()));
         loop {
-            match tokens.peek().expect("expects a tokens") {
-                Token::OpC(operator, _) => {
+            match tokens.peek() {
+                Some(Token::OpC(operator, _)) => {
                     tokens.pop(); // consume operator
                     terms.push(Term::new(tokens, operator));
                 }
-                Token::Eval(_) => {
-                    tokens.pop(); // consume evalto
-                    let _res = tokens.consume_peano_num(); // consume ans num. expect a number ( not an expression )
-                    break;
-                }
-                Token::Pe(_) => {
+                Some(Token::Eval(_)) | Some(Token::Pe(_)) | None => {
                     break;
                 }
-                _ => panic!("unexpected token: {:?}"),
+                token => panic!("unexpected token: {:?}", token),
             }
         }
         terms
@@ -151,13 +175,12 @@ impl Terms {
         latter.rm_first_operator();
         (former, latter)
     }
-    fn to_string(self) -> String {
+    fn to_string(&self, format: NumFormat) -> String {
         let mut s = "".to_string();
-        let terms = self.terms.into_iter();
-        for term in terms {
+        for term in self.terms.iter() {
             match term {
                 Term::Leaf(operator, num) => {
-                    let snum = &get_peano_num(num);
+                    let snum = &format.get_num(*num);
                     match operator.as_ref() {
                         "" => s += snum,
                         "+" => s = s + " + " + snum,
@@ -168,12 +191,12 @@ impl Terms {
                 Term::Node(operator, terms) => {
                     match operator.as_ref() {
                         "" => (),
-                        "+" => s = s + " + ",
-                        "*" => s = s + " * ",
+                        "+" => s += " + ",
+                        "*" => s += " * ",
                         _ => panic!("TODO"),
                     }
                     s += "(";
-                    s += &terms.to_string();
+                    s += &terms.to_string(format);
                     s += ")";
                 }
             }
@@ -197,6 +220,42 @@ impl Terms {
     }
 }
 
+// why `e evalto n` is not derivable, given e doesn't evaluate to n. the rule is
+// determined by e, and only its last premise can fail
+fn get_failures_eval(node: &RuleNode, n: usize, format: NumFormat) -> Vec<Failure> {
+    let (rule, op, n1, n2) = match node {
+        RuleNode::EPlus(node) => (
+            "E-Plus",
+            "plus",
+            node.premise_term1.get_val(),
+            node.premise_term2.get_val(),
+        ),
+        RuleNode::ETimes(node) => (
+            "E-Times",
+            "times",
+            node.premise_term1.get_val(),
+            node.premise_term2.get_val(),
+        ),
+        _ => return vec![only_concludes("E-Const", "n evalto n")],
+    };
+    let premise = format!(
+        "{} {} {} is {}",
+        format.get_num(n1),
+        op,
+        format.get_num(n2),
+        format.get_num(n)
+    );
+    let premise_failures = match op {
+        "plus" => get_failures_plus(n1, n2, n, format),
+        _ => get_failures_times(n1, n2, n, format),
+    };
+    vec![Failure::Premise(
+        rule.to_string(),
+        premise,
+        premise_failures,
+    )]
+}
+
 fn get_rule_eval(mut terms: Terms) -> RuleNode {
     let terms_clone = terms.clone();
     if terms.len() == 1 {
@@ -249,17 +308,9 @@ pub struct EConstNode {
     n: usize,
 }
 impl EConstNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let n = get_peano_num(self.n);
-        let nl = if with_newline { "\n" } else { "" };
-        write!(
-            w,
-            "{}{} evalto {} by E-Const {{}}{}",
-            get_depth_space(depth),
-            n,
-            n,
-            nl,
-        )
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let n = format.get_num(self.n);
+        (format!("{} evalto {} by E-Const", n, n), vec![])
     }
     fn get_val(&self) -> usize {
         self.n
@@ -273,27 +324,19 @@ pub struct EPlusNode {
     premise_term2: Box<RuleNode>,
 }
 impl EPlusNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
         let n1 = self.premise_term1.get_val();
         let n2 = self.premise_term2.get_val();
-        let _ = write!(
-            w,
-            "{}{} evalto {} by E-Plus {{\n",
-            get_depth_space(depth),
-            self.terms.to_string(),
-            get_peano_num(n1 + n2)
+        let conclusion = format!(
+            "{} evalto {} by E-Plus",
+            self.terms.to_string(format),
+            format.get_num(n1 + n2)
         );
-
-        let _ = self.premise_term1.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
-        let _ = self.premise_term2.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
-
         let premise = get_rule_plus(n1, n2);
-        let _ = premise.show(w, depth + 2, true);
-
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+        (
+            conclusion,
+            vec![*self.premise_term1, *self.premise_term2, premise],
+        )
     }
     fn get_val(&self) -> usize {
         self.premise_term1.get_val() + self.premise_term2.get_val()
@@ -307,27 +350,19 @@ pub struct ETimesNode {
     premise_term2: Box<RuleNode>,
 }
 impl ETimesNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
         let n1 = self.premise_term1.get_val();
         let n2 = self.premise_term2.get_val();
-        let _ = write!(
-            w,
-            "{}{} evalto {} by E-Times {{\n",
-            get_depth_space(depth),
-            self.terms.to_string(),
-            get_peano_num(n1 * n2)
+        let conclusion = format!(
+            "{} evalto {} by E-Times",
+            self.terms.to_string(format),
+            format.get_num(n1 * n2)
         );
-
-        let _ = self.premise_term1.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
-        let _ = self.premise_term2.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
-
         let premise = get_rule_times(n1, n2);
-        let _ = premise.show(w, depth + 2, true);
-
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+        (
+            conclusion,
+            vec![*self.premise_term1, *self.premise_term2, premise],
+        )
     }
     fn get_val(&self) -> usize {
         self.premise_term1.get_val() * self.premise_term2.get_val()
@@ -339,11 +374,9 @@ pub struct PZeroNode {
     n: usize,
 }
 impl PZeroNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let n = get_peano_num(self.n);
-        let _ = write!(w, "{}", get_depth_space(depth));
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "Z plus {} is {} by P-Zero {{}}{}", n, n, nl)
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let n = format.get_num(self.n);
+        (format!("Z plus {} is {} by P-Zero", n, n), vec![])
     }
     fn get_val(&self) -> usize {
         self.n
@@ -356,19 +389,15 @@ pub struct PSuccNode {
     n2: usize,
 }
 impl PSuccNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
-            w,
-            "{}{} plus {} is {} by P-Succ {{\n",
-            get_depth_space(depth),
-            get_peano_num(self.n1),
-            get_peano_num(self.n2),
-            get_peano_num(self.n1 + self.n2),
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let conclusion = format!(
+            "{} plus {} is {} by P-Succ",
+            format.get_num(self.n1),
+            format.get_num(self.n2),
+            format.get_num(self.n1 + self.n2),
         );
         let premise = get_rule_plus(self.n1 - 1, self.n2);
-        let _ = premise.show(w, depth + 2, true);
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+        (conclusion, vec![premise])
     }
     fn get_val(&self) -> usize {
         self.n1 + self.n2
@@ -380,11 +409,9 @@ pub struct TZeroNode {
     n: usize,
 }
 impl TZeroNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let n = get_peano_num(self.n);
-        let _ = write!(w, "{}", get_depth_space(depth));
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "Z times {} is Z by T-Zero {{}}{}", n, nl)
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let n = format.get_num(self.n);
+        (format!("Z times {} is Z by T-Zero", n), vec![])
     }
     fn get_val(&self) -> usize {
         self.n
@@ -397,22 +424,16 @@ pub struct TSuccNode {
     n2: usize,
 }
 impl TSuccNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
-            w,
-            "{}{} times {} is {} by T-Succ {{\n",
-            get_depth_space(depth),
-            get_peano_num(self.n1),
-            get_peano_num(self.n2),
-            get_peano_num(self.n1 * self.n2),
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let conclusion = format!(
+            "{} times {} is {} by T-Succ",
+            format.get_num(self.n1),
+            format.get_num(self.n2),
+            format.get_num(self.n1 * self.n2),
         );
         let premise1 = get_rule_times(self.n1 - 1, self.n2);
-        let _ = premise1.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
         let premise2 = get_rule_plus(self.n2, (self.n1 - 1) * self.n2);
-        let _ = premise2.show(w, depth + 2, true);
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+        (conclusion, vec![premise1, premise2])
     }
     fn get_val(&self) -> usize {
         self.n1 * self.n2
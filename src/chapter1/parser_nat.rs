@@ -1,3 +1,4 @@
+use super::failure::{get_chain_failures, get_failure_message, only_concludes, Failure};
 use super::lexer::Tokens;
 use super::util::*;
 use std::io::{self, Write};
@@ -10,42 +11,149 @@ pub enum RuleNode {
     TSucc(TSuccNode),
 }
 impl RuleNode {
-    pub fn new(tokens: &mut Tokens) -> RuleNode {
-        let n1 = tokens.consume_peano_num();
+    pub fn new(tokens: &mut Tokens, format: NumFormat) -> Result<RuleNode, String> {
+        let n1 = tokens.consume_peano_num_or_unknown()?;
         let op = tokens.consume_operator();
-        let n2 = tokens.consume_peano_num();
+        let n2 = tokens.consume_peano_num_or_unknown()?;
         tokens.pop(); // consume is
-        let n3 = tokens.consume_peano_num();
-        match op.as_ref() {
-            "plus" => get_rule_plus(n1, n2, n3),
-            "times" => get_rule_times(n1, n2, n3),
+        let n3 = tokens.consume_peano_num_or_unknown()?;
+        tokens.expect_end()?;
+        let (n1, n2, n3) = solve(n1, &op, n2, n3, format)?;
+        let n = match op.as_ref() {
+            "plus" => n1 + n2,
+            "times" => n1 * n2,
             _ => panic!("unexpected operator"),
+        };
+        if n != n3 {
+            let summary = get_not_hold_message(&format.get_num(n3), &format.get_num(n));
+            let judgement = format!(
+                "{} {} {} is {}",
+                format.get_num(n1),
+                op,
+                format.get_num(n2),
+                format.get_num(n3)
+            );
+            let failures = match op.as_ref() {
+                "plus" => get_failures_plus(n1, n2, n3, format),
+                _ => get_failures_times(n1, n2, n3, format),
+            };
+            return Err(get_failure_message(&summary, &judgement, &failures));
+        }
+        match op.as_ref() {
+            "plus" => Ok(get_rule_plus(n1, n2, n3)),
+            _ => Ok(get_rule_times(n1, n2, n3)),
         }
     }
 
-    pub fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
+    pub fn show<W: Write>(
+        self,
+        w: &mut W,
+        depth: usize,
+        with_newline: bool,
+        format: NumFormat,
+    ) -> io::Result<()> {
+        show_derivation(w, self, depth, with_newline, |node| node.get_step(format))
+    }
+
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
         match self {
-            RuleNode::PZero(node) => node.show(w, depth, with_newline),
-            RuleNode::PSucc(node) => node.show(w, depth, with_newline),
-            RuleNode::TZero(node) => node.show(w, depth, with_newline),
-            RuleNode::TSucc(node) => node.show(w, depth, with_newline),
+            RuleNode::PZero(node) => node.get_step(format),
+            RuleNode::PSucc(node) => node.get_step(format),
+            RuleNode::TZero(node) => node.get_step(format),
+            RuleNode::TSucc(node) => node.get_step(format),
+        }
+    }
+}
+
+// fills in the number given as `?`
+fn solve(
+    n1: Option<usize>,
+    op: &str,
+    n2: Option<usize>,
+    n3: Option<usize>,
+    format: NumFormat,
+) -> Result<(usize, usize, usize), String> {
+    let to_string = |n: Option<usize>, unknown: &str| match n {
+        Some(n) => format.get_num(n),
+        None => unknown.to_string(),
+    };
+    let no_solution = || {
+        Err(format!(
+            "judgement has no solution: no n satisfies `{} {} {} is {}`",
+            to_string(n1, "n"),
+            op,
+            to_string(n2, "n"),
+            to_string(n3, "n")
+        ))
+    };
+    match (n1, n2, n3, op) {
+        (Some(n1), Some(n2), Some(n3), _) => Ok((n1, n2, n3)),
+        (Some(n1), Some(n2), None, "plus") => Ok((n1, n2, n1 + n2)),
+        (Some(n1), Some(n2), None, _) => Ok((n1, n2, n1 * n2)),
+        (Some(n1), None, Some(n3), "plus") if n1 <= n3 => Ok((n1, n3 - n1, n3)),
+        (None, Some(n2), Some(n3), "plus") if n2 <= n3 => Ok((n3 - n2, n2, n3)),
+        (Some(_), None, Some(_), "plus") | (None, Some(_), Some(_), "plus") => no_solution(),
+        // any number solves `Z times ? is Z`, and Z is taken
+        (Some(0), None, Some(0), _) | (None, Some(0), Some(0), _) => {
+            Ok((n1.unwrap_or(0), n2.unwrap_or(0), 0))
         }
+        (Some(n1), None, Some(n3), _) if n1 != 0 && n3 % n1 == 0 => Ok((n1, n3 / n1, n3)),
+        (None, Some(n2), Some(n3), _) if n2 != 0 && n3 % n2 == 0 => Ok((n3 / n2, n2, n3)),
+        (Some(_), None, Some(_), _) | (None, Some(_), Some(_), _) => no_solution(),
+        _ => Err("judgement has more than one `?`".to_string()),
+    }
+}
+
+// why `n1 plus n2 is n3` is not derivable, given n1 + n2 != n3
+pub fn get_failures_plus(n1: usize, n2: usize, n3: usize, format: NumFormat) -> Vec<Failure> {
+    get_chain_failures(
+        only_concludes("P-Zero", "Z plus n is n"),
+        "P-Succ",
+        n1.min(n3),
+        |k| {
+            format!(
+                "{} plus {} is {}",
+                format.get_num(n1 - k),
+                format.get_num(n2),
+                format.get_num(n3 - k)
+            )
+        },
+        vec![
+            only_concludes("P-Zero", "Z plus n is n"),
+            only_concludes("P-Succ", "S(n1) plus n2 is S(n3)"),
+        ],
+    )
+}
+
+// why `n1 times n2 is n3` is not derivable, given n1 * n2 != n3
+pub fn get_failures_times(n1: usize, n2: usize, n3: usize, format: NumFormat) -> Vec<Failure> {
+    let mut failures = vec![only_concludes("T-Zero", "Z times n is Z")];
+    if n1 == 0 {
+        failures.push(only_concludes("T-Succ", "S(n1) times n2 is n4"));
+    } else {
+        // the first premise `n1 times n2 is n` is derivable only for this n
+        let n = (n1 - 1) * n2;
+        let premise = format!(
+            "{} plus {} is {}",
+            format.get_num(n2),
+            format.get_num(n),
+            format.get_num(n3)
+        );
+        let premise_failures = get_failures_plus(n2, n, n3, format);
+        failures.push(Failure::Premise(
+            "T-Succ".to_string(),
+            premise,
+            premise_failures,
+        ));
     }
+    failures
 }
 
 fn get_rule_times(n1: usize, n2: usize, n3: usize) -> RuleNode {
     if n1 == 0 {
         RuleNode::TZero(TZeroNode { nat_num: n2 })
     } else {
-        let premise1 = Box::new(get_rule_times(n1 - 1, n2, n3 - n2));
-        let premise2 = Box::new(get_rule_plus(n2, n3 - n2, n3));
-        RuleNode::TSucc(TSuccNode {
-            n1,
-            n2,
-            n3,
-            premise1,
-            premise2,
-        })
+        RuleNode::TSucc(TSuccNode { n1, n2, n3 })
     }
 }
 
@@ -53,13 +161,7 @@ fn get_rule_plus(n1: usize, n2: usize, n3: usize) -> RuleNode {
     if n1 == 0 {
         RuleNode::PZero(PZeroNode { nat_num: n2 })
     } else {
-        let premise = get_rule_plus(n1 - 1, n2, n3 - 1);
-        RuleNode::PSucc(PSuccNode {
-            n1,
-            n2,
-            n3,
-            premise: Box::new(premise),
-        })
+        RuleNode::PSucc(PSuccNode { n1, n2, n3 })
     }
 }
 
@@ -68,37 +170,29 @@ pub struct PZeroNode {
     nat_num: usize,
 }
 impl PZeroNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let n = get_peano_num(self.nat_num);
-        let _ = write!(w, "{}", get_depth_space(depth));
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "Z plus {} is {} by P-Zero {{}}{}", n, n, nl)
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let n = format.get_num(self.nat_num);
+        (format!("Z plus {} is {} by P-Zero", n, n), vec![])
     }
 }
 
+// the premises are made when the node is shown, so a node only holds its numbers
 #[derive(Debug, PartialEq, Clone)]
 pub struct PSuccNode {
     n1: usize,
     n2: usize,
     n3: usize,
-    premise: Box<RuleNode>,
 }
 impl PSuccNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let n1 = get_peano_num(self.n1);
-        let n2 = get_peano_num(self.n2);
-        let n3 = get_peano_num(self.n3);
-        let _ = write!(
-            w,
-            "{}{} plus {} is {} by P-Succ {{\n",
-            get_depth_space(depth),
-            n1,
-            n2,
-            n3
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let conclusion = format!(
+            "{} plus {} is {} by P-Succ",
+            format.get_num(self.n1),
+            format.get_num(self.n2),
+            format.get_num(self.n3)
         );
-        let _ = self.premise.show(w, depth + 2, true);
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+        let premise = get_rule_plus(self.n1 - 1, self.n2, self.n3 - 1);
+        (conclusion, vec![premise])
     }
 }
 
@@ -107,11 +201,9 @@ pub struct TZeroNode {
     nat_num: usize,
 }
 impl TZeroNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let n = get_peano_num(self.nat_num);
-        let _ = write!(w, "{}", get_depth_space(depth));
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "Z times {} is Z by T-Zero {{}}{}", n, nl)
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let n = format.get_num(self.nat_num);
+        (format!("Z times {} is Z by T-Zero", n), vec![])
     }
 }
 
@@ -120,26 +212,17 @@ pub struct TSuccNode {
     n1: usize,
     n2: usize,
     n3: usize,
-    premise1: Box<RuleNode>,
-    premise2: Box<RuleNode>,
 }
 impl TSuccNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let n1 = get_peano_num(self.n1);
-        let n2 = get_peano_num(self.n2);
-        let n3 = get_peano_num(self.n3);
-        let _ = write!(
-            w,
-            "{}{} times {} is {} by T-Succ {{\n",
-            get_depth_space(depth),
-            n1,
-            n2,
-            n3
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let conclusion = format!(
+            "{} times {} is {} by T-Succ",
+            format.get_num(self.n1),
+            format.get_num(self.n2),
+            format.get_num(self.n3)
         );
-        let _ = self.premise1.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
-        let _ = self.premise2.show(w, depth + 2, true);
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+        let premise1 = get_rule_times(self.n1 - 1, self.n2, self.n3 - self.n2);
+        let premise2 = get_rule_plus(self.n2, self.n3 - self.n2, self.n3);
+        (conclusion, vec![premise1, premise2])
     }
 }
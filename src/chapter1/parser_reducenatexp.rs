@@ -1,16 +1,14 @@
+use super::failure::{get_failure_message, only_concludes, Failure};
 use super::lexer::{Token, Tokens};
-use super::util::{get_depth_space, get_peano_num};
+use super::parser_nat::{get_failures_plus, get_failures_times};
+use super::util::{show_derivation, NumFormat};
 
+use std::collections::HashMap;
 use std::io::{self, Write};
 
 #[derive(Debug, Clone)]
 pub enum RuleNode {
-    MROne(MROneNode),
-    DRTimes(DRTimesNode),
-    DRPlusL(DRPlusLNode),
-    RPlus(RPlusNode),
-    RPlusR(RPlusRNode),
-    RTimes(RTimesNode),
+    Reduce(ReduceNode),
     PZero(PZeroNode),
     PSucc(PSuccNode),
     TZero(TZeroNode),
@@ -18,222 +16,457 @@ pub enum RuleNode {
 }
 
 impl RuleNode {
-    pub fn new(tokens: &mut Tokens) -> RuleNode {
-        let before_terms = Terms::new(tokens);
-        let eval_op = match tokens.pop().expect("eval operator") {
-            Token::EvalMR(_) => "-*->".to_string(),
-            Token::EvalDR(_) => "-d->".to_string(),
-            Token::EvalONE(_) => "--->".to_string(),
-            _ => panic!("TODO"),
+    pub fn new(tokens: &mut Tokens, format: NumFormat) -> Result<RuleNode, String> {
+        let before = Expression::new(tokens)?;
+        let arrow = match tokens.pop() {
+            Some(Token::EvalMR(_)) => "-*->",
+            Some(Token::EvalDR(_)) => "-d->",
+            Some(Token::EvalONE(_)) => "--->",
+            _ => return Err("expects ---> , -d-> or -*->".to_string()),
         };
-        let after_terms = Terms::new(tokens);
+        let after = Expression::new(tokens)?;
+        tokens.expect_end()?;
 
-        match eval_op.as_ref() {
-            "-*->" => RuleNode::MROne(MROneNode {
-                before_terms,
-                after_terms,
-            }),
-            "-d->" => RuleNode::DRPlusL(DRPlusLNode {
-                before_terms,
-                after_terms,
-            }),
-            "--->" => RuleNode::RPlusR(RPlusRNode {
-                before_terms,
-                after_terms,
-            }),
-            _ => panic!("TODO"),
+        let node = match arrow {
+            "-*->" => get_rule_multi(&before, &after),
+            "-d->" => get_rule_det(&before)
+                .filter(|(e, _)| *e == after)
+                .map(|(_, n)| n),
+            _ => get_rule_one(&before, &after),
+        };
+        match node {
+            Some(node) => Ok(node),
+            None => {
+                let summary = get_not_reduce_message(&before, arrow, &after, format);
+                let judgement = format!(
+                    "{} {} {}",
+                    before.to_string(format),
+                    arrow,
+                    after.to_string(format)
+                );
+                let failures = match arrow {
+                    "-*->" => get_failures_multi(&before, &after, format),
+                    "-d->" => get_failures_step(&before, &after, true, format),
+                    _ => get_failures_step(&before, &after, false, format),
+                };
+                Err(get_failure_message(&summary, &judgement, &failures))
+            }
         }
     }
-    pub fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
+    pub fn show<W: Write>(
+        self,
+        w: &mut W,
+        depth: usize,
+        with_newline: bool,
+        format: NumFormat,
+    ) -> io::Result<()> {
+        show_derivation(w, self, depth, with_newline, |node| node.get_step(format))
+    }
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
         match self {
-            RuleNode::MROne(node) => node.show(w, depth, with_newline),
-            RuleNode::DRTimes(node) => node.show(w, depth, with_newline),
-            RuleNode::DRPlusL(node) => node.show(w, depth, with_newline),
-            RuleNode::RPlus(node) => node.show(w, depth, with_newline),
-            RuleNode::RPlusR(node) => node.show(w, depth, with_newline),
-            RuleNode::RTimes(node) => node.show(w, depth, with_newline),
-            RuleNode::PZero(node) => node.show(w, depth, with_newline),
-            RuleNode::PSucc(node) => node.show(w, depth, with_newline),
-            RuleNode::TZero(node) => node.show(w, depth, with_newline),
-            RuleNode::TSucc(node) => node.show(w, depth, with_newline),
+            RuleNode::Reduce(node) => node.get_step(format),
+            RuleNode::PZero(node) => node.get_step(format),
+            RuleNode::PSucc(node) => node.get_step(format),
+            RuleNode::TZero(node) => node.get_step(format),
+            RuleNode::TSucc(node) => node.get_step(format),
         }
     }
 }
 
-fn get_rplus(terms: &mut Terms) -> RuleNode {
-    let (_, n1) = terms.pop().expect("");
-    let (_, n2) = terms.pop().expect("");
-    RuleNode::RPlus(RPlusNode { n1, n2 })
-}
-
-fn get_drtimes(terms: &mut Terms) -> RuleNode {
-    let (_, n1) = terms.pop().expect("");
-    let (_, n2) = terms.pop().expect("");
-    RuleNode::DRTimes(DRTimesNode { n1, n2 })
+fn get_not_reduce_message(
+    before: &Expression,
+    arrow: &str,
+    after: &Expression,
+    format: NumFormat,
+) -> String {
+    let reason = match arrow {
+        "-*->" => format!(
+            "`{}` does not reduce to `{}` in any number of steps",
+            before.to_string(format),
+            after.to_string(format)
+        ),
+        "-d->" => match get_rule_det(before) {
+            Some((e, _)) => format!(
+                "`{}` reduces to `{}` by -d->, not `{}`",
+                before.to_string(format),
+                e.to_string(format),
+                after.to_string(format)
+            ),
+            None => format!("`{}` does not reduce any further", before.to_string(format)),
+        },
+        _ => format!(
+            "`{}` does not reduce to `{}` in one step",
+            before.to_string(format),
+            after.to_string(format)
+        ),
+    };
+    format!("judgement does not hold: {}", reason)
 }
 
-fn get_rtimes_r(terms: &mut Terms) -> RuleNode {
-    terms.reverse();
-    let (_, n1) = terms.pop().expect("");
-    let (_, n2) = terms.pop().expect("");
-    terms.reverse();
-    RuleNode::RTimes(RTimesNode { n1, n2 })
-}
-
-fn get_rule_plus(n1: usize, n2: usize) -> RuleNode {
-    if n1 == 0 {
-        RuleNode::PZero(PZeroNode { n: n2 })
-    } else {
-        RuleNode::PSucc(PSuccNode { n1, n2 })
+// e -*-> e' by MR-Zero, MR-One or MR-Multi along the shortest path found
+fn get_rule_multi(before: &Expression, after: &Expression) -> Option<RuleNode> {
+    let path = find_path(before, after)?;
+    if path.len() == 1 {
+        return Some(RuleNode::Reduce(ReduceNode {
+            rule: "MR-Zero".to_string(),
+            arrow: "-*->",
+            before: before.clone(),
+            after: after.clone(),
+            premises: Vec::new(),
+        }));
     }
+    // e0 -*-> en by MR-Multi { e0 -*-> e1 by MR-One; e1 -*-> en by ... }
+    let mut node: Option<RuleNode> = None;
+    for i in (0..path.len() - 1).rev() {
+        let step = get_rule_one(&path[i], &path[i + 1]).expect("a one-step reduction");
+        let one = RuleNode::Reduce(ReduceNode {
+            rule: "MR-One".to_string(),
+            arrow: "-*->",
+            before: path[i].clone(),
+            after: path[i + 1].clone(),
+            premises: vec![step],
+        });
+        node = Some(match node {
+            None => one,
+            Some(rest) => RuleNode::Reduce(ReduceNode {
+                rule: "MR-Multi".to_string(),
+                arrow: "-*->",
+                before: path[i].clone(),
+                after: after.clone(),
+                premises: vec![one, rest],
+            }),
+        });
+    }
+    node
 }
 
-fn get_rule_times(n1: usize, n2: usize) -> RuleNode {
-    if n1 == 0 {
-        RuleNode::TZero(TZeroNode { n: n2 })
-    } else {
-        RuleNode::TSucc(TSuccNode { n1, n2 })
+// the expressions from `before` to `after`, each reducing to the next one by --->. the step
+// -d-> takes is tried first and another one only when that can't reach `after`, so no
+// expression is visited twice
+fn find_path(before: &Expression, after: &Expression) -> Option<Vec<Expression>> {
+    if !before.reduces_to(after) {
+        return None;
+    }
+    let mut path = vec![before.clone()];
+    while path.last().expect("") != after {
+        let e = path.last().expect("");
+        let next = match get_rule_det(e) {
+            Some((e_, _)) if e_.reduces_to(after) => e_,
+            _ => e
+                .reduce_all()
+                .into_iter()
+                .map(|(_, e_)| e_)
+                .find(|e_| e_.reduces_to(after))
+                .expect("a step toward an expression reachable by -*->"),
+        };
+        path.push(next);
     }
+    Some(path)
 }
 
-#[derive(Debug, Clone)]
-pub struct DRTimesNode {
-    n1: usize,
-    n2: usize,
+// why `before -*-> after` is not derivable, given before doesn't reduce to after
+fn get_failures_multi(before: &Expression, after: &Expression, format: NumFormat) -> Vec<Failure> {
+    let before_str = before.to_string(format);
+    let after_str = after.to_string(format);
+    let multi = format!(
+        "MR-Multi requires `{} -*-> e` and `e -*-> {}` for some e, but no such e exists",
+        before_str, after_str
+    );
+    vec![
+        only_concludes("MR-Zero", "e -*-> e"),
+        Failure::Premise(
+            "MR-One".to_string(),
+            format!("{} ---> {}", before_str, after_str),
+            get_failures_step(before, after, false, format),
+        ),
+        Failure::Conclusion(multi),
+    ]
 }
-impl DRTimesNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
-            w,
-            "{}{} * {} -d-> {} by DR-Times {{\n",
-            get_depth_space(depth),
-            get_peano_num(self.n1),
-            get_peano_num(self.n2),
-            get_peano_num(self.n1 * self.n2),
-        );
 
-        let premise = get_rule_times(self.n1, self.n2);
-        let _ = premise.show(w, depth + 2, true);
+// why `before ---> after` is not derivable, or `before -d-> after` when `det` is true. the
+// rules are those for the operator of before, and only the operands of after can be premises
+fn get_failures_step(
+    before: &Expression,
+    after: &Expression,
+    det: bool,
+    format: NumFormat,
+) -> Vec<Failure> {
+    let (prefix, arrow) = if det { ("DR", "-d->") } else { ("R", "--->") };
+    let (name, symbol, e1, e2) = match before {
+        Expression::Num(_) => {
+            let reason = format!("no rule concludes `n {} e`", arrow);
+            return vec![Failure::Conclusion(reason)];
+        }
+        Expression::Plus(e1, e2) => ("Plus", "+", e1, e2),
+        Expression::Times(e1, e2) => ("Times", "*", e1, e2),
+    };
+    let operands = match after {
+        Expression::Plus(a1, a2) | Expression::Times(a1, a2) if before.is_same_op(after) => {
+            Some((a1, a2))
+        }
+        _ => None,
+    };
+    let mut failures = Vec::new();
 
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+    let rule = format!("{}-{}", prefix, name);
+    match (&**e1, &**e2, after) {
+        (Expression::Num(n1), Expression::Num(n2), Expression::Num(n3)) => {
+            let op = name.to_lowercase();
+            let premise = format!(
+                "{} {} {} is {}",
+                format.get_num(*n1),
+                op,
+                format.get_num(*n2),
+                format.get_num(*n3)
+            );
+            let premise_failures = match name {
+                "Plus" => get_failures_plus(*n1, *n2, *n3, format),
+                _ => get_failures_times(*n1, *n2, *n3, format),
+            };
+            failures.push(Failure::Premise(rule, premise, premise_failures));
+        }
+        _ => {
+            let form = format!("n1 {} n2 {} n3", symbol, arrow);
+            failures.push(only_concludes(&rule, &form));
+        }
     }
-}
-
-#[derive(Debug, Clone)]
-pub struct DRPlusLNode {
-    before_terms: Terms,
-    after_terms: Terms,
-}
-impl DRPlusLNode {
-    fn show<W: Write>(mut self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
-            w,
-            "{}{} -d-> {} by DR-PlusL {{\n",
-            get_depth_space(depth),
-            self.before_terms.clone().to_string(),
-            self.after_terms.to_string(),
-        );
 
-        let premise = get_drtimes(&mut self.before_terms);
-        let _ = premise.show(w, depth + 2, true);
+    let rule = format!("{}-{}L", prefix, name);
+    match operands {
+        Some((a1, a2)) if a2 == e2 => {
+            let premise = format!(
+                "{} {} {}",
+                e1.to_string(format),
+                arrow,
+                a1.to_string(format)
+            );
+            let premise_failures = get_failures_step(e1, a1, det, format);
+            failures.push(Failure::Premise(rule, premise, premise_failures));
+        }
+        _ => {
+            let form = format!("e1 {} e2 {} e1' {} e2", symbol, arrow, symbol);
+            failures.push(only_concludes(&rule, &form));
+        }
+    }
 
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+    // -d-> reduces the right operand only after the left one is a number
+    let rule = format!("{}-{}R", prefix, name);
+    match operands {
+        Some((a1, a2)) if a1 == e1 && (!det || matches!(**e1, Expression::Num(_))) => {
+            let premise = format!(
+                "{} {} {}",
+                e2.to_string(format),
+                arrow,
+                a2.to_string(format)
+            );
+            let premise_failures = get_failures_step(e2, a2, det, format);
+            failures.push(Failure::Premise(rule, premise, premise_failures));
+        }
+        _ if det => {
+            let form = format!("n1 {} e2 {} n1 {} e2'", symbol, arrow, symbol);
+            failures.push(only_concludes(&rule, &form));
+        }
+        _ => {
+            let form = format!("e1 {} e2 {} e1 {} e2'", symbol, arrow, symbol);
+            failures.push(only_concludes(&rule, &form));
+        }
     }
+    failures
 }
 
+pub enum GraphFormat {
+    Dot,
+    Text,
+}
+
+// every expression reachable from a term by ---> and the one-step reductions between them
 #[derive(Debug, Clone)]
-pub struct MROneNode {
-    before_terms: Terms,
-    after_terms: Terms,
+pub struct ReductionGraph {
+    // in the order of breadth-first search from the term
+    nodes: Vec<Expression>,
+    // (from, to, rules)
+    edges: Vec<(usize, usize, String)>,
 }
-impl MROneNode {
-    fn show<W: Write>(mut self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
-            w,
-            "{}{} -*-> {} by MR-One {{\n",
-            get_depth_space(depth),
-            self.before_terms.clone().to_string(),
-            self.after_terms.to_string(),
-        );
+impl ReductionGraph {
+    pub fn new(tokens: &mut Tokens) -> Result<ReductionGraph, String> {
+        let term = Expression::new(tokens)?;
+        tokens.expect_end()?;
+        let mut indices: HashMap<Expression, usize> = HashMap::new();
+        let mut nodes = vec![term.clone()];
+        let mut edges = Vec::new();
+        indices.insert(term, 0);
+        let mut i = 0;
+        while i < nodes.len() {
+            for (rule, reduced) in nodes[i].reduce_all() {
+                let j = match indices.get(&reduced) {
+                    Some(j) => *j,
+                    None => {
+                        indices.insert(reduced.clone(), nodes.len());
+                        nodes.push(reduced);
+                        nodes.len() - 1
+                    }
+                };
+                edges.push((i, j, rule));
+            }
+            i += 1;
+        }
+        Ok(ReductionGraph { nodes, edges })
+    }
 
-        let premise = get_rplus(&mut self.before_terms);
-        let _ = premise.show(w, depth + 2, true);
+    pub fn show<W: Write>(
+        self,
+        w: &mut W,
+        format: GraphFormat,
+        num_format: NumFormat,
+    ) -> io::Result<()> {
+        match format {
+            GraphFormat::Dot => self.show_dot(w, num_format),
+            GraphFormat::Text => self.show_text(w, num_format),
+        }
+    }
 
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+    fn show_dot<W: Write>(self, w: &mut W, format: NumFormat) -> io::Result<()> {
+        writeln!(w, "digraph reduction {{")?;
+        for (i, node) in self.nodes.iter().enumerate() {
+            writeln!(w, "  n{} [label=\"{}\"];", i, node.to_string(format))?;
+        }
+        for (from, to, rule) in self.edges.iter() {
+            writeln!(w, "  n{} -> n{} [label=\"{}\"];", from, to, rule)?;
+        }
+        writeln!(w, "}}")
+    }
+
+    // each expression followed by the expressions it reduces to in one step
+    fn show_text<W: Write>(self, w: &mut W, format: NumFormat) -> io::Result<()> {
+        for (i, node) in self.nodes.iter().enumerate() {
+            writeln!(w, "{}", node.to_string(format))?;
+            for (_, to, rule) in self.edges.iter().filter(|(from, _, _)| *from == i) {
+                writeln!(
+                    w,
+                    "  ---> {} by {}",
+                    self.nodes[*to].to_string(format),
+                    rule
+                )?;
+            }
+            if !self.edges.iter().any(|(from, _, _)| *from == i) {
+                writeln!(w, "  (normal form)")?;
+            }
+        }
+        Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct RPlusNode {
-    n1: usize,
-    n2: usize,
+// e ---> e' by R-Plus, R-Times, R-PlusL, R-PlusR, R-TimesL or R-TimesR
+fn get_rule_one(before: &Expression, after: &Expression) -> Option<RuleNode> {
+    let (name, e1, e2) = match before {
+        Expression::Num(_) => return None,
+        Expression::Plus(e1, e2) => ("Plus", e1, e2),
+        Expression::Times(e1, e2) => ("Times", e1, e2),
+    };
+    let (rule, premise) = match (&**e1, &**e2, after) {
+        (Expression::Num(n1), Expression::Num(n2), Expression::Num(_)) => {
+            (format!("R-{}", name), get_rule_nat(name, *n1, *n2))
+        }
+        (_, _, Expression::Plus(e1_, e2_)) | (_, _, Expression::Times(e1_, e2_))
+            if before.is_same_op(after) =>
+        {
+            if e2 == e2_ {
+                (format!("R-{}L", name), get_rule_one(e1, e1_)?)
+            } else if e1 == e1_ {
+                (format!("R-{}R", name), get_rule_one(e2, e2_)?)
+            } else {
+                return None;
+            }
+        }
+        _ => return None,
+    };
+    if (rule.ends_with("Plus") || rule.ends_with("Times")) && before.get_val() != after.get_val() {
+        return None;
+    }
+    Some(RuleNode::Reduce(ReduceNode {
+        rule,
+        arrow: "--->",
+        before: before.clone(),
+        after: after.clone(),
+        premises: vec![premise],
+    }))
 }
-impl RPlusNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
-            w,
-            "{}{} + {} ---> {} by R-Plus {{\n",
-            get_depth_space(depth),
-            get_peano_num(self.n1),
-            get_peano_num(self.n2),
-            get_peano_num(self.n1 + self.n2),
-        );
 
-        let premise = get_rule_plus(self.n1, self.n2);
-        let _ = premise.show(w, depth + 2, true);
+// the only e' with e -d-> e', reducing the left operand first
+fn get_rule_det(before: &Expression) -> Option<(Expression, RuleNode)> {
+    let (name, e1, e2) = match before {
+        Expression::Num(_) => return None,
+        Expression::Plus(e1, e2) => ("Plus", e1, e2),
+        Expression::Times(e1, e2) => ("Times", e1, e2),
+    };
+    let (rule, after, premise) = match (&**e1, &**e2) {
+        (Expression::Num(n1), Expression::Num(n2)) => {
+            let after = Expression::Num(before.get_val());
+            (format!("DR-{}", name), after, get_rule_nat(name, *n1, *n2))
+        }
+        (Expression::Num(_), _) => {
+            let (e2_, premise) = get_rule_det(e2)?;
+            let after = before.with_operands(e1.as_ref().clone(), e2_);
+            (format!("DR-{}R", name), after, premise)
+        }
+        _ => {
+            let (e1_, premise) = get_rule_det(e1)?;
+            let after = before.with_operands(e1_, e2.as_ref().clone());
+            (format!("DR-{}L", name), after, premise)
+        }
+    };
+    let node = RuleNode::Reduce(ReduceNode {
+        rule,
+        arrow: "-d->",
+        before: before.clone(),
+        after: after.clone(),
+        premises: vec![premise],
+    });
+    Some((after, node))
+}
 
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+fn get_rule_nat(name: &str, n1: usize, n2: usize) -> RuleNode {
+    match name {
+        "Plus" => get_rule_plus(n1, n2),
+        _ => get_rule_times(n1, n2),
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct RPlusRNode {
-    before_terms: Terms,
-    after_terms: Terms,
+fn get_rule_plus(n1: usize, n2: usize) -> RuleNode {
+    if n1 == 0 {
+        RuleNode::PZero(PZeroNode { n: n2 })
+    } else {
+        RuleNode::PSucc(PSuccNode { n1, n2 })
+    }
 }
-impl RPlusRNode {
-    fn show<W: Write>(mut self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
-            w,
-            "{}{} ---> {} by R-PlusR {{\n",
-            get_depth_space(depth),
-            self.before_terms.clone().to_string(),
-            self.after_terms.to_string(),
-        );
-
-        let premise = get_rtimes_r(&mut self.before_terms);
-        let _ = premise.show(w, depth + 2, true);
 
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+fn get_rule_times(n1: usize, n2: usize) -> RuleNode {
+    if n1 == 0 {
+        RuleNode::TZero(TZeroNode { n: n2 })
+    } else {
+        RuleNode::TSucc(TSuccNode { n1, n2 })
     }
 }
 
+// a judgement on reduction and the derivations of its premises
 #[derive(Debug, Clone)]
-pub struct RTimesNode {
-    n1: usize,
-    n2: usize,
+pub struct ReduceNode {
+    rule: String,
+    arrow: &'static str,
+    before: Expression,
+    after: Expression,
+    premises: Vec<RuleNode>,
 }
-impl RTimesNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
-            w,
-            "{}{} * {} ---> {} by R-Times {{\n",
-            get_depth_space(depth),
-            get_peano_num(self.n1),
-            get_peano_num(self.n2),
-            get_peano_num(self.n1 * self.n2),
+impl ReduceNode {
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let conclusion = format!(
+            "{} {} {} by {}",
+            self.before.to_string(format),
+            self.arrow,
+            self.after.to_string(format),
+            self.rule,
         );
-
-        let premise = get_rule_times(self.n1, self.n2);
-        let _ = premise.show(w, depth + 2, true);
-
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+        (conclusion, self.premises)
     }
 }
 
@@ -242,11 +475,9 @@ pub struct PZeroNode {
     n: usize,
 }
 impl PZeroNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let n = get_peano_num(self.n);
-        let _ = write!(w, "{}", get_depth_space(depth));
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "Z plus {} is {} by P-Zero {{}}{}", n, n, nl)
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let n = format.get_num(self.n);
+        (format!("Z plus {} is {} by P-Zero", n, n), vec![])
     }
 }
 
@@ -256,19 +487,15 @@ pub struct PSuccNode {
     n2: usize,
 }
 impl PSuccNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
-            w,
-            "{}{} plus {} is {} by P-Succ {{\n",
-            get_depth_space(depth),
-            get_peano_num(self.n1),
-            get_peano_num(self.n2),
-            get_peano_num(self.n1 + self.n2),
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let conclusion = format!(
+            "{} plus {} is {} by P-Succ",
+            format.get_num(self.n1),
+            format.get_num(self.n2),
+            format.get_num(self.n1 + self.n2),
         );
         let premise = get_rule_plus(self.n1 - 1, self.n2);
-        let _ = premise.show(w, depth + 2, true);
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+        (conclusion, vec![premise])
     }
 }
 #[derive(Debug, PartialEq, Clone)]
@@ -276,11 +503,9 @@ pub struct TZeroNode {
     n: usize,
 }
 impl TZeroNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let n = get_peano_num(self.n);
-        let _ = write!(w, "{}", get_depth_space(depth));
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "Z times {} is Z by T-Zero {{}}{}", n, nl)
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let n = format.get_num(self.n);
+        (format!("Z times {} is Z by T-Zero", n), vec![])
     }
 }
 
@@ -290,73 +515,145 @@ pub struct TSuccNode {
     n2: usize,
 }
 impl TSuccNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
-            w,
-            "{}{} times {} is {} by T-Succ {{\n",
-            get_depth_space(depth),
-            get_peano_num(self.n1),
-            get_peano_num(self.n2),
-            get_peano_num(self.n1 * self.n2),
+    fn get_step(self, format: NumFormat) -> (String, Vec<RuleNode>) {
+        let conclusion = format!(
+            "{} times {} is {} by T-Succ",
+            format.get_num(self.n1),
+            format.get_num(self.n2),
+            format.get_num(self.n1 * self.n2),
         );
         let premise1 = get_rule_times(self.n1 - 1, self.n2);
-        let _ = premise1.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
         let premise2 = get_rule_plus(self.n2, (self.n1 - 1) * self.n2);
-        let _ = premise2.show(w, depth + 2, true);
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
+        (conclusion, vec![premise1, premise2])
     }
 }
 
-#[derive(Debug, Clone)]
-struct Terms {
-    terms: Vec<(String, usize)>,
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum Expression {
+    Num(usize),
+    Plus(Box<Expression>, Box<Expression>),
+    Times(Box<Expression>, Box<Expression>),
 }
-impl Terms {
-    fn new(tokens: &mut Tokens) -> Terms {
-        let mut terms: Vec<(String, usize)> = Vec::new();
-        let num = tokens.consume_peano_num();
-        terms.push(("".to_string(), num));
-        loop {
-            match tokens.peek() {
-                Some(token) => match token {
-                    Token::OpC(operator, _) => {
-                        tokens.pop(); // consume operator
-                        let num = tokens.consume_peano_num();
-                        terms.push((operator, num));
-                    }
-                    Token::EvalMR(_) => break,
-                    Token::EvalDR(_) => break,
-                    Token::EvalONE(_) => break,
-                    _ => panic!("unexpected token: {:?}"),
-                },
-                None => break,
+impl Expression {
+    // e ::= t (+ t)*, t ::= a (* a)*, a ::= n | ( e )
+    pub(crate) fn new(tokens: &mut Tokens) -> Result<Expression, String> {
+        let mut e = Expression::new_times(tokens)?;
+        while let Some(Token::OpC(operator, _)) = tokens.peek() {
+            if operator != "+" {
+                break;
             }
+            tokens.pop(); // consume +
+            let e2 = Expression::new_times(tokens)?;
+            e = Expression::Plus(Box::new(e), Box::new(e2));
         }
-        Terms { terms }
-    }
-    fn pop(&mut self) -> Option<(String, usize)> {
-        self.terms.reverse();
-        let term = self.terms.pop();
-        self.terms.reverse();
-        term
-    }
-    fn reverse(&mut self) {
-        self.terms.reverse();
-    }
-    fn to_string(self) -> String {
-        let mut s = "".to_string();
-        let terms = self.terms.into_iter();
-        for (operator, num) in terms {
-            let snum = &get_peano_num(num);
-            match operator.as_ref() {
-                "" => s += snum,
-                "+" => s = s + " + " + snum,
-                "*" => s = s + " * " + snum,
-                _ => panic!("TODO"),
+        Ok(e)
+    }
+    fn new_times(tokens: &mut Tokens) -> Result<Expression, String> {
+        let mut e = Expression::new_atom(tokens)?;
+        while let Some(Token::OpC(operator, _)) = tokens.peek() {
+            if operator != "*" {
+                break;
+            }
+            tokens.pop(); // consume *
+            let e2 = Expression::new_atom(tokens)?;
+            e = Expression::Times(Box::new(e), Box::new(e2));
+        }
+        Ok(e)
+    }
+    fn new_atom(tokens: &mut Tokens) -> Result<Expression, String> {
+        match tokens.peek() {
+            Some(Token::ParenS(_)) => {
+                tokens.pop(); // consume (
+                let e = Expression::new(tokens)?;
+                tokens.consume_paren_end()?;
+                Ok(e)
+            }
+            // `?` can't be solved, since a term reduces to more than one term
+            _ => Ok(Expression::Num(tokens.consume_peano_num()?)),
+        }
+    }
+
+    pub(crate) fn get_val(&self) -> usize {
+        match self {
+            Expression::Num(n) => *n,
+            Expression::Plus(e1, e2) => e1.get_val() + e2.get_val(),
+            Expression::Times(e1, e2) => e1.get_val() * e2.get_val(),
+        }
+    }
+
+    // whether e -*-> e'. a step keeps the operator at the top until both of its operands are
+    // numbers, so e' is either the value of e or has the operator over operands e's reduce to
+    fn reduces_to(&self, other: &Expression) -> bool {
+        match (self, other) {
+            (_, Expression::Num(n)) => self.get_val() == *n,
+            (Expression::Plus(e1, e2), Expression::Plus(e1_, e2_))
+            | (Expression::Times(e1, e2), Expression::Times(e1_, e2_)) => {
+                e1.reduces_to(e1_) && e2.reduces_to(e2_)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_same_op(&self, other: &Expression) -> bool {
+        matches!(
+            (self, other),
+            (Expression::Plus(_, _), Expression::Plus(_, _))
+                | (Expression::Times(_, _), Expression::Times(_, _))
+        )
+    }
+
+    fn with_operands(&self, e1: Expression, e2: Expression) -> Expression {
+        match self {
+            Expression::Times(_, _) => Expression::Times(Box::new(e1), Box::new(e2)),
+            _ => Expression::Plus(Box::new(e1), Box::new(e2)),
+        }
+    }
+
+    // every e' with e ---> e' and the rules used, e.g. R-PlusL(R-Times), in the order
+    // R-Plus/R-Times, then the left operand first
+    fn reduce_all(&self) -> Vec<(String, Expression)> {
+        let (name, e1, e2) = match self {
+            Expression::Num(_) => return Vec::new(),
+            Expression::Plus(e1, e2) => ("Plus", e1, e2),
+            Expression::Times(e1, e2) => ("Times", e1, e2),
+        };
+        let mut reduced = Vec::new();
+        if let (Expression::Num(_), Expression::Num(_)) = (&**e1, &**e2) {
+            reduced.push((format!("R-{}", name), Expression::Num(self.get_val())));
+        }
+        for (rule, e1_) in e1.reduce_all() {
+            let rule = format!("R-{}L({})", name, rule);
+            reduced.push((rule, self.with_operands(e1_, e2.as_ref().clone())));
+        }
+        for (rule, e2_) in e2.reduce_all() {
+            let rule = format!("R-{}R({})", name, rule);
+            reduced.push((rule, self.with_operands(e1.as_ref().clone(), e2_)));
+        }
+        reduced
+    }
+
+    // + and * are left associative, and * binds tighter than +
+    pub fn to_string(&self, format: NumFormat) -> String {
+        match self {
+            Expression::Num(n) => format.get_num(*n),
+            Expression::Plus(e1, e2) => {
+                let e2 = match **e2 {
+                    Expression::Plus(_, _) => format!("({})", e2.to_string(format)),
+                    _ => e2.to_string(format),
+                };
+                format!("{} + {}", e1.to_string(format), e2)
+            }
+            Expression::Times(e1, e2) => {
+                let e1 = match **e1 {
+                    Expression::Plus(_, _) => format!("({})", e1.to_string(format)),
+                    _ => e1.to_string(format),
+                };
+                let e2 = match **e2 {
+                    Expression::Num(_) => e2.to_string(format),
+                    _ => format!("({})", e2.to_string(format)),
+                };
+                format!("{} * {}", e1, e2)
             }
         }
-        s
     }
 }
@@ -1,20 +1,107 @@
-pub fn get_peano_num(nat_num: usize) -> String {
-    let mut s = "".to_string();
+use std::io::{self, Write};
 
-    for _ in 0..nat_num {
-        s += "S(";
+// how natural numbers are written in derivations
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum NumFormat {
+    // S(S(Z)), as in the book
+    Peano,
+    // #2, which the lexer also accepts. Z stays Z as the rules spell it out
+    Decimal,
+}
+impl NumFormat {
+    pub fn get_num(self, nat_num: usize) -> String {
+        match self {
+            NumFormat::Decimal if nat_num != 0 => format!("#{}", nat_num),
+            _ => get_peano_num(nat_num),
+        }
     }
+}
+
+pub fn get_peano_num(nat_num: usize) -> String {
+    let mut s = String::with_capacity(3 * nat_num + 1);
+    s += &"S(".repeat(nat_num);
     s += "Z";
-    for _ in 0..nat_num {
-        s += ")";
-    }
+    s += &")".repeat(nat_num);
     s
 }
 
+// the message for a judgement whose stated result differs from the derivable one
+pub fn get_not_hold_message(expected: &str, derivable: &str) -> String {
+    format!(
+        "judgement does not hold: expected {}, derivable value is {}",
+        expected, derivable
+    )
+}
+
+// compares the result stated after the relation with the derivable one, both lexed alike.
+// nothing stated means the result is left to be computed. `get_stated` recovers the stated
+// text from the number of its tokens for the message
+pub fn check_stated_result<T: PartialEq>(
+    stated: &[T],
+    derivable: &str,
+    lex: impl FnOnce(String) -> Vec<T>,
+    get_stated: impl FnOnce(usize) -> String,
+) -> Result<(), String> {
+    if stated.is_empty() || stated == lex(derivable.to_string()).as_slice() {
+        return Ok(());
+    }
+    Err(get_not_hold_message(&get_stated(stated.len()), derivable))
+}
+
+// the message for a judgement of an ML system that no rule can conclude, e.g. `1 + true`
+pub fn get_no_rule_message(expression_str: &str) -> String {
+    format!(
+        "judgement does not hold: no rule applies to {}",
+        expression_str
+    )
+}
+
 pub fn get_depth_space(depth: usize) -> String {
-    let mut s = "".to_string();
-    for _ in 0..depth {
-        s += " ";
+    " ".repeat(depth)
+}
+
+enum Step<N> {
+    Node(N, usize, bool),
+    Text(String),
+}
+
+// writes the derivation of `root`, where `get_step` gives the conclusion of a node with
+// its rule, e.g. `Z plus Z is Z by P-Zero`, and its premises. an explicit stack is used
+// instead of recursion so that derivations deeper than the call stack can be written
+pub fn show_derivation<N, W: Write>(
+    w: &mut W,
+    root: N,
+    depth: usize,
+    with_newline: bool,
+    get_step: impl Fn(N) -> (String, Vec<N>),
+) -> io::Result<()> {
+    let mut stack = vec![Step::Node(root, depth, with_newline)];
+    while let Some(step) = stack.pop() {
+        let (node, depth, with_newline) = match step {
+            Step::Node(node, depth, with_newline) => (node, depth, with_newline),
+            Step::Text(s) => {
+                write!(w, "{}", s)?;
+                continue;
+            }
+        };
+        let nl = if with_newline { "\n" } else { "" };
+        let (conclusion, premises) = get_step(node);
+        if premises.is_empty() {
+            write!(w, "{}{} {{}}{}", get_depth_space(depth), conclusion, nl)?;
+            continue;
+        }
+        writeln!(w, "{}{} {{", get_depth_space(depth), conclusion)?;
+        stack.push(Step::Text(format!("{}}}{}", get_depth_space(depth), nl)));
+        // pushed in reverse so that the first premise is written first
+        let len = premises.len();
+        for (i, premise) in premises.into_iter().enumerate().rev() {
+            if i + 1 == len {
+                stack.push(Step::Node(premise, depth + 2, true));
+            } else {
+                stack.push(Step::Text(";\n".to_string()));
+                stack.push(Step::Node(premise, depth + 2, false));
+            }
+        }
     }
-    s
+    Ok(())
 }
@@ -0,0 +1,293 @@
+mod derivation;
+mod evalml;
+mod hole;
+mod nat;
+
+use derivation::Derivation;
+
+use std::fmt;
+
+use crate::chapter1::util::NumFormat;
+use crate::chapter1::{self, DerivationRules};
+use crate::{parser_evalml1, parser_evalml2, parser_evalml3};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum System {
+    Nat,
+    CompareNat1,
+    CompareNat2,
+    CompareNat3,
+    EvalNatExp,
+    ReduceNatExp,
+    EvalML1,
+    EvalML2,
+    EvalML3,
+}
+impl fmt::Display for System {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CheckError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+impl CheckError {
+    fn new(text: &str, offset: usize, message: String) -> CheckError {
+        let before = &text[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = match before.rfind('\n') {
+            Some(i) => before[i + 1..].chars().count() + 1,
+            None => before.chars().count() + 1,
+        };
+        CheckError {
+            line,
+            column,
+            message,
+        }
+    }
+}
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+// the lexer of the derivers of the system, which the judgements are read with
+enum Lexer {
+    Nat(chapter1::lexer::Lexer),
+    EvalML(parser_evalml3::lexer::Lexer),
+}
+impl Lexer {
+    fn new(system: &System) -> Lexer {
+        match system {
+            System::EvalML1 | System::EvalML2 | System::EvalML3 => {
+                Lexer::EvalML(parser_evalml3::lexer::Lexer::new())
+            }
+            _ => Lexer::Nat(chapter1::lexer::Lexer::new()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Judgement {
+    Nat(nat::Judgement),
+    EvalML(evalml::Judgement),
+}
+impl Judgement {
+    fn new(lexer: &Lexer, judgement: &str, system: &System) -> Result<Judgement, String> {
+        match lexer {
+            Lexer::Nat(lexer) => {
+                let mut tokens = lexer.lex(judgement.to_string());
+                tokens.check_invalid()?;
+                Ok(Judgement::Nat(nat::Judgement::new(&mut tokens, system)?))
+            }
+            Lexer::EvalML(lexer) => Ok(Judgement::EvalML(evalml::Judgement::new(
+                lexer, judgement, system,
+            )?)),
+        }
+    }
+
+    fn holds(&self) -> bool {
+        match self {
+            Judgement::Nat(judgement) => judgement.holds(),
+            Judgement::EvalML(judgement) => judgement.holds(),
+        }
+    }
+}
+impl fmt::Display for Judgement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Judgement::Nat(judgement) => write!(f, "{}", judgement),
+            Judgement::EvalML(judgement) => write!(f, "{}", judgement),
+        }
+    }
+}
+
+// the premises the rule requires for the conclusion, where None is a premise yet unknown
+fn solve_premises(
+    system: &System,
+    rule: &str,
+    conclusion: &Judgement,
+    premises: &[Option<Judgement>],
+) -> Result<Vec<Judgement>, String> {
+    match conclusion {
+        Judgement::Nat(conclusion) => {
+            let mut nat_premises = Vec::new();
+            for premise in premises.iter() {
+                nat_premises.push(match premise {
+                    Some(Judgement::Nat(premise)) => Some(premise.clone()),
+                    Some(_) => return Err("expects a judgement on natural numbers".to_string()),
+                    None => None,
+                });
+            }
+            let solved = nat::solve_premises(system, rule, conclusion, &nat_premises)?;
+            Ok(solved.into_iter().map(Judgement::Nat).collect())
+        }
+        Judgement::EvalML(conclusion) => {
+            let mut evalml_premises = Vec::new();
+            for premise in premises.iter() {
+                evalml_premises.push(match premise {
+                    Some(Judgement::EvalML(premise)) => Some(premise.clone()),
+                    Some(_) => return Err("expects a judgement of EvalML".to_string()),
+                    None => None,
+                });
+            }
+            let solved = evalml::solve_premises(system, rule, conclusion, &evalml_premises)?;
+            Ok(solved.into_iter().map(Judgement::EvalML).collect())
+        }
+    }
+}
+
+// the derivation the deriver of the system outputs for the judgement
+fn derive(lexer: &Lexer, judgement: &str, system: &System) -> String {
+    // the derivers of EvalML only take `evalto`, and B-Plus, ... have no premises
+    let parsed = Judgement::new(lexer, judgement, system);
+    if let Ok(Judgement::EvalML(parsed)) = &parsed {
+        if let Some(rule) = parsed.get_bop_rule() {
+            return format!("{} by {} {{}}", judgement, rule);
+        }
+    }
+    // nor do those of EvalNatExp and ReduceNatExp take `plus` and `times`, derived by Nat
+    let is_nat_operation = matches!(
+        parsed,
+        Ok(Judgement::Nat(nat::Judgement::Plus(..)))
+            | Ok(Judgement::Nat(nat::Judgement::Times(..)))
+    );
+    let mut buf = Vec::<u8>::new();
+    let rules = match system {
+        _ if is_nat_operation => DerivationRules::Nat,
+        System::Nat => DerivationRules::Nat,
+        System::CompareNat1 => DerivationRules::CompNat1,
+        System::CompareNat2 => DerivationRules::CompNat2,
+        System::CompareNat3 => DerivationRules::CompNat3,
+        System::EvalNatExp => DerivationRules::EvalNatExp,
+        System::ReduceNatExp => DerivationRules::ReduceNatExp,
+        System::EvalML1 => {
+            parser_evalml1::derive(judgement, &mut buf);
+            return String::from_utf8(buf).expect("expects result str");
+        }
+        System::EvalML2 => {
+            parser_evalml2::derive(judgement, &mut buf);
+            return String::from_utf8(buf).expect("expects result str");
+        }
+        System::EvalML3 => {
+            parser_evalml3::derive(judgement, &mut buf);
+            return String::from_utf8(buf).expect("expects result str");
+        }
+    };
+    let _ = chapter1::derive(judgement, rules, NumFormat::Peano, &mut buf);
+    String::from_utf8(buf).expect("expects result str")
+}
+
+// checks every rule application of the derivation written in the syntax `derive` outputs,
+// and reports the first invalid one
+pub fn check(text: &str, system: System) -> Result<(), CheckError> {
+    let derivation = Derivation::new(text)?;
+    if let Some(hole) = derivation.find_hole() {
+        let message = "unfilled hole".to_string();
+        return Err(CheckError::new(text, hole.start, message));
+    }
+    check_derivation(&Lexer::new(&system), text, &derivation, &system)
+}
+
+// completes the subtrees written as `?` or `judgement by ?` and leaves the rest of the
+// text as it is. holes that cannot be filled are reported
+pub fn fill(text: &str, system: System) -> Result<String, Vec<CheckError>> {
+    let mut derivation = Derivation::new(text).map_err(|error| vec![error])?;
+    let mut replacements = Vec::new();
+    let mut errors = Vec::new();
+    if derivation.is_hole() && derivation.judgement.is_empty() {
+        let message = "the conclusion cannot be a hole".to_string();
+        return Err(vec![CheckError::new(text, derivation.start, message)]);
+    }
+    // the lexer is made once, as it takes much longer to make than to use
+    let lexer = Lexer::new(&system);
+    hole::fill_holes(
+        &lexer,
+        text,
+        &mut derivation,
+        &system,
+        &mut replacements,
+        &mut errors,
+    );
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    // the filled subtrees are valid by construction, so only the user's steps are checked
+    check_derivation(&lexer, text, &derivation, &system).map_err(|error| vec![error])?;
+    Ok(hole::replace(text, replacements))
+}
+
+// walks the derivation with an explicit stack, since it can be deeper than the call stack
+fn check_derivation(
+    lexer: &Lexer,
+    text: &str,
+    derivation: &Derivation,
+    system: &System,
+) -> Result<(), CheckError> {
+    let mut stack = vec![derivation];
+    while let Some(derivation) = stack.pop() {
+        if derivation.is_hole() {
+            continue;
+        }
+        check_step(lexer, text, derivation, system)?;
+        // pushed in reverse so that the first premise is checked first
+        stack.extend(derivation.premises.iter().rev());
+    }
+    Ok(())
+}
+
+// checks the rule application at the root of the derivation
+fn check_step(
+    lexer: &Lexer,
+    text: &str,
+    derivation: &Derivation,
+    system: &System,
+) -> Result<(), CheckError> {
+    let parse = |derivation: &Derivation| {
+        Judgement::new(lexer, &derivation.judgement, system)
+            .map_err(|message| CheckError::new(text, derivation.start, message))
+    };
+    let conclusion = parse(derivation)?;
+    let mut premises = Vec::new();
+    for premise in derivation.premises.iter() {
+        premises.push((premise.start, parse(premise)?));
+    }
+    let mismatch = |start: usize, message: &str| CheckError::new(text, start, message.to_string());
+
+    let result = match conclusion {
+        Judgement::Nat(conclusion) => {
+            let mut nat_premises = Vec::new();
+            for (start, premise) in premises {
+                match premise {
+                    Judgement::Nat(premise) => nat_premises.push(premise),
+                    _ => return Err(mismatch(start, "expects a judgement on natural numbers")),
+                }
+            }
+            nat::check_rule(system, &derivation.rule, &conclusion, &nat_premises)
+        }
+        Judgement::EvalML(conclusion) => {
+            let mut evalml_premises = Vec::new();
+            for (start, premise) in premises {
+                match premise {
+                    Judgement::EvalML(premise) => evalml_premises.push(premise),
+                    _ => return Err(mismatch(start, "expects a judgement of EvalML")),
+                }
+            }
+            evalml::check_rule(system, &derivation.rule, &conclusion, &evalml_premises)
+        }
+    };
+    if let Err(message) = result {
+        let message = format!("invalid application of {}: {}", derivation.rule, message);
+        return Err(CheckError::new(text, derivation.start, message));
+    }
+    Ok(())
+}
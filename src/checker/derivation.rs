@@ -0,0 +1,202 @@
+use super::CheckError;
+
+// a derivation as written by the user, before its judgements are parsed
+#[derive(Debug, Clone)]
+pub struct Derivation {
+    pub judgement: String,
+    pub rule: String,
+    pub premises: Vec<Derivation>,
+    // byte offsets of the derivation in the whole text
+    pub start: usize,
+    pub end: usize,
+}
+impl Derivation {
+    pub fn new(text: &str) -> Result<Derivation, CheckError> {
+        let mut parser = Parser { text, position: 0 };
+        let derivation = parser.parse_derivation()?;
+        parser.skip_spaces();
+        if parser.position != text.len() {
+            return Err(parser.error("unexpected text after the derivation"));
+        }
+        Ok(derivation)
+    }
+
+    // `?` in place of a premise, or `judgement by ?`
+    pub fn is_hole(&self) -> bool {
+        self.rule == "?"
+    }
+
+    pub fn find_hole(&self) -> Option<&Derivation> {
+        let mut stack = vec![self];
+        while let Some(derivation) = stack.pop() {
+            if derivation.is_hole() {
+                return Some(derivation);
+            }
+            stack.extend(derivation.premises.iter().rev());
+        }
+        None
+    }
+}
+// the premises are dropped one by one, since dropping them recursively overflows the stack
+// for deep derivations
+impl Drop for Derivation {
+    fn drop(&mut self) {
+        let mut premises = std::mem::take(&mut self.premises);
+        while let Some(mut premise) = premises.pop() {
+            premises.append(&mut premise.premises);
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+impl<'a> Parser<'a> {
+    // derivation ::= judgement by Rule { [derivation (; derivation)* [;]] }
+    //              | judgement by ? | ?
+    // the derivations whose premises are being parsed are kept on an explicit stack, so that
+    // derivations deeper than the call stack can be parsed
+    fn parse_derivation(&mut self) -> Result<Derivation, CheckError> {
+        let mut stack: Vec<Derivation> = Vec::new();
+        loop {
+            let mut derivation = self.parse_conclusion()?;
+            if derivation.end == 0 {
+                self.skip_spaces();
+                match self.peek_char() {
+                    Some('}') => {
+                        self.position += 1;
+                        derivation.end = self.position;
+                    }
+                    None => return Err(self.error("expected `}`")),
+                    _ => {
+                        stack.push(derivation);
+                        continue;
+                    }
+                }
+            }
+            // the derivation is complete, and so are its parents whose `}` follows it
+            loop {
+                let parent = match stack.last_mut() {
+                    Some(parent) => parent,
+                    None => return Ok(derivation),
+                };
+                parent.premises.push(derivation);
+                self.skip_spaces();
+                match self.peek_char() {
+                    Some(';') => {
+                        self.position += 1;
+                        self.skip_spaces();
+                        match self.peek_char() {
+                            Some('}') => self.position += 1,
+                            None => return Err(self.error("expected `}`")),
+                            _ => break,
+                        }
+                    }
+                    Some('}') => self.position += 1,
+                    _ => return Err(self.error("expected `;` or `}` after a premise")),
+                }
+                derivation = stack.pop().expect("a parent");
+                derivation.end = self.position;
+            }
+        }
+    }
+
+    // `judgement by Rule {`, `judgement by ?` or `?`. the end of the first is left as 0
+    // until its premises are parsed
+    fn parse_conclusion(&mut self) -> Result<Derivation, CheckError> {
+        self.skip_spaces();
+        let start = self.position;
+        if self.peek_char() == Some('?') {
+            self.position += 1;
+            return Ok(Derivation {
+                judgement: "".to_string(),
+                rule: "?".to_string(),
+                premises: Vec::new(),
+                start,
+                end: self.position,
+            });
+        }
+        let by = match find_keyword(&self.text[start..], "by") {
+            Some(by) => start + by,
+            None => return Err(self.error("expected `by` after the judgement")),
+        };
+        let judgement = self.text[start..by].trim().to_string();
+        if judgement.is_empty() {
+            return Err(self.error("expected a judgement"));
+        }
+        self.position = by + "by".len();
+        self.skip_spaces();
+        if self.peek_char() == Some('?') {
+            self.position += 1;
+            return Ok(Derivation {
+                judgement,
+                rule: "?".to_string(),
+                premises: Vec::new(),
+                start,
+                end: self.position,
+            });
+        }
+        let rule = self.consume_rule_name()?;
+        self.skip_spaces();
+        self.consume_char('{')?;
+        Ok(Derivation {
+            judgement,
+            rule,
+            premises: Vec::new(),
+            start,
+            end: 0,
+        })
+    }
+
+    fn consume_rule_name(&mut self) -> Result<String, CheckError> {
+        let rest = &self.text[self.position..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected a rule name after `by`"));
+        }
+        self.position += len;
+        Ok(rest[..len].to_string())
+    }
+
+    fn consume_char(&mut self, c: char) -> Result<(), CheckError> {
+        if self.peek_char() == Some(c) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", c)))
+        }
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn skip_spaces(&mut self) {
+        let rest = &self.text[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn error(&self, message: &str) -> CheckError {
+        CheckError::new(self.text, self.position, message.to_string())
+    }
+}
+
+// the first occurrence of `keyword` as a whole word
+fn find_keyword(text: &str, keyword: &str) -> Option<usize> {
+    let is_word_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '\'';
+    let mut offset = 0;
+    while let Some(i) = text[offset..].find(keyword) {
+        let begin = offset + i;
+        let end = begin + keyword.len();
+        let before = text[..begin].chars().last();
+        let after = text[end..].chars().next();
+        if !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char) {
+            return Some(begin);
+        }
+        offset = end;
+    }
+    None
+}
@@ -0,0 +1,598 @@
+use std::fmt;
+
+use super::System;
+
+use crate::parser_evalml3::expression::Expression;
+use crate::parser_evalml3::lexer::{Lexer, Token, Tokens};
+use crate::parser_evalml3::terms::{AppTerm, FunTerm, IfTerm, LetRecTerm, LetTerm, Term};
+use crate::parser_evalml3::value::{Environment, Value};
+
+// an expression seen through its parentheses: an operation or a single term
+enum View<'a> {
+    BinOp(String, Expression, Expression),
+    Term(&'a Term),
+}
+fn get_view(expression: &Expression) -> View<'_> {
+    let mut expression = expression;
+    while let [(_, Term::Paren(inner))] = expression.terms.as_slice() {
+        expression = inner;
+    }
+    match expression.terms.as_slice() {
+        [(_, term)] => View::Term(term),
+        _ => {
+            let (split_position, op) = expression.get_split_position();
+            let (e1, e2) = expression.get_splitted_expression(split_position);
+            View::BinOp(op, e1, e2)
+        }
+    }
+}
+
+fn from_term(term: &Term) -> Expression {
+    Expression {
+        terms: vec![("".to_string(), term.clone())],
+    }
+}
+
+fn push(environment: &Environment, x: &str, value: Value) -> Environment {
+    let mut environment = environment.clone();
+    environment.set_val(x.to_string(), value);
+    environment
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Judgement {
+    // the judgements of EvalML1 have no environment
+    Eval(Option<Environment>, Expression, Value),
+    // i1 plus i2 is i3, i1 less than i2 is b, ...
+    BOp(String, i32, i32, Value),
+}
+impl Judgement {
+    pub fn new(lexer: &Lexer, judgement: &str, system: &System) -> Result<Judgement, String> {
+        if let Some(unknown) = lexer.find_unknown(judgement) {
+            return Err(format!("unexpected `{}`", unknown));
+        }
+        let mut tokens = lexer.lex(judgement.to_string());
+        Judgement::parse(&mut tokens, system)
+            .map_err(|expected| lexer.get_syntax_error(judgement, &tokens, &expected))
+    }
+    fn parse(tokens: &mut Tokens, system: &System) -> Result<Judgement, String> {
+        let judgement = match (tokens.tokens.first(), tokens.tokens.get(1)) {
+            (Some(Token::Int(_)), Some(Token::Var(op)))
+                if op == "plus" || op == "minus" || op == "times" || op == "less" =>
+            {
+                let op = op.clone();
+                let i1 = tokens.try_consume_num()?;
+                tokens.pop(); // consume operator
+                let op = if op == "less" {
+                    tokens.expect(Token::Var("than".to_string()), "than")?;
+                    "less than".to_string()
+                } else {
+                    op
+                };
+                let i2 = tokens.try_consume_num()?;
+                tokens.expect(Token::Var("is".to_string()), "is")?;
+                Judgement::BOp(op, i1, i2, Value::try_new(tokens)?)
+            }
+            _ => {
+                let environment = match system {
+                    System::EvalML1 => None,
+                    _ => {
+                        let environment = Environment::try_new(tokens, Value::try_new)?;
+                        tokens.expect(Token::ENV, "|-")?;
+                        Some(environment)
+                    }
+                };
+                let expression = Expression::try_new(tokens)?;
+                tokens.expect(Token::Eval("evalto".to_string()), "evalto")?;
+                Judgement::Eval(environment, expression, Value::try_new(tokens)?)
+            }
+        };
+        if !tokens.tokens.is_empty() {
+            return Err("expects the end of the judgement".to_string());
+        }
+        Ok(judgement)
+    }
+
+    // the rule that concludes `i1 plus i2 is i3`, ...
+    pub fn get_bop_rule(&self) -> Option<&'static str> {
+        match self {
+            Judgement::BOp(op, _, _, _) => match op.as_ref() {
+                "plus" => Some("B-Plus"),
+                "minus" => Some("B-Minus"),
+                "times" => Some("B-Times"),
+                _ => Some("B-Lt"),
+            },
+            Judgement::Eval(_, _, _) => None,
+        }
+    }
+
+    // `evalto` is decided by the derivers, so only `i1 plus i2 is i3`, ... are decided here
+    pub fn holds(&self) -> bool {
+        match self.get_bop_rule() {
+            Some(rule) => get_premises(rule, self, &[]).is_ok(),
+            None => true,
+        }
+    }
+}
+
+impl fmt::Display for Judgement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Judgement::Eval(None, e, v) => write!(f, "{} evalto {}", e, v),
+            Judgement::Eval(Some(environment), e, v) => {
+                write!(f, "{}{} evalto {}", environment, e, v)
+            }
+            Judgement::BOp(op, i1, i2, v) => write!(f, "{} {} {} is {}", i1, op, i2, v),
+        }
+    }
+}
+
+fn get_rules(system: &System) -> Vec<String> {
+    let mut rules: Vec<String> = vec![
+        "E-Int",
+        "E-Bool",
+        "E-IfT",
+        "E-IfF",
+        "E-IfTError",
+        "E-IfFError",
+        "E-IfInt",
+        "E-IfError",
+        "E-Plus",
+        "E-Minus",
+        "E-Times",
+        "E-Lt",
+        "B-Plus",
+        "B-Minus",
+        "B-Times",
+        "B-Lt",
+    ]
+    .into_iter()
+    .map(|rule| rule.to_string())
+    .collect();
+    let mut kinds = vec!["Bool", "Error"];
+    if *system != System::EvalML1 {
+        rules.extend(
+            vec!["E-Var1", "E-Var2", "E-Let", "E-LetErrorL", "E-LetErrorR"]
+                .into_iter()
+                .map(|rule| rule.to_string()),
+        );
+    }
+    if *system == System::EvalML3 {
+        rules.extend(
+            vec![
+                "E-Var",
+                "E-Fun",
+                "E-App",
+                "E-AppRec",
+                "E-LetRec",
+                "E-AppError",
+                "E-AppRecError",
+                "E-AppErrorL",
+                "E-AppErrorR",
+                "E-AppInt",
+                "E-AppBool",
+                "E-LetRecError",
+                "E-IfFun",
+            ]
+            .into_iter()
+            .map(|rule| rule.to_string()),
+        );
+        kinds.push("Fun");
+    }
+    for op in ["Plus", "Minus", "Times", "Lt"].iter() {
+        for kind in kinds.iter() {
+            rules.push(format!("E-{}{}L", op, kind));
+            rules.push(format!("E-{}{}R", op, kind));
+        }
+    }
+    rules
+}
+
+pub fn check_rule(
+    system: &System,
+    rule: &str,
+    conclusion: &Judgement,
+    premises: &[Judgement],
+) -> Result<(), String> {
+    let known: Vec<_> = premises.iter().cloned().map(Some).collect();
+    let expected = solve_premises(system, rule, conclusion, &known)?;
+    if expected.len() != premises.len() {
+        return Err(format!(
+            "{} takes {} premise(s), but {} are given",
+            rule,
+            expected.len(),
+            premises.len()
+        ));
+    }
+    for (i, (expected, actual)) in expected.iter().zip(premises.iter()).enumerate() {
+        if expected != actual {
+            return Err(format!(
+                "premise {} must be `{}`, found `{}`",
+                i + 1,
+                expected,
+                actual
+            ));
+        }
+    }
+    Ok(())
+}
+
+// the premises the rule requires for the conclusion, where None is a premise yet unknown
+pub fn solve_premises(
+    system: &System,
+    rule: &str,
+    conclusion: &Judgement,
+    premises: &[Option<Judgement>],
+) -> Result<Vec<Judgement>, String> {
+    if !get_rules(system).iter().any(|r| r == rule) {
+        return Err(format!("not a rule of {}", system));
+    }
+    get_premises(rule, conclusion, premises)
+}
+
+// the value the premise evaluates to, if it is a known evaluation judgement
+fn get_value(premises: &[Option<Judgement>], i: usize) -> Result<Value, String> {
+    match premises.get(i) {
+        Some(Some(Judgement::Eval(_, _, v))) => Ok(v.clone()),
+        _ => Err(format!("premise {} must be an evaluation judgement", i + 1)),
+    }
+}
+
+// the value of the premise, or else the one the other premises determine, e.g. the value of
+// e1 of E-Let from the binding its second premise starts with
+fn get_value_or(
+    premises: &[Option<Judgement>],
+    i: usize,
+    other: Option<Value>,
+) -> Result<Value, String> {
+    get_value(premises, i).or_else(|message| other.ok_or(message))
+}
+
+// the operands of the B-Plus, ... premise
+fn get_operands(premises: &[Option<Judgement>], i: usize) -> Option<(i32, i32)> {
+    match premises.get(i) {
+        Some(Some(Judgement::BOp(_, i1, i2, _))) => Some((*i1, *i2)),
+        _ => None,
+    }
+}
+
+// the innermost binding of the environment of the premise
+fn get_last_binding(premises: &[Option<Judgement>], i: usize) -> Option<(String, Value)> {
+    match premises.get(i) {
+        Some(Some(Judgement::Eval(Some(environment), _, _))) => environment.stack.last().cloned(),
+        _ => None,
+    }
+}
+
+// the closure and the argument of E-App or E-AppRec from the premise evaluating the body:
+// its environment is that of the closure extended by the argument, and for E-AppRec by
+// the closure itself before the argument
+fn get_application(
+    premises: &[Option<Judgement>],
+    i: usize,
+    is_rec: bool,
+) -> Option<(Value, Value)> {
+    let (environment, body) = match premises.get(i) {
+        Some(Some(Judgement::Eval(Some(environment), body, _))) => (environment, body),
+        _ => return None,
+    };
+    let mut closure_environment = environment.clone();
+    let (parameter, argument) = closure_environment.stack.pop()?;
+    let function = if is_rec {
+        let (_, function) = closure_environment.stack.pop()?;
+        function
+    } else {
+        let fun_term = FunTerm {
+            parameter,
+            function_body: body.clone(),
+        };
+        Value::Fun(fun_term, closure_environment)
+    };
+    Some((function, argument))
+}
+
+// None for the operators of the later systems, ::, :=, ...
+fn get_op_name(op: &str) -> Option<&'static str> {
+    match op {
+        "+" => Some("Plus"),
+        "-" => Some("Minus"),
+        "*" => Some("Times"),
+        "<" => Some("Lt"),
+        _ => None,
+    }
+}
+
+// the premises the rule requires for the conclusion; metavariables that do not
+// occur in the conclusion are taken from the known premises
+fn get_premises(
+    rule: &str,
+    conclusion: &Judgement,
+    premises: &[Option<Judgement>],
+) -> Result<Vec<Judgement>, String> {
+    let mismatch = |form: &str| Err(format!("the conclusion of {} must be `{}`", rule, form));
+    let (environment, expression, value) = match conclusion {
+        Judgement::BOp(op, i1, i2, v) => {
+            let expected = match op.as_ref() {
+                "plus" => i1.checked_add(*i2).map(Value::Num),
+                "minus" => i1.checked_sub(*i2).map(Value::Num),
+                "times" => i1.checked_mul(*i2).map(Value::Num),
+                _ => Some(Value::Bool((i1 < i2).to_string())),
+            };
+            if Some(rule) != conclusion.get_bop_rule() {
+                return Err(format!(
+                    "the conclusion of {} cannot be `{}`",
+                    rule, conclusion
+                ));
+            }
+            return match expected {
+                Some(ref expected) if expected == v => Ok(vec![]),
+                Some(expected) => {
+                    mismatch(&Judgement::BOp(op.to_string(), *i1, *i2, expected).to_string())
+                }
+                None => Err(format!("{} {} {} overflows", i1, op, i2)),
+            };
+        }
+        Judgement::Eval(environment, expression, value) => (environment, expression, value),
+    };
+    let eval = |environment: &Option<Environment>, e: &Expression, v: Value| {
+        Judgement::Eval(environment.clone(), e.clone(), v)
+    };
+    let is_error = *value == Value::Error;
+    let is_op = |op: &str, rule_prefix: &str| {
+        get_op_name(op).is_some_and(|name| rule.starts_with(&format!("{}{}", rule_prefix, name)))
+    };
+
+    let premises = match (rule, get_view(expression)) {
+        ("E-Int", View::Term(Term::Val(i))) if *value == Value::Num(*i) => vec![],
+        ("E-Int", _) => return mismatch("i evalto i"),
+        ("E-Bool", View::Term(Term::Bool(b))) if *value == Value::Bool(b.clone()) => vec![],
+        ("E-Bool", _) => return mismatch("b evalto b"),
+        ("E-Var", View::Term(Term::Var(x))) => {
+            match environment.as_ref().expect("an environment").find_val(x) {
+                Some(ref v) if v == value => vec![],
+                Some(_) => return mismatch("E |- x evalto E(x)"),
+                None => return Err(format!("variable '{}' is not bound", x)),
+            }
+        }
+        ("E-Var1", View::Term(Term::Var(x))) => {
+            match environment.as_ref().expect("an environment").stack.last() {
+                Some((y, v)) if y == x && v == value => vec![],
+                _ => return mismatch("E, x = v |- x evalto v"),
+            }
+        }
+        ("E-Var2", View::Term(Term::Var(x))) => {
+            let mut environment = environment.clone().expect("an environment");
+            match environment.stack.pop() {
+                Some((y, _)) if y != *x => {
+                    vec![eval(&Some(environment), expression, value.clone())]
+                }
+                _ => return mismatch("E, y = v' |- x evalto v"),
+            }
+        }
+        ("E-Var", _) | ("E-Var1", _) | ("E-Var2", _) => return mismatch("E |- x evalto v"),
+        ("E-Plus", View::BinOp(op, e1, e2))
+        | ("E-Minus", View::BinOp(op, e1, e2))
+        | ("E-Times", View::BinOp(op, e1, e2))
+        | ("E-Lt", View::BinOp(op, e1, e2))
+            if get_op_name(&op)
+                .map(|name| format!("E-{}", name))
+                .as_deref()
+                == Some(rule) =>
+        {
+            let operands = get_operands(premises, 2);
+            let v1 = get_value_or(premises, 0, operands.map(|(i1, _)| Value::Num(i1)))?;
+            let v2 = get_value_or(premises, 1, operands.map(|(_, i2)| Value::Num(i2)))?;
+            let (i1, i2) = match (v1, v2) {
+                (Value::Num(i1), Value::Num(i2)) => (i1, i2),
+                (Value::Num(_), _) => {
+                    return Err("premise 2 must evaluate to an integer".to_string())
+                }
+                _ => return Err("premise 1 must evaluate to an integer".to_string()),
+            };
+            let op = match op.as_ref() {
+                "+" => "plus",
+                "-" => "minus",
+                "*" => "times",
+                _ => "less than",
+            };
+            vec![
+                eval(environment, &e1, Value::Num(i1)),
+                eval(environment, &e2, Value::Num(i2)),
+                Judgement::BOp(op.to_string(), i1, i2, value.clone()),
+            ]
+        }
+        ("E-Plus", _) => return mismatch("e1 + e2 evalto i3"),
+        ("E-Minus", _) => return mismatch("e1 - e2 evalto i3"),
+        ("E-Times", _) => return mismatch("e1 * e2 evalto i3"),
+        ("E-Lt", _) => return mismatch("e1 < e2 evalto b3"),
+        ("E-IfT", View::Term(Term::If(if_term)))
+        | ("E-IfF", View::Term(Term::If(if_term)))
+        | ("E-IfTError", View::Term(Term::If(if_term)))
+        | ("E-IfFError", View::Term(Term::If(if_term)))
+            if rule.ends_with("Error") == is_error =>
+        {
+            let IfTerm {
+                condition_expression,
+                then_expression,
+                else_expression,
+            } = if_term;
+            let (condition, branch) = if rule.starts_with("E-IfT") {
+                (true, then_expression)
+            } else {
+                (false, else_expression)
+            };
+            vec![
+                eval(
+                    environment,
+                    condition_expression,
+                    Value::Bool(condition.to_string()),
+                ),
+                eval(environment, branch, value.clone()),
+            ]
+        }
+        ("E-IfT", _) | ("E-IfF", _) => return mismatch("if e1 then e2 else e3 evalto v"),
+        ("E-IfTError", _) | ("E-IfFError", _) => {
+            return mismatch("if e1 then e2 else e3 evalto error")
+        }
+        ("E-Let", View::Term(Term::Let(let_term)))
+        | ("E-LetErrorR", View::Term(Term::Let(let_term)))
+            if rule.contains("Error") == is_error =>
+        {
+            let LetTerm {
+                let_expression,
+                in_expression,
+            } = let_term;
+            let binding = get_last_binding(premises, 1)
+                .filter(|(x, _)| *x == let_expression.identifier)
+                .map(|(_, v1)| v1);
+            let v1 = get_value_or(premises, 0, binding)?;
+            if v1 == Value::Error {
+                return Err(format!("premise 1 of {} cannot evaluate to error", rule));
+            }
+            let extended = environment
+                .as_ref()
+                .map(|environment| push(environment, &let_expression.identifier, v1.clone()));
+            vec![
+                eval(environment, &let_expression.expression, v1),
+                eval(&extended, in_expression, value.clone()),
+            ]
+        }
+        ("E-Let", _) => return mismatch("E |- let x = e1 in e2 evalto v"),
+        ("E-LetErrorR", _) => return mismatch("E |- let x = e1 in e2 evalto error"),
+        ("E-LetErrorL", View::Term(Term::Let(let_term))) if is_error => {
+            vec![eval(
+                environment,
+                &let_term.let_expression.expression,
+                Value::Error,
+            )]
+        }
+        ("E-LetErrorL", _) => return mismatch("E |- let x = e1 in e2 evalto error"),
+        ("E-Fun", View::Term(Term::Fun(fun_term))) => {
+            let closure = Value::Fun(
+                fun_term.clone(),
+                environment.clone().expect("an environment"),
+            );
+            if *value != closure {
+                return mismatch("E |- fun x -> e evalto (E)[fun x -> e]");
+            }
+            vec![]
+        }
+        ("E-Fun", _) => return mismatch("E |- fun x -> e evalto (E)[fun x -> e]"),
+        ("E-LetRec", View::Term(Term::LetRec(let_rec_term)))
+        | ("E-LetRecError", View::Term(Term::LetRec(let_rec_term)))
+            if rule.ends_with("Error") == is_error =>
+        {
+            let LetRecTerm {
+                identifier,
+                fun_term,
+                in_expression,
+            } = let_rec_term;
+            let environment = environment.clone().expect("an environment");
+            let closure = Value::RecFun(identifier.clone(), fun_term.clone(), environment.clone());
+            let extended = push(&environment, identifier, closure);
+            vec![eval(&Some(extended), in_expression, value.clone())]
+        }
+        ("E-LetRec", _) => return mismatch("E |- let rec f = fun x -> e1 in e2 evalto v"),
+        ("E-LetRecError", _) => return mismatch("E |- let rec f = fun x -> e1 in e2 evalto error"),
+        ("E-App", View::Term(Term::App(app_term)))
+        | ("E-AppError", View::Term(Term::App(app_term)))
+        | ("E-AppRec", View::Term(Term::App(app_term)))
+        | ("E-AppRecError", View::Term(Term::App(app_term)))
+            if rule.ends_with("Error") == is_error =>
+        {
+            let AppTerm { function, argument } = app_term;
+            let application = get_application(premises, 2, rule.starts_with("E-AppRec"));
+            let v1 = get_value_or(premises, 0, application.clone().map(|(v1, _)| v1))?;
+            let v2 = get_value_or(premises, 1, application.map(|(_, v2)| v2))?;
+            if v2 == Value::Error {
+                return Err(format!("premise 2 of {} cannot evaluate to error", rule));
+            }
+            let (closure_environment, body) = match (rule.starts_with("E-AppRec"), &v1) {
+                (false, Value::Fun(fun_term, closure_environment)) => (
+                    push(closure_environment, &fun_term.parameter, v2.clone()),
+                    &fun_term.function_body,
+                ),
+                (true, Value::RecFun(identifier, fun_term, closure_environment)) => (
+                    push(
+                        &push(closure_environment, identifier, v1.clone()),
+                        &fun_term.parameter,
+                        v2.clone(),
+                    ),
+                    &fun_term.function_body,
+                ),
+                (false, _) => return Err("premise 1 must evaluate to (E)[fun x -> e]".to_string()),
+                (true, _) => {
+                    return Err("premise 1 must evaluate to (E)[rec f = fun x -> e]".to_string())
+                }
+            };
+            vec![
+                eval(environment, &from_term(function), v1.clone()),
+                eval(environment, argument, v2),
+                eval(&Some(closure_environment), body, value.clone()),
+            ]
+        }
+        ("E-App", _) | ("E-AppRec", _) => return mismatch("E |- e1 e2 evalto v"),
+        ("E-AppError", _) | ("E-AppRecError", _) => return mismatch("E |- e1 e2 evalto error"),
+        ("E-AppErrorR", View::Term(Term::App(app_term))) if is_error => {
+            let function = get_value(premises, 0)?;
+            if function.get_kind() != "Fun" {
+                return Err("premise 1 must evaluate to a function".to_string());
+            }
+            vec![
+                eval(environment, &from_term(&app_term.function), function),
+                eval(environment, &app_term.argument, Value::Error),
+            ]
+        }
+        ("E-AppErrorL", View::Term(Term::App(app_term)))
+        | ("E-AppInt", View::Term(Term::App(app_term)))
+        | ("E-AppBool", View::Term(Term::App(app_term)))
+            if is_error =>
+        {
+            let function = get_value(premises, 0)?;
+            let name = match function {
+                Value::Error => "E-AppErrorL".to_string(),
+                _ => format!("E-App{}", function.get_kind()),
+            };
+            if rule != name {
+                return Err(format!(
+                    "premise 1 of {} cannot evaluate to {}",
+                    rule, function
+                ));
+            }
+            vec![eval(environment, &from_term(&app_term.function), function)]
+        }
+        (_, View::Term(Term::App(_))) if rule.starts_with("E-App") => {
+            return mismatch("E |- e1 e2 evalto error")
+        }
+        (_, View::Term(Term::If(if_term))) if rule.starts_with("E-If") && is_error => {
+            // E-IfInt, E-IfFun and E-IfError
+            let condition = get_value(premises, 0)?;
+            if rule != format!("E-If{}", condition.get_kind()) {
+                return Err(format!(
+                    "premise 1 of {} cannot evaluate to {}",
+                    rule, condition
+                ));
+            }
+            vec![eval(environment, &if_term.condition_expression, condition)]
+        }
+        (_, _) if rule.starts_with("E-If") => {
+            return mismatch("if e1 then e2 else e3 evalto error")
+        }
+        (_, View::BinOp(op, e1, e2)) if is_op(&op, "E-") && is_error => {
+            // E-PlusBoolL, E-PlusErrorR, ...
+            let (operand, side) = if rule.ends_with('L') {
+                (e1, 'L')
+            } else {
+                (e2, 'R')
+            };
+            let v = get_value(premises, 0)?;
+            let name = get_op_name(&op).expect("an operator of EvalML3");
+            if rule != format!("E-{}{}{}", name, v.get_kind(), side) {
+                return Err(format!("premise 1 of {} cannot evaluate to {}", rule, v));
+            }
+            vec![eval(environment, &operand, v)]
+        }
+        _ => return mismatch("e1 op e2 evalto error"),
+    };
+    Ok(premises)
+}
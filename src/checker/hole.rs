@@ -0,0 +1,191 @@
+use super::derivation::Derivation;
+use super::Lexer;
+use super::{check_derivation, derive, solve_premises, CheckError, Judgement, System};
+
+// fills the holes of the derivation with the subtrees derived by the system, and records
+// the text to replace each hole with. an explicit stack is used, since the derivation can
+// be deeper than the call stack
+pub fn fill_holes(
+    lexer: &Lexer,
+    text: &str,
+    derivation: &mut Derivation,
+    system: &System,
+    replacements: &mut Vec<(usize, usize, String)>,
+    errors: &mut Vec<CheckError>,
+) {
+    let mut stack = vec![derivation];
+    while let Some(derivation) = stack.pop() {
+        // judgement by ?
+        if derivation.is_hole() {
+            match derive_checked(lexer, &derivation.judgement, system) {
+                Ok((derived_text, _)) => {
+                    let column = get_column(text, derivation.start);
+                    let filled = indent(&derived_text, 0, column);
+                    replacements.push((derivation.start, derivation.end, filled));
+                }
+                Err(message) => {
+                    let message = format!("cannot fill the hole: {}", message);
+                    errors.push(CheckError::new(text, derivation.start, message));
+                }
+            }
+            continue;
+        }
+
+        // ? in place of a premise, solved from the conclusion and all the other premises
+        let is_written: Vec<bool> = derivation
+            .premises
+            .iter()
+            .map(|premise| !premise.judgement.is_empty())
+            .collect();
+        if is_written.contains(&false) {
+            let solved = solve_holes(lexer, derivation, system);
+            for (i, premise) in derivation.premises.iter_mut().enumerate() {
+                if is_written[i] {
+                    continue;
+                }
+                let derived = solved.as_ref().map_err(String::clone).and_then(|solved| {
+                    let judgement = solved[i].to_string();
+                    let (derived_text, _) = derive_checked(lexer, &judgement, system)?;
+                    Ok((judgement, derived_text))
+                });
+                match derived {
+                    Ok((judgement, derived_text)) => {
+                        let column = get_column(text, premise.start);
+                        let filled = indent(&derived_text, 0, column);
+                        replacements.push((premise.start, premise.end, filled));
+                        premise.judgement = judgement;
+                    }
+                    Err(message) => {
+                        let message = format!("cannot fill the hole: {}", message);
+                        errors.push(CheckError::new(text, premise.start, message));
+                    }
+                }
+            }
+        }
+
+        // the premises filled above are complete, so only the written ones are walked
+        let written: Vec<&mut Derivation> = derivation
+            .premises
+            .iter_mut()
+            .zip(is_written)
+            .filter(|(_, is_written)| *is_written)
+            .map(|(premise, _)| premise)
+            .collect();
+        stack.extend(written.into_iter().rev());
+    }
+    // the premises of a step are filled before the subtrees of the earlier ones
+    errors.sort_by_key(|error| (error.line, error.column));
+}
+
+// the judgements of the premises, solved from the conclusion and the premises the user wrote.
+// what they leave open, e.g. the middle of L-Trans with both premises unknown, is taken
+// from the tree the deriver makes
+fn solve_holes(
+    lexer: &Lexer,
+    derivation: &Derivation,
+    system: &System,
+) -> Result<Vec<Judgement>, String> {
+    let conclusion = Judgement::new(lexer, &derivation.judgement, system)?;
+    let mut known = Vec::new();
+    for premise in derivation.premises.iter() {
+        known.push(match premise.judgement.as_ref() {
+            "" => None,
+            judgement => Some(Judgement::new(lexer, judgement, system)?),
+        });
+    }
+    let rule = &derivation.rule;
+    let solved = solve_premises(system, rule, &conclusion, &known).or_else(|_| {
+        let (_, tree) = derive_checked(lexer, &derivation.judgement, system)?;
+        if tree.rule != *rule {
+            return Err(format!(
+                "`{}` is derived by {}, not by {}",
+                derivation.judgement, tree.rule, rule
+            ));
+        }
+        if tree.premises.len() != known.len() {
+            return Err(format!(
+                "{} takes {} premise(s) for `{}`",
+                tree.rule,
+                tree.premises.len(),
+                derivation.judgement
+            ));
+        }
+        let mut guessed = Vec::new();
+        for (premise, subtree) in known.iter().zip(tree.premises.iter()) {
+            guessed.push(match premise {
+                Some(premise) => Some(premise.clone()),
+                None => Some(Judgement::new(lexer, &subtree.judgement, system)?),
+            });
+        }
+        solve_premises(system, rule, &conclusion, &guessed)
+    })?;
+    if solved.len() != known.len() {
+        return Err(format!(
+            "{} takes {} premise(s), but {} are given",
+            rule,
+            solved.len(),
+            known.len()
+        ));
+    }
+    Ok(solved)
+}
+
+// derives the judgement, and makes sure the derived tree is valid and concludes it
+fn derive_checked(
+    lexer: &Lexer,
+    judgement: &str,
+    system: &System,
+) -> Result<(String, Derivation), String> {
+    let conclusion = Judgement::new(lexer, judgement, system)?;
+    if !conclusion.holds() {
+        return Err(format!("`{}` does not hold", judgement));
+    }
+    let derived_text = derive(lexer, judgement, system).trim_end().to_string();
+    if derived_text.starts_with("judgement does not hold") {
+        return Err(format!("`{}` does not hold", judgement));
+    }
+    let cannot_derive = |_| format!("cannot derive `{}`", judgement);
+    let derived = Derivation::new(&derived_text).map_err(cannot_derive)?;
+    if Judgement::new(lexer, &derived.judgement, system) != Ok(conclusion) {
+        return Err(format!("`{}` does not hold", judgement));
+    }
+    check_derivation(lexer, &derived_text, &derived, system).map_err(cannot_derive)?;
+    Ok((derived_text, derived))
+}
+
+pub fn replace(text: &str, mut replacements: Vec<(usize, usize, String)>) -> String {
+    replacements.sort_by_key(|(start, _, _)| *start);
+    let mut s = "".to_string();
+    let mut position = 0;
+    for (start, end, filled) in replacements {
+        s += &text[position..start];
+        s += &filled;
+        position = end;
+    }
+    s += &text[position..];
+    s
+}
+
+// moves the lines after the first one from the column `from` to the column `to`
+fn indent(derivation: &str, from: usize, to: usize) -> String {
+    derivation
+        .split('\n')
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 {
+                return line.to_string();
+            }
+            let spaces = line.len() - line.trim_start_matches(' ').len();
+            format!("{}{}", " ".repeat(to), &line[spaces.min(from)..])
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn get_column(text: &str, offset: usize) -> usize {
+    let before = &text[..offset];
+    match before.rfind('\n') {
+        Some(i) => before[i + 1..].chars().count(),
+        None => before.chars().count(),
+    }
+}
@@ -0,0 +1,430 @@
+use std::fmt;
+
+use super::System;
+
+use crate::chapter1::lexer::{Token, Tokens};
+use crate::chapter1::parser_reducenatexp::Expression;
+use crate::chapter1::util::{get_peano_num, NumFormat};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Judgement {
+    Plus(usize, usize, usize),
+    Times(usize, usize, usize),
+    Less(usize, usize),
+    Eval(Expression, usize),
+    // ---> , -d-> or -*->
+    Reduce(String, Expression, Expression),
+}
+impl Judgement {
+    pub fn new(tokens: &mut Tokens, system: &System) -> Result<Judgement, String> {
+        let judgement = match system {
+            System::Nat => Judgement::new_nat(tokens)?,
+            System::CompareNat1 | System::CompareNat2 | System::CompareNat3 => {
+                let n1 = tokens.consume_peano_num()?;
+                tokens.consume(|token| matches!(token, Token::Lt(_)), "`is less than`")?;
+                Judgement::Less(n1, tokens.consume_peano_num()?)
+            }
+            System::EvalNatExp | System::ReduceNatExp => {
+                let expression = Expression::new(tokens)?;
+                let is_eval = *system == System::EvalNatExp;
+                match tokens.peek() {
+                    Some(Token::Eval(_)) if is_eval => {
+                        tokens.pop(); // consume evalto
+                        Judgement::Eval(expression, tokens.consume_peano_num()?)
+                    }
+                    Some(Token::EvalONE(_)) if !is_eval => {
+                        tokens.pop(); // consume the arrow
+                        Judgement::Reduce("--->".to_string(), expression, Expression::new(tokens)?)
+                    }
+                    Some(Token::EvalDR(_)) if !is_eval => {
+                        tokens.pop(); // consume the arrow
+                        Judgement::Reduce("-d->".to_string(), expression, Expression::new(tokens)?)
+                    }
+                    Some(Token::EvalMR(_)) if !is_eval => {
+                        tokens.pop(); // consume the arrow
+                        Judgement::Reduce("-*->".to_string(), expression, Expression::new(tokens)?)
+                    }
+                    Some(Token::Op(op, _)) => {
+                        tokens.pop(); // consume plus or times
+                        let n1 = match expression {
+                            Expression::Num(n1) => n1,
+                            _ => return Err(format!("expects a number before `{}`", op)),
+                        };
+                        Judgement::new_operation(&op, n1, tokens)?
+                    }
+                    _ => {
+                        let expected = if is_eval {
+                            "`evalto`, `plus` or `times`"
+                        } else {
+                            "`--->`, `-d->`, `-*->`, `plus` or `times`"
+                        };
+                        return Err(tokens.get_unexpected_message(expected));
+                    }
+                }
+            }
+            _ => panic!("not a system on natural numbers"),
+        };
+        tokens.expect_end()?;
+        Ok(judgement)
+    }
+    fn new_nat(tokens: &mut Tokens) -> Result<Judgement, String> {
+        let n1 = tokens.consume_peano_num()?;
+        match tokens.peek() {
+            Some(Token::Op(op, _)) => {
+                tokens.pop(); // consume plus or times
+                Judgement::new_operation(&op, n1, tokens)
+            }
+            _ => Err(tokens.get_unexpected_message("`plus` or `times`")),
+        }
+    }
+    // `n2 is n3` following `n1 plus` or `n1 times`
+    fn new_operation(op: &str, n1: usize, tokens: &mut Tokens) -> Result<Judgement, String> {
+        let n2 = tokens.consume_peano_num()?;
+        tokens.consume(|token| matches!(token, Token::Equal(_)), "`is`")?;
+        let n3 = tokens.consume_peano_num()?;
+        if op == "plus" {
+            Ok(Judgement::Plus(n1, n2, n3))
+        } else {
+            Ok(Judgement::Times(n1, n2, n3))
+        }
+    }
+
+    // the derivers of chapter1 panic on false judgements, so they are decided here first.
+    // reductions are left to the checking of the derived tree
+    pub fn holds(&self) -> bool {
+        match self {
+            Judgement::Plus(n1, n2, n3) => n1 + n2 == *n3,
+            Judgement::Times(n1, n2, n3) => n1 * n2 == *n3,
+            Judgement::Less(n1, n2) => n1 < n2,
+            Judgement::Eval(e, n) => e.get_val() == *n,
+            Judgement::Reduce(_, _, _) => true,
+        }
+    }
+}
+impl fmt::Display for Judgement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let format = NumFormat::Peano;
+        match self {
+            Judgement::Plus(n1, n2, n3) => write!(
+                f,
+                "{} plus {} is {}",
+                get_peano_num(*n1),
+                get_peano_num(*n2),
+                get_peano_num(*n3)
+            ),
+            Judgement::Times(n1, n2, n3) => write!(
+                f,
+                "{} times {} is {}",
+                get_peano_num(*n1),
+                get_peano_num(*n2),
+                get_peano_num(*n3)
+            ),
+            Judgement::Less(n1, n2) => {
+                write!(
+                    f,
+                    "{} is less than {}",
+                    get_peano_num(*n1),
+                    get_peano_num(*n2)
+                )
+            }
+            Judgement::Eval(e, n) => {
+                write!(f, "{} evalto {}", e.to_string(format), get_peano_num(*n))
+            }
+            Judgement::Reduce(arrow, e1, e2) => {
+                write!(
+                    f,
+                    "{} {} {}",
+                    e1.to_string(format),
+                    arrow,
+                    e2.to_string(format)
+                )
+            }
+        }
+    }
+}
+
+fn get_rules(system: &System) -> Vec<&'static str> {
+    let nat_rules = vec!["P-Zero", "P-Succ", "T-Zero", "T-Succ"];
+    match system {
+        System::Nat => nat_rules,
+        System::CompareNat1 => vec!["L-Succ", "L-Trans"],
+        System::CompareNat2 => vec!["L-Zero", "L-SuccSucc"],
+        System::CompareNat3 => vec!["L-Succ", "L-SuccR"],
+        System::EvalNatExp => [nat_rules, vec!["E-Const", "E-Plus", "E-Times"]].concat(),
+        System::ReduceNatExp => [
+            nat_rules,
+            vec![
+                "R-Plus",
+                "R-Times",
+                "R-PlusL",
+                "R-PlusR",
+                "R-TimesL",
+                "R-TimesR",
+                "MR-Zero",
+                "MR-Multi",
+                "MR-One",
+                "DR-Plus",
+                "DR-Times",
+                "DR-PlusL",
+                "DR-PlusR",
+                "DR-TimesL",
+                "DR-TimesR",
+            ],
+        ]
+        .concat(),
+        _ => panic!("not a system on natural numbers"),
+    }
+}
+
+pub fn check_rule(
+    system: &System,
+    rule: &str,
+    conclusion: &Judgement,
+    premises: &[Judgement],
+) -> Result<(), String> {
+    let known: Vec<_> = premises.iter().cloned().map(Some).collect();
+    let expected = solve_premises(system, rule, conclusion, &known)?;
+    if expected.len() != premises.len() {
+        return Err(format!(
+            "{} takes {} premise(s), but {} are given",
+            rule,
+            expected.len(),
+            premises.len()
+        ));
+    }
+    for (i, (expected, actual)) in expected.iter().zip(premises.iter()).enumerate() {
+        if expected != actual {
+            return Err(format!(
+                "premise {} must be `{}`, found `{}`",
+                i + 1,
+                expected,
+                actual
+            ));
+        }
+    }
+    Ok(())
+}
+
+// the premises the rule requires for the conclusion, where None is a premise yet unknown
+pub fn solve_premises(
+    system: &System,
+    rule: &str,
+    conclusion: &Judgement,
+    premises: &[Option<Judgement>],
+) -> Result<Vec<Judgement>, String> {
+    if !get_rules(system).contains(&rule) {
+        return Err(format!("not a rule of {}", system));
+    }
+    get_premises(rule, conclusion, premises)
+}
+
+// the premises the rule requires for the conclusion; metavariables that do not
+// occur in the conclusion are taken from the known premises
+fn get_premises(
+    rule: &str,
+    conclusion: &Judgement,
+    premises: &[Option<Judgement>],
+) -> Result<Vec<Judgement>, String> {
+    let premise = |i: usize| premises.get(i).and_then(Option::as_ref);
+    let mismatch = |form: &str| Err(format!("the conclusion of {} must be `{}`", rule, form));
+    let premises =
+        match (rule, conclusion) {
+            ("P-Zero", Judgement::Plus(0, n2, n3)) if n2 == n3 => vec![],
+            ("P-Zero", _) => return mismatch("Z plus n is n"),
+            ("P-Succ", Judgement::Plus(n1, n2, n3)) if *n1 > 0 && *n3 > 0 => {
+                vec![Judgement::Plus(n1 - 1, *n2, n3 - 1)]
+            }
+            ("P-Succ", _) => return mismatch("S(n1) plus n2 is S(n3)"),
+            ("T-Zero", Judgement::Times(0, _, 0)) => vec![],
+            ("T-Zero", _) => return mismatch("Z times n is Z"),
+            ("T-Succ", Judgement::Times(n1, n2, n4)) if *n1 > 0 => {
+                let n3 = match (premise(0), premise(1)) {
+                    (Some(Judgement::Times(_, _, n3)), _)
+                    | (None, Some(Judgement::Plus(_, n3, _))) => *n3,
+                    _ => return Err("premise 1 must be `n1 times n2 is n3`".to_string()),
+                };
+                vec![
+                    Judgement::Times(n1 - 1, *n2, n3),
+                    Judgement::Plus(*n2, n3, *n4),
+                ]
+            }
+            ("T-Succ", _) => return mismatch("S(n1) times n2 is n4"),
+            ("L-Succ", Judgement::Less(n1, n2)) if n1 + 1 == *n2 => vec![],
+            ("L-Succ", _) => return mismatch("n is less than S(n)"),
+            ("L-Trans", Judgement::Less(n1, n3)) => {
+                let n2 = match (premise(0), premise(1)) {
+                    (Some(Judgement::Less(_, n2)), _) | (None, Some(Judgement::Less(n2, _))) => *n2,
+                    _ => return Err("premise 1 must be `n1 is less than n2`".to_string()),
+                };
+                vec![Judgement::Less(*n1, n2), Judgement::Less(n2, *n3)]
+            }
+            ("L-Zero", Judgement::Less(0, n)) if *n > 0 => vec![],
+            ("L-Zero", _) => return mismatch("Z is less than S(n)"),
+            ("L-SuccSucc", Judgement::Less(n1, n2)) if *n1 > 0 && *n2 > 0 => {
+                vec![Judgement::Less(n1 - 1, n2 - 1)]
+            }
+            ("L-SuccSucc", _) => return mismatch("S(n1) is less than S(n2)"),
+            ("L-SuccR", Judgement::Less(n1, n2)) if *n2 > 0 => vec![Judgement::Less(*n1, n2 - 1)],
+            ("L-SuccR", _) => return mismatch("n1 is less than S(n2)"),
+            ("E-Const", Judgement::Eval(Expression::Num(n1), n2)) if n1 == n2 => vec![],
+            ("E-Const", _) => return mismatch("n evalto n"),
+            ("E-Plus", Judgement::Eval(Expression::Plus(e1, e2), n))
+            | ("E-Times", Judgement::Eval(Expression::Times(e1, e2), n)) => {
+                let operands = match premise(2) {
+                    Some(Judgement::Plus(n1, n2, _)) | Some(Judgement::Times(n1, n2, _)) => {
+                        Some((*n1, *n2))
+                    }
+                    _ => None,
+                };
+                // n1 and n2 are the values of e1 and e2, or else the operands of the last premise
+                let n1 = match premise(0) {
+                    Some(Judgement::Eval(_, n1)) => Some(*n1),
+                    _ => operands.map(|(n1, _)| n1),
+                };
+                let n2 = match premise(1) {
+                    Some(Judgement::Eval(_, n2)) => Some(*n2),
+                    _ => operands.map(|(_, n2)| n2),
+                };
+                let (n1, n2) = match (n1, n2) {
+                    (Some(n1), Some(n2)) => (n1, n2),
+                    _ => {
+                        return Err("premises 1 and 2 must be `e1 evalto n1` and `e2 evalto n2`"
+                            .to_string())
+                    }
+                };
+                let operation = if rule == "E-Plus" {
+                    Judgement::Plus(n1, n2, *n)
+                } else {
+                    Judgement::Times(n1, n2, *n)
+                };
+                vec![
+                    Judgement::Eval(*e1.clone(), n1),
+                    Judgement::Eval(*e2.clone(), n2),
+                    operation,
+                ]
+            }
+            ("E-Plus", _) => return mismatch("e1 + e2 evalto n"),
+            ("E-Times", _) => return mismatch("e1 * e2 evalto n"),
+            ("R-Plus", Judgement::Reduce(arrow, e, Expression::Num(n3)))
+            | ("R-Times", Judgement::Reduce(arrow, e, Expression::Num(n3)))
+            | ("DR-Plus", Judgement::Reduce(arrow, e, Expression::Num(n3)))
+            | ("DR-Times", Judgement::Reduce(arrow, e, Expression::Num(n3)))
+                if arrow == get_arrow(rule) =>
+            {
+                match (rule.ends_with("Plus"), e) {
+                    (true, Expression::Plus(e1, e2)) => match (&**e1, &**e2) {
+                        (Expression::Num(n1), Expression::Num(n2)) => {
+                            vec![Judgement::Plus(*n1, *n2, *n3)]
+                        }
+                        _ => return mismatch(&format!("n1 + n2 {} n3", arrow)),
+                    },
+                    (false, Expression::Times(e1, e2)) => match (&**e1, &**e2) {
+                        (Expression::Num(n1), Expression::Num(n2)) => {
+                            vec![Judgement::Times(*n1, *n2, *n3)]
+                        }
+                        _ => return mismatch(&format!("n1 * n2 {} n3", arrow)),
+                    },
+                    (true, _) => return mismatch(&format!("n1 + n2 {} n3", arrow)),
+                    (false, _) => return mismatch(&format!("n1 * n2 {} n3", arrow)),
+                }
+            }
+            ("R-Plus", _) | ("DR-Plus", _) => {
+                return mismatch(&format!("n1 + n2 {} n3", get_arrow(rule)))
+            }
+            ("R-Times", _) | ("DR-Times", _) => {
+                return mismatch(&format!("n1 * n2 {} n3", get_arrow(rule)))
+            }
+            ("R-PlusL", Judgement::Reduce(arrow, Expression::Plus(e1, e2), after))
+            | ("R-TimesL", Judgement::Reduce(arrow, Expression::Times(e1, e2), after))
+            | ("DR-PlusL", Judgement::Reduce(arrow, Expression::Plus(e1, e2), after))
+            | ("DR-TimesL", Judgement::Reduce(arrow, Expression::Times(e1, e2), after))
+                if arrow == get_arrow(rule) && is_same_operator(rule, after) =>
+            {
+                let (e1_, e2_) = get_operands(after);
+                if **e2 != e2_ {
+                    return mismatch(&get_form(rule));
+                }
+                vec![Judgement::Reduce(arrow.to_string(), *e1.clone(), e1_)]
+            }
+            ("R-PlusR", Judgement::Reduce(arrow, Expression::Plus(e1, e2), after))
+            | ("R-TimesR", Judgement::Reduce(arrow, Expression::Times(e1, e2), after))
+            | ("DR-PlusR", Judgement::Reduce(arrow, Expression::Plus(e1, e2), after))
+            | ("DR-TimesR", Judgement::Reduce(arrow, Expression::Times(e1, e2), after))
+                if arrow == get_arrow(rule) && is_same_operator(rule, after) =>
+            {
+                let (e1_, e2_) = get_operands(after);
+                // the deterministic reduction rewrites the right operand only after the left one
+                let is_nat = matches!(**e1, Expression::Num(_));
+                if **e1 != e1_ || (rule.starts_with("DR") && !is_nat) {
+                    return mismatch(&get_form(rule));
+                }
+                vec![Judgement::Reduce(arrow.to_string(), *e2.clone(), e2_)]
+            }
+            ("R-PlusL", _) | ("R-TimesL", _) | ("DR-PlusL", _) | ("DR-TimesL", _) => {
+                return mismatch(&get_form(rule))
+            }
+            ("R-PlusR", _) | ("R-TimesR", _) | ("DR-PlusR", _) | ("DR-TimesR", _) => {
+                return mismatch(&get_form(rule))
+            }
+            ("MR-Zero", Judgement::Reduce(arrow, e1, e2)) if arrow == "-*->" && e1 == e2 => vec![],
+            ("MR-Zero", _) => return mismatch("e -*-> e"),
+            ("MR-One", Judgement::Reduce(arrow, e1, e2)) if arrow == "-*->" => {
+                vec![Judgement::Reduce(
+                    "--->".to_string(),
+                    e1.clone(),
+                    e2.clone(),
+                )]
+            }
+            ("MR-One", _) => return mismatch("e -*-> e'"),
+            ("MR-Multi", Judgement::Reduce(arrow, e1, e3)) if arrow == "-*->" => {
+                let e2 = match (premise(0), premise(1)) {
+                    (Some(Judgement::Reduce(_, _, e2)), _)
+                    | (None, Some(Judgement::Reduce(_, e2, _))) => e2.clone(),
+                    _ => return Err("premise 1 must be `e -*-> e'`".to_string()),
+                };
+                vec![
+                    Judgement::Reduce(arrow.to_string(), e1.clone(), e2.clone()),
+                    Judgement::Reduce(arrow.to_string(), e2, e3.clone()),
+                ]
+            }
+            ("MR-Multi", _) => return mismatch("e -*-> e''"),
+            _ => panic!("unknown rule: {}", rule),
+        };
+    Ok(premises)
+}
+
+fn get_arrow(rule: &str) -> &'static str {
+    if rule.starts_with("DR") {
+        "-d->"
+    } else if rule.starts_with("MR") {
+        "-*->"
+    } else {
+        "--->"
+    }
+}
+
+fn is_same_operator(rule: &str, expression: &Expression) -> bool {
+    match expression {
+        Expression::Plus(_, _) => rule.contains("Plus"),
+        Expression::Times(_, _) => rule.contains("Times"),
+        Expression::Num(_) => false,
+    }
+}
+
+fn get_operands(expression: &Expression) -> (Expression, Expression) {
+    match expression {
+        Expression::Plus(e1, e2) | Expression::Times(e1, e2) => (*e1.clone(), *e2.clone()),
+        Expression::Num(_) => panic!("expects a binary expression"),
+    }
+}
+
+// e.g. `e1 + e2 ---> e1' + e2` for R-PlusL
+fn get_form(rule: &str) -> String {
+    let op = if rule.contains("Plus") { "+" } else { "*" };
+    let arrow = get_arrow(rule);
+    match (rule.ends_with('L'), rule.starts_with("DR")) {
+        (true, _) => format!("e1 {} e2 {} e1' {} e2", op, arrow, op),
+        (false, false) => format!("e1 {} e2 {} e1 {} e2'", op, arrow, op),
+        (false, true) => format!("n1 {} e2 {} n1 {} e2'", op, arrow, op),
+    }
+}
@@ -1,4 +1,14 @@
 pub mod chapter1;
+pub mod checker;
+pub mod parser_evalcontml1;
+pub mod parser_evalcontml4;
 pub mod parser_evalml1;
 pub mod parser_evalml2;
 pub mod parser_evalml3;
+pub mod parser_evalml4;
+pub mod parser_evalml5;
+pub mod parser_evalnamelessml3;
+pub mod parser_evalrefml3;
+pub mod parser_namelessml3;
+pub mod poly_typing_ml4;
+pub mod typing_ml4;
@@ -13,6 +13,6 @@ fn main() {
     println!("derivation tree ==================");
     let stdout = io::stdout();
     let mut stdout = stdout.lock();
-    let _ = derive(judgement, &mut stdout);
+    derive(judgement, &mut stdout);
     println!("==================================");
 }
@@ -0,0 +1,62 @@
+mod continuation;
+mod nodes;
+mod value;
+
+use super::chapter1::util::check_stated_result;
+use super::parser_evalml3::expression::Expression;
+use super::parser_evalml3::lexer::{Lexer, Token};
+use continuation::Continuation;
+use nodes::writer::RuleWriter;
+use nodes::RuleNode;
+use value::Value;
+
+use std::io::Write;
+
+pub fn derive<W: Write>(judgement: &str, w: &mut W) {
+    let lexer = Lexer::new();
+    let mut tokens = lexer.lex(judgement.to_string());
+
+    let expression = Expression::new(&mut tokens);
+    let derivation_tree = match tokens.peek().expect("a token") {
+        Token::PASS => {
+            tokens.pop(); // consume =>
+            let value = Value::new(expression);
+            RuleNode::new_cont(value, Continuation::new(&mut tokens))
+        }
+        Token::CONT => {
+            tokens.pop(); // consume >>
+            RuleNode::new_eval(expression, Continuation::new(&mut tokens))
+        }
+        // `e evalto v` is short for `e >> _ evalto v`
+        _ => RuleNode::new_eval(expression, Continuation::Ret),
+    };
+    let derivation_tree = match derivation_tree {
+        Ok(derivation_tree) => derivation_tree,
+        Err(message) => {
+            let _ = writeln!(w, "{}", message);
+            return;
+        }
+    };
+    if !tokens.consume_relation(&Token::Eval("evalto".to_string())) {
+        let _ = writeln!(
+            w,
+            "{}",
+            lexer.get_syntax_error(judgement, &tokens, "expects `evalto`")
+        );
+        return;
+    }
+
+    let value = derivation_tree.get_val().to_string();
+    if let Err(message) = check_stated_result(
+        &tokens.tokens,
+        &value,
+        |derivable| lexer.lex(derivable).tokens,
+        |count| lexer.get_tail(judgement, count),
+    ) {
+        let _ = writeln!(w, "{}", message);
+        return;
+    }
+    let mut writer = RuleWriter::new(w, 0);
+    let _ = derivation_tree.show(&mut writer);
+    writer.write_nl();
+}
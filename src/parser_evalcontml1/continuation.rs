@@ -0,0 +1,101 @@
+use super::super::parser_evalml3::expression::Expression;
+use super::super::parser_evalml3::lexer::{Token, Tokens};
+use super::value::Value;
+
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub enum Frame {
+    // {_ op e}
+    EvalR(String, Expression),
+    // {v op _}
+    BOp(Value, String),
+    // {if _ then e1 else e2}
+    If(Expression, Expression),
+}
+impl Frame {
+    pub fn new(tokens: &mut Tokens) -> Frame {
+        tokens.pop(); // consume {
+        let frame = match tokens.peek().expect("a token") {
+            Token::WILD => {
+                tokens.pop(); // consume _
+                let operator = tokens.consume_op();
+                Frame::EvalR(operator, Expression::new(tokens))
+            }
+            Token::IF => {
+                tokens.pop(); // consume if
+                tokens.pop(); // consume _
+                tokens.pop(); // consume then
+                let then_expression = Expression::new(tokens);
+                tokens.pop(); // consume else
+                let else_expression = Expression::new(tokens);
+                Frame::If(then_expression, else_expression)
+            }
+            _ => {
+                let value = Value::consume(tokens);
+                let operator = tokens.consume_op();
+                tokens.pop(); // consume _
+                Frame::BOp(value, operator)
+            }
+        };
+        tokens.pop(); // consume }
+        frame
+    }
+}
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Frame::EvalR(operator, expression) => {
+                write!(f, "{{_ {} {}}}", operator, expression)
+            }
+            Frame::BOp(value, operator) => write!(f, "{{{} {} _}}", value, operator),
+            Frame::If(then_expression, else_expression) => write!(
+                f,
+                "{{if _ then {} else {}}}",
+                then_expression, else_expression
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Continuation {
+    Ret,
+    Frame(Frame, Box<Continuation>),
+}
+impl Continuation {
+    pub fn new(tokens: &mut Tokens) -> Continuation {
+        match tokens.peek().expect("a token") {
+            Token::WILD => {
+                tokens.pop(); // consume _
+                Continuation::Ret
+            }
+            Token::BS => {
+                let frame = Frame::new(tokens);
+                // `{...}` is short for `{...} >> _`
+                match tokens.peek() {
+                    Some(Token::CONT) => {
+                        tokens.pop(); // consume >>
+                        Continuation::Frame(frame, Box::new(Continuation::new(tokens)))
+                    }
+                    _ => Continuation::Frame(frame, Box::new(Continuation::Ret)),
+                }
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+    pub fn push(self, frame: Frame) -> Continuation {
+        Continuation::Frame(frame, Box::new(self))
+    }
+}
+impl fmt::Display for Continuation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Continuation::Ret => write!(f, "_"),
+            Continuation::Frame(frame, continuation) => match **continuation {
+                Continuation::Ret => write!(f, "{}", frame),
+                _ => write!(f, "{} >> {}", frame, continuation),
+            },
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use super::super::parser_evalml3::expression::Expression;
+use super::super::parser_evalml3::terms::Term;
+use super::continuation::{Continuation, Frame};
+use super::nodes::bnodes::BOpNode;
+use super::nodes::cnodes::{CBOpNode, CEvalRNode, CIfNode, CRetNode};
+use super::nodes::enodes::{EBinOpNode, EIfNode, EValNode};
+use super::nodes::writer::RuleWriter;
+use super::value::Value;
+use std::io::{self, Write};
+
+pub mod bnodes;
+pub mod cnodes;
+pub mod enodes;
+pub mod writer;
+
+#[derive(Debug, Clone)]
+pub enum RuleNode {
+    EVal(EValNode),
+    EBinOp(EBinOpNode),
+    EIf(EIfNode),
+    CRet(CRetNode),
+    CEvalR(CEvalRNode),
+    CBOp(CBOpNode),
+    CIf(CIfNode),
+    BOp(BOpNode),
+}
+
+impl RuleNode {
+    // e >> k evalto v
+    // fails when no rule applies, e.g. to `1 + true`
+    pub fn new_eval(
+        mut expression: Expression,
+        continuation: Continuation,
+    ) -> Result<RuleNode, String> {
+        let original_expression = expression.clone();
+        let node = if expression.terms.len() == 1 {
+            match expression.get_first_term() {
+                Term::Val(num) => RuleNode::EVal(EValNode::new(
+                    original_expression,
+                    continuation,
+                    Value::Num(num),
+                )?),
+                Term::Bool(b) => RuleNode::EVal(EValNode::new(
+                    original_expression,
+                    continuation,
+                    Value::Bool(b),
+                )?),
+                Term::Paren(expression) => RuleNode::new_eval(expression, continuation)?,
+                Term::If(if_term) => {
+                    RuleNode::EIf(EIfNode::new(original_expression, continuation, if_term)?)
+                }
+                _ => panic!("unexpected"),
+            }
+        } else {
+            RuleNode::EBinOp(EBinOpNode::new(original_expression, continuation)?)
+        };
+        Ok(node)
+    }
+
+    // v => k evalto v'
+    pub fn new_cont(value: Value, continuation: Continuation) -> Result<RuleNode, String> {
+        let node = match continuation {
+            Continuation::Ret => RuleNode::CRet(CRetNode { value }),
+            Continuation::Frame(frame, continuation) => match frame {
+                Frame::EvalR(operator, expression) => {
+                    RuleNode::CEvalR(CEvalRNode::new(value, operator, expression, *continuation)?)
+                }
+                Frame::BOp(left_value, operator) => {
+                    RuleNode::CBOp(CBOpNode::new(left_value, operator, value, *continuation)?)
+                }
+                Frame::If(then_expression, else_expression) => RuleNode::CIf(CIfNode::new(
+                    value,
+                    then_expression,
+                    else_expression,
+                    *continuation,
+                )?),
+            },
+        };
+        Ok(node)
+    }
+
+    // every judgement in a derivation evaluates to the answer of the whole program
+    pub fn get_val(&self) -> Value {
+        match self {
+            RuleNode::EVal(node) => node.premise.get_val(),
+            RuleNode::EBinOp(node) => node.premise.get_val(),
+            RuleNode::EIf(node) => node.premise.get_val(),
+            RuleNode::CRet(node) => node.value.clone(),
+            RuleNode::CEvalR(node) => node.premise.get_val(),
+            RuleNode::CBOp(node) => node.premise2.get_val(),
+            RuleNode::CIf(node) => node.premise.get_val(),
+            RuleNode::BOp(node) => node.get_val(),
+        }
+    }
+
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        match self {
+            RuleNode::EVal(node) => node.show(writer),
+            RuleNode::EBinOp(node) => node.show(writer),
+            RuleNode::EIf(node) => node.show(writer),
+            RuleNode::CRet(node) => node.show(writer),
+            RuleNode::CEvalR(node) => node.show(writer),
+            RuleNode::CBOp(node) => node.show(writer),
+            RuleNode::CIf(node) => node.show(writer),
+            RuleNode::BOp(node) => node.show(writer),
+        }
+    }
+}
+
+pub fn get_depth_space(depth: usize) -> String {
+    let mut s = "".to_string();
+    for _ in 0..depth {
+        s += " ";
+    }
+    s
+}
@@ -0,0 +1,36 @@
+use super::super::value::Value;
+use super::writer::RuleWriter;
+use std::io::{self, Write};
+
+#[derive(Debug, Clone)]
+pub struct BOpNode {
+    pub i1: i32,
+    pub i2: i32,
+    pub op: String,
+}
+impl BOpNode {
+    pub fn get_val(&self) -> Value {
+        match self.op.as_ref() {
+            "+" => Value::Num(self.i1 + self.i2),
+            "-" => Value::Num(self.i1 - self.i2),
+            "*" => Value::Num(self.i1 * self.i2),
+            "<" => Value::Bool((self.i1 < self.i2).to_string()),
+            _ => panic!("todo"),
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let (op_str, rule_str) = match self.op.as_ref() {
+            "+" => ("plus", "B-Plus"),
+            "-" => ("minus", "B-Minus"),
+            "*" => ("times", "B-Times"),
+            "<" => ("less than", "B-Lt"),
+            _ => panic!("todo"),
+        };
+        writer.show_judgement(
+            format!("{} {} {} is {}", self.i1, op_str, self.i2, self.get_val()),
+            rule_str.to_string(),
+            None,
+            None,
+        )
+    }
+}
@@ -0,0 +1,177 @@
+use super::super::super::chapter1::util::get_no_rule_message;
+use super::super::super::parser_evalml3::expression::Expression;
+use super::super::continuation::{Continuation, Frame};
+use super::super::nodes::RuleNode;
+use super::super::value::Value;
+use super::bnodes::BOpNode;
+use super::writer::RuleWriter;
+
+use std::io::{self, Write};
+
+#[derive(Debug, Clone)]
+pub struct CRetNode {
+    pub value: Value,
+}
+impl CRetNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_cont(
+            self.value.to_string(),
+            &Continuation::Ret,
+            self.value.to_string(),
+            "C-Ret".to_string(),
+            None,
+            None,
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CEvalRNode {
+    pub value: Value,
+    pub continuation: Continuation,
+    pub premise: Box<RuleNode>,
+}
+impl CEvalRNode {
+    // v => {_ op e} >> k
+    pub fn new(
+        value: Value,
+        operator: String,
+        expression: Expression,
+        continuation: Continuation,
+    ) -> Result<CEvalRNode, String> {
+        let premise = RuleNode::new_eval(
+            expression.clone(),
+            continuation
+                .clone()
+                .push(Frame::BOp(value.clone(), operator.clone())),
+        )?;
+        Ok(CEvalRNode {
+            value,
+            continuation: continuation.push(Frame::EvalR(operator, expression)),
+            premise: Box::new(premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_cont(
+            self.value.to_string(),
+            &self.continuation,
+            self.premise.get_val().to_string(),
+            "C-EvalR".to_string(),
+            Some(*self.premise),
+            None,
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CBOpNode {
+    pub value: Value,
+    pub continuation: Continuation,
+    pub rule_str: String,
+    pub premise1: Box<RuleNode>,
+    pub premise2: Box<RuleNode>,
+}
+impl CBOpNode {
+    // v2 => {v1 op _} >> k
+    pub fn new(
+        left_value: Value,
+        operator: String,
+        value: Value,
+        continuation: Continuation,
+    ) -> Result<CBOpNode, String> {
+        let (i1, i2) = match (&left_value, &value) {
+            (Value::Num(i1), Value::Num(i2)) => (*i1, *i2),
+            _ => {
+                let expression_str = format!("{} {} {}", left_value, operator, value);
+                return Err(get_no_rule_message(&expression_str));
+            }
+        };
+        let rule_str = match operator.as_ref() {
+            "+" => "C-Plus",
+            "-" => "C-Minus",
+            "*" => "C-Times",
+            "<" => "C-Lt",
+            _ => panic!("todo"),
+        };
+        let premise1 = BOpNode {
+            i1,
+            i2,
+            op: operator.clone(),
+        };
+        let premise2 = RuleNode::new_cont(premise1.get_val(), continuation.clone())?;
+        Ok(CBOpNode {
+            value,
+            continuation: continuation.push(Frame::BOp(left_value, operator)),
+            rule_str: rule_str.to_string(),
+            premise1: Box::new(RuleNode::BOp(premise1)),
+            premise2: Box::new(premise2),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_cont(
+            self.value.to_string(),
+            &self.continuation,
+            self.premise2.get_val().to_string(),
+            self.rule_str,
+            Some(*self.premise1),
+            Some(*self.premise2),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CIfNode {
+    pub value: Value,
+    pub continuation: Continuation,
+    pub rule_str: String,
+    pub premise: Box<RuleNode>,
+}
+impl CIfNode {
+    // b => {if _ then e1 else e2} >> k
+    pub fn new(
+        value: Value,
+        then_expression: Expression,
+        else_expression: Expression,
+        continuation: Continuation,
+    ) -> Result<CIfNode, String> {
+        let flag = match &value {
+            Value::Bool(b) => b.clone(),
+            _ => {
+                let expression_str = format!(
+                    "if {} then {} else {}",
+                    value, then_expression, else_expression
+                );
+                return Err(get_no_rule_message(&expression_str));
+            }
+        };
+        let (premise, rule_str) = if flag == "true" {
+            (
+                RuleNode::new_eval(then_expression.clone(), continuation.clone())?,
+                "C-IfT",
+            )
+        } else {
+            (
+                RuleNode::new_eval(else_expression.clone(), continuation.clone())?,
+                "C-IfF",
+            )
+        };
+        Ok(CIfNode {
+            value,
+            continuation: continuation.push(Frame::If(then_expression, else_expression)),
+            rule_str: rule_str.to_string(),
+            premise: Box::new(premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_cont(
+            self.value.to_string(),
+            &self.continuation,
+            self.premise.get_val().to_string(),
+            self.rule_str,
+            Some(*self.premise),
+            None,
+        )
+    }
+}
+
+// EvalContML1 has no error rules, so an ill-typed program has no derivation at all
@@ -0,0 +1,110 @@
+use super::super::super::parser_evalml3::expression::Expression;
+use super::super::super::parser_evalml3::terms::IfTerm;
+use super::super::continuation::{Continuation, Frame};
+use super::super::nodes::RuleNode;
+use super::super::value::Value;
+use super::writer::RuleWriter;
+
+use std::io::{self, Write};
+
+#[derive(Debug, Clone)]
+pub struct EValNode {
+    pub expression: Expression,
+    pub continuation: Continuation,
+    pub value: Value,
+    pub premise: Box<RuleNode>,
+}
+impl EValNode {
+    pub fn new(
+        expression: Expression,
+        continuation: Continuation,
+        value: Value,
+    ) -> Result<EValNode, String> {
+        let premise = RuleNode::new_cont(value.clone(), continuation.clone())?;
+        Ok(EValNode {
+            expression,
+            continuation,
+            value,
+            premise: Box::new(premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let rule_str = match self.value {
+            Value::Num(_) => "E-Int",
+            Value::Bool(_) => "E-Bool",
+        };
+        writer.show_eval(
+            self.expression.to_string(),
+            &self.continuation,
+            self.premise.get_val().to_string(),
+            rule_str.to_string(),
+            Some(*self.premise),
+            None,
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EBinOpNode {
+    pub expression: Expression,
+    pub continuation: Continuation,
+    pub premise: Box<RuleNode>,
+}
+impl EBinOpNode {
+    pub fn new(expression: Expression, continuation: Continuation) -> Result<EBinOpNode, String> {
+        let (split_position, operator) = expression.get_split_position();
+        let (former, latter) = expression.get_splitted_expression(split_position);
+        let premise = RuleNode::new_eval(
+            former,
+            continuation.clone().push(Frame::EvalR(operator, latter)),
+        )?;
+        Ok(EBinOpNode {
+            expression,
+            continuation,
+            premise: Box::new(premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_eval(
+            self.expression.to_string(),
+            &self.continuation,
+            self.premise.get_val().to_string(),
+            "E-BinOp".to_string(),
+            Some(*self.premise),
+            None,
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EIfNode {
+    pub expression: Expression,
+    pub continuation: Continuation,
+    pub premise: Box<RuleNode>,
+}
+impl EIfNode {
+    pub fn new(
+        expression: Expression,
+        continuation: Continuation,
+        term: IfTerm,
+    ) -> Result<EIfNode, String> {
+        let frame = Frame::If(term.then_expression, term.else_expression);
+        let premise =
+            RuleNode::new_eval(term.condition_expression, continuation.clone().push(frame))?;
+        Ok(EIfNode {
+            expression,
+            continuation,
+            premise: Box::new(premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_eval(
+            self.expression.to_string(),
+            &self.continuation,
+            self.premise.get_val().to_string(),
+            "E-If".to_string(),
+            Some(*self.premise),
+            None,
+        )
+    }
+}
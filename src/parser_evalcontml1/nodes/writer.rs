@@ -0,0 +1,92 @@
+use super::super::continuation::Continuation;
+use super::super::nodes::{get_depth_space, RuleNode};
+
+use std::io::{self, Write};
+
+pub struct RuleWriter<W> {
+    w: W,
+    depth: usize,
+}
+impl<W: Write> RuleWriter<W> {
+    pub fn new(w: W, depth: usize) -> RuleWriter<W> {
+        RuleWriter { w, depth }
+    }
+
+    pub fn write_nl(&mut self) {
+        let _ = writeln!(self.w);
+    }
+
+    fn inc_depth(&mut self) {
+        self.depth += 2;
+    }
+    fn dec_depth(&mut self) {
+        self.depth -= 2;
+    }
+
+    // e >> k evalto v
+    pub fn show_eval(
+        &mut self,
+        expression_str: String,
+        continuation: &Continuation,
+        evalto_str: String,
+        rule_str: String,
+        premise1: Option<RuleNode>,
+        premise2: Option<RuleNode>,
+    ) -> io::Result<()> {
+        let judgement_str = format!(
+            "{} >> {} evalto {}",
+            expression_str, continuation, evalto_str
+        );
+        self.show_judgement(judgement_str, rule_str, premise1, premise2)
+    }
+
+    // v => k evalto v'
+    pub fn show_cont(
+        &mut self,
+        value_str: String,
+        continuation: &Continuation,
+        evalto_str: String,
+        rule_str: String,
+        premise1: Option<RuleNode>,
+        premise2: Option<RuleNode>,
+    ) -> io::Result<()> {
+        let judgement_str = format!("{} => {} evalto {}", value_str, continuation, evalto_str);
+        self.show_judgement(judgement_str, rule_str, premise1, premise2)
+    }
+
+    pub fn show_judgement(
+        &mut self,
+        judgement_str: String,
+        rule_str: String,
+        premise1: Option<RuleNode>,
+        premise2: Option<RuleNode>,
+    ) -> io::Result<()> {
+        let _ = write!(
+            self.w,
+            "{}{} by {} {{",
+            get_depth_space(self.depth),
+            judgement_str,
+            rule_str,
+        );
+
+        let mut eol_necessity = false;
+
+        self.inc_depth();
+        if let Some(premise) = premise1 {
+            let _ = writeln!(self.w);
+            let _ = premise.show(self);
+            eol_necessity = true;
+        }
+        if let Some(premise) = premise2 {
+            let _ = writeln!(self.w, ";");
+            let _ = premise.show(self);
+            eol_necessity = true;
+        }
+        self.dec_depth();
+        if eol_necessity {
+            write!(self.w, "\n{}}}", get_depth_space(self.depth))
+        } else {
+            write!(self.w, "}}")
+        }
+    }
+}
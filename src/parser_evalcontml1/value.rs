@@ -0,0 +1,40 @@
+use super::super::parser_evalml3::expression::Expression;
+use super::super::parser_evalml3::lexer::{Token, Tokens};
+use super::super::parser_evalml3::terms::Term;
+
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Num(i32),
+    Bool(String),
+}
+impl Value {
+    // a value written as an expression, e.g. the left hand side of `v => k`
+    pub fn new(mut expression: Expression) -> Value {
+        if expression.terms.len() != 1 {
+            panic!("expect a value");
+        }
+        match expression.get_first_term() {
+            Term::Val(num) => Value::Num(num),
+            Term::Bool(b) => Value::Bool(b),
+            Term::Paren(expression) => Value::new(expression),
+            _ => panic!("expect a value"),
+        }
+    }
+    pub fn consume(tokens: &mut Tokens) -> Value {
+        match tokens.peek().expect("a token") {
+            Token::Int(_) => Value::Num(tokens.consume_num()),
+            Token::Bool(_) => Value::Bool(tokens.consume_bool()),
+            _ => panic!("unexpected"),
+        }
+    }
+}
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Num(num) => write!(f, "{}", num),
+            Value::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use super::super::parser_evalml3::expression::Expression;
+use super::super::parser_evalml3::lexer::{Token, Tokens};
+use super::super::parser_evalml3::terms::ListMatch;
+use super::value::{Environment, Value};
+
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub enum Frame {
+    // {E |- _ op e}
+    EvalR(Environment, String, Expression),
+    // {v op _}
+    BOp(Value, String),
+    // {E |- if _ then e1 else e2}
+    If(Environment, Expression, Expression),
+    // {E |- let x = _ in e}
+    Let(Environment, String, Expression),
+    // {E |- _ e}
+    EvalArg(Environment, Expression),
+    // {v _}
+    App(Value),
+    // {E |- match _ with [] -> e1 | x :: y -> e2}
+    Match(Environment, ListMatch),
+}
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Frame::EvalR(environment, operator, expression) => {
+                write!(f, "{{{}_ {} {}}}", environment, operator, expression)
+            }
+            Frame::BOp(value, operator) => match value {
+                // `::` is right associative, so a list on its left needs parentheses
+                Value::Cons(_, _) if operator == "::" => {
+                    write!(f, "{{({}) {} _}}", value, operator)
+                }
+                _ => write!(f, "{{{} {} _}}", value, operator),
+            },
+            Frame::If(environment, then_expression, else_expression) => write!(
+                f,
+                "{{{}if _ then {} else {}}}",
+                environment, then_expression, else_expression
+            ),
+            Frame::Let(environment, identifier, expression) => write!(
+                f,
+                "{{{}let {} = _ in {}}}",
+                environment, identifier, expression
+            ),
+            Frame::EvalArg(environment, expression) => {
+                write!(f, "{{{}_ {}}}", environment, expression)
+            }
+            Frame::App(value) => write!(f, "{{{} _}}", value),
+            Frame::Match(environment, match_term) => write!(
+                f,
+                "{{{}match _ with [] -> {} | {} :: {} -> {}}}",
+                environment,
+                match_term.nil_expression,
+                match_term.head_identifier,
+                match_term.tail_identifier,
+                match_term.cons_expression
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Continuation {
+    Ret,
+    Frame(Frame, Box<Continuation>),
+}
+impl Continuation {
+    pub fn new(tokens: &mut Tokens) -> Continuation {
+        match tokens.peek().expect("a token") {
+            Token::WILD => {
+                tokens.pop(); // consume _
+                Continuation::Ret
+            }
+            _ => panic!("todo"),
+        }
+    }
+    pub fn push(self, frame: Frame) -> Continuation {
+        Continuation::Frame(frame, Box::new(self))
+    }
+}
+impl fmt::Display for Continuation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Continuation::Ret => write!(f, "_"),
+            // `{...}` is short for `{...} >> _`
+            Continuation::Frame(frame, continuation) => match **continuation {
+                Continuation::Ret => write!(f, "{}", frame),
+                _ => write!(f, "{} >> {}", frame, continuation),
+            },
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use super::super::chapter1::util::get_no_rule_message;
+use super::super::parser_evalml3::expression::Expression;
+use super::super::parser_evalml3::nodes::bnodes::BOpNode;
+use super::super::parser_evalml3::nodes::writer::Rule;
+use super::super::parser_evalml3::terms::{ListMatch, Term};
+use super::continuation::{Continuation, Frame};
+use super::nodes::cnodes::{
+    get_bop_val, CBOpNode, CEvalArgNode, CEvalFunNode, CEvalRNode, CIfNode, CLetBodyNode,
+    CMatchNode, CRetNode,
+};
+use super::nodes::enodes::{EFrameNode, ELetCcNode, ELetRecNode, EValNode};
+use super::nodes::writer::RuleWriter;
+use super::value::{Environment, Value};
+use std::io::{self, Write};
+
+pub mod cnodes;
+pub mod enodes;
+pub mod writer;
+
+#[derive(Debug, Clone)]
+pub enum RuleNode {
+    EVal(EValNode),
+    EFrame(EFrameNode),
+    ELetRec(ELetRecNode),
+    ELetCc(ELetCcNode),
+    CRet(CRetNode),
+    CEvalR(CEvalRNode),
+    CBOp(CBOpNode),
+    CIf(CIfNode),
+    CLetBody(CLetBodyNode),
+    CEvalArg(CEvalArgNode),
+    CEvalFun(CEvalFunNode),
+    CMatch(CMatchNode),
+    BOp(BOpNode),
+}
+
+impl RuleNode {
+    // E |- e >> k evalto v
+    pub fn new_eval(
+        environment: Environment,
+        mut expression: Expression,
+        continuation: Continuation,
+    ) -> Result<RuleNode, String> {
+        let original_expression = expression.clone();
+        if expression.terms.len() != 1 {
+            let (split_position, operator) = expression.get_split_position();
+            let (former, latter) = expression.get_splitted_expression(split_position);
+            let rule_str = match operator.as_ref() {
+                "::" => "E-Cons",
+                _ => "E-BinOp",
+            };
+            let frame = Frame::EvalR(environment.clone(), operator, latter);
+            return Ok(RuleNode::EFrame(EFrameNode::new(
+                environment,
+                original_expression,
+                continuation,
+                former,
+                frame,
+                rule_str,
+            )?));
+        }
+        let node = match expression.get_first_term() {
+            Term::Val(num) => RuleNode::EVal(EValNode::new(
+                environment,
+                original_expression,
+                continuation,
+                Value::Num(num),
+                "E-Int",
+            )?),
+            Term::Bool(b) => RuleNode::EVal(EValNode::new(
+                environment,
+                original_expression,
+                continuation,
+                Value::Bool(b),
+                "E-Bool",
+            )?),
+            Term::Nil => RuleNode::EVal(EValNode::new(
+                environment,
+                original_expression,
+                continuation,
+                Value::Nil,
+                "E-Nil",
+            )?),
+            Term::Var(identifier) => {
+                let value = match environment.find_val(&identifier) {
+                    Some(value) => value,
+                    None => return Err(get_no_rule_message(&identifier)),
+                };
+                RuleNode::EVal(EValNode::new(
+                    environment,
+                    original_expression,
+                    continuation,
+                    value,
+                    "E-Var",
+                )?)
+            }
+            Term::Fun(fun_term) => {
+                let value = Value::Fun(fun_term, environment.clone());
+                RuleNode::EVal(EValNode::new(
+                    environment,
+                    original_expression,
+                    continuation,
+                    value,
+                    "E-Fun",
+                )?)
+            }
+            Term::Paren(expression) => RuleNode::new_eval(environment, expression, continuation)?,
+            Term::If(if_term) => {
+                let frame = Frame::If(
+                    environment.clone(),
+                    if_term.then_expression,
+                    if_term.else_expression,
+                );
+                RuleNode::EFrame(EFrameNode::new(
+                    environment,
+                    original_expression,
+                    continuation,
+                    if_term.condition_expression,
+                    frame,
+                    "E-If",
+                )?)
+            }
+            Term::Let(let_term) => {
+                let frame = Frame::Let(
+                    environment.clone(),
+                    let_term.let_expression.identifier,
+                    let_term.in_expression,
+                );
+                RuleNode::EFrame(EFrameNode::new(
+                    environment,
+                    original_expression,
+                    continuation,
+                    let_term.let_expression.expression,
+                    frame,
+                    "E-Let",
+                )?)
+            }
+            Term::App(app_term) => {
+                let terms: Vec<(String, Term)> = vec![("".to_string(), *app_term.function)];
+                let function_expression = Expression { terms };
+                let frame = Frame::EvalArg(environment.clone(), app_term.argument);
+                RuleNode::EFrame(EFrameNode::new(
+                    environment,
+                    original_expression,
+                    continuation,
+                    function_expression,
+                    frame,
+                    "E-App",
+                )?)
+            }
+            Term::Match(match_term) => {
+                let match_term = ListMatch::new(match_term);
+                let matched_expression = match_term.expression.clone();
+                let frame = Frame::Match(environment.clone(), match_term);
+                RuleNode::EFrame(EFrameNode::new(
+                    environment,
+                    original_expression,
+                    continuation,
+                    matched_expression,
+                    frame,
+                    "E-Match",
+                )?)
+            }
+            Term::LetRec(let_rec_term) => RuleNode::ELetRec(ELetRecNode::new(
+                environment,
+                original_expression,
+                continuation,
+                let_rec_term,
+            )?),
+            Term::LetCc(let_cc_term) => RuleNode::ELetCc(ELetCcNode::new(
+                environment,
+                original_expression,
+                continuation,
+                let_cc_term,
+            )?),
+            _ => return Err(get_no_rule_message(&original_expression.to_string())),
+        };
+        Ok(node)
+    }
+
+    // v => k evalto v'
+    pub fn new_cont(value: Value, continuation: Continuation) -> Result<RuleNode, String> {
+        let (frame, next_continuation) = match continuation {
+            Continuation::Ret => return Ok(RuleNode::CRet(CRetNode { value })),
+            Continuation::Frame(frame, next_continuation) => (frame, *next_continuation),
+        };
+        let node =
+            match frame {
+                Frame::EvalR(environment, operator, expression) => RuleNode::CEvalR(
+                    CEvalRNode::new(value, environment, operator, expression, next_continuation)?,
+                ),
+                Frame::BOp(left_value, operator) => RuleNode::CBOp(CBOpNode::new(
+                    left_value,
+                    operator,
+                    value,
+                    next_continuation,
+                )?),
+                Frame::If(environment, then_expression, else_expression) => {
+                    RuleNode::CIf(CIfNode::new(
+                        value,
+                        environment,
+                        then_expression,
+                        else_expression,
+                        next_continuation,
+                    )?)
+                }
+                Frame::Let(environment, identifier, expression) => {
+                    RuleNode::CLetBody(CLetBodyNode::new(
+                        value,
+                        environment,
+                        identifier,
+                        expression,
+                        next_continuation,
+                    )?)
+                }
+                Frame::EvalArg(environment, expression) => RuleNode::CEvalArg(CEvalArgNode::new(
+                    value,
+                    environment,
+                    expression,
+                    next_continuation,
+                )?),
+                Frame::App(function_value) => {
+                    RuleNode::CEvalFun(CEvalFunNode::new(function_value, value, next_continuation)?)
+                }
+                Frame::Match(environment, match_term) => RuleNode::CMatch(CMatchNode::new(
+                    value,
+                    environment,
+                    match_term,
+                    next_continuation,
+                )?),
+            };
+        Ok(node)
+    }
+
+    // every judgement in a derivation evaluates to the answer of the whole program
+    pub fn get_val(&self) -> Value {
+        match self {
+            RuleNode::EVal(node) => node.premise.get_val(),
+            RuleNode::EFrame(node) => node.premise.get_val(),
+            RuleNode::ELetRec(node) => node.premise.get_val(),
+            RuleNode::ELetCc(node) => node.premise.get_val(),
+            RuleNode::CRet(node) => node.value.clone(),
+            RuleNode::CEvalR(node) => node.premise.get_val(),
+            RuleNode::CBOp(node) => node.premise2.get_val(),
+            RuleNode::CIf(node) => node.premise.get_val(),
+            RuleNode::CLetBody(node) => node.premise.get_val(),
+            RuleNode::CEvalArg(node) => node.premise.get_val(),
+            RuleNode::CEvalFun(node) => node.premise.get_val(),
+            RuleNode::CMatch(node) => node.premise.get_val(),
+            RuleNode::BOp(node) => get_bop_val(node),
+        }
+    }
+}
+
+impl Rule for RuleNode {
+    type Context = ();
+
+    fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        match self {
+            RuleNode::EVal(node) => node.show(writer),
+            RuleNode::EFrame(node) => node.show(writer),
+            RuleNode::ELetRec(node) => node.show(writer),
+            RuleNode::ELetCc(node) => node.show(writer),
+            RuleNode::CRet(node) => node.show(writer),
+            RuleNode::CEvalR(node) => node.show(writer),
+            RuleNode::CBOp(node) => node.show(writer),
+            RuleNode::CIf(node) => node.show(writer),
+            RuleNode::CLetBody(node) => node.show(writer),
+            RuleNode::CEvalArg(node) => node.show(writer),
+            RuleNode::CEvalFun(node) => node.show(writer),
+            RuleNode::CMatch(node) => node.show(writer),
+            RuleNode::BOp(node) => node.show(writer),
+        }
+    }
+}
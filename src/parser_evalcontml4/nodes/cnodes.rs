@@ -0,0 +1,400 @@
+use super::super::super::chapter1::util::get_no_rule_message;
+use super::super::super::parser_evalml3::expression::Expression;
+use super::super::super::parser_evalml3::nodes::bnodes::BOpNode;
+use super::super::super::parser_evalml3::terms::ListMatch;
+use super::super::continuation::{Continuation, Frame};
+use super::super::nodes::RuleNode;
+use super::super::value::{Environment, Value};
+use super::writer::{Premises, RuleWriter};
+
+use std::io::{self, Write};
+
+#[derive(Debug, Clone)]
+pub struct CRetNode {
+    pub value: Value,
+}
+impl CRetNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_cont(
+            self.value.to_string(),
+            &Continuation::Ret,
+            self.value.to_string(),
+            "C-Ret".to_string(),
+            Premises(None, None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CEvalRNode {
+    pub value: Value,
+    pub continuation: Continuation,
+    pub rule_str: String,
+    pub premise: Box<RuleNode>,
+}
+impl CEvalRNode {
+    // v => {E |- _ op e} >> k
+    pub fn new(
+        value: Value,
+        environment: Environment,
+        operator: String,
+        expression: Expression,
+        continuation: Continuation,
+    ) -> Result<CEvalRNode, String> {
+        let premise = RuleNode::new_eval(
+            environment.clone(),
+            expression.clone(),
+            continuation
+                .clone()
+                .push(Frame::BOp(value.clone(), operator.clone())),
+        )?;
+        let rule_str = match operator.as_ref() {
+            "::" => "C-EvalConsR",
+            _ => "C-EvalR",
+        };
+        Ok(CEvalRNode {
+            value,
+            continuation: continuation.push(Frame::EvalR(environment, operator, expression)),
+            rule_str: rule_str.to_string(),
+            premise: Box::new(premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_cont(
+            self.value.to_string(),
+            &self.continuation,
+            self.premise.get_val().to_string(),
+            self.rule_str,
+            Premises(Some(*self.premise), None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CBOpNode {
+    pub value: Value,
+    pub continuation: Continuation,
+    pub rule_str: String,
+    pub premise1: Option<Box<RuleNode>>,
+    pub premise2: Box<RuleNode>,
+}
+impl CBOpNode {
+    // v2 => {v1 op _} >> k
+    pub fn new(
+        left_value: Value,
+        operator: String,
+        value: Value,
+        continuation: Continuation,
+    ) -> Result<CBOpNode, String> {
+        let frame = Frame::BOp(left_value.clone(), operator.clone());
+        if operator == "::" {
+            let cons = Value::Cons(Box::new(left_value), Box::new(value.clone()));
+            let premise2 = RuleNode::new_cont(cons, continuation.clone())?;
+            return Ok(CBOpNode {
+                value,
+                continuation: continuation.push(frame),
+                rule_str: "C-Cons".to_string(),
+                premise1: None,
+                premise2: Box::new(premise2),
+            });
+        }
+        let expression_str = format!("{} {} {}", left_value, operator, value);
+        let (i1, i2) = match (&left_value, &value) {
+            (Value::Num(i1), Value::Num(i2)) => (*i1, *i2),
+            _ => return Err(get_no_rule_message(&expression_str)),
+        };
+        let rule_str = match operator.as_ref() {
+            "+" => "C-Plus",
+            "-" => "C-Minus",
+            "*" => "C-Times",
+            "<" => "C-Lt",
+            _ => return Err(get_no_rule_message(&expression_str)),
+        };
+        let premise1 = BOpNode {
+            i1,
+            i2,
+            op: operator,
+        };
+        let premise2 = RuleNode::new_cont(get_bop_val(&premise1), continuation.clone())?;
+        Ok(CBOpNode {
+            value,
+            continuation: continuation.push(frame),
+            rule_str: rule_str.to_string(),
+            premise1: Some(Box::new(RuleNode::BOp(premise1))),
+            premise2: Box::new(premise2),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let val = self.premise2.get_val();
+        let (premise1, premise2) = match self.premise1 {
+            Some(premise1) => (*premise1, Some(*self.premise2)),
+            None => (*self.premise2, None),
+        };
+        writer.show_cont(
+            self.value.to_string(),
+            &self.continuation,
+            val.to_string(),
+            self.rule_str,
+            Premises(Some(premise1), premise2, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CIfNode {
+    pub value: Value,
+    pub continuation: Continuation,
+    pub rule_str: String,
+    pub premise: Box<RuleNode>,
+}
+impl CIfNode {
+    // b => {E |- if _ then e1 else e2} >> k
+    pub fn new(
+        value: Value,
+        environment: Environment,
+        then_expression: Expression,
+        else_expression: Expression,
+        continuation: Continuation,
+    ) -> Result<CIfNode, String> {
+        let flag = match &value {
+            Value::Bool(b) => b.clone(),
+            _ => {
+                let expression_str = format!(
+                    "if {} then {} else {}",
+                    value, then_expression, else_expression
+                );
+                return Err(get_no_rule_message(&expression_str));
+            }
+        };
+        let (branch_expression, rule_str) = if flag == "true" {
+            (then_expression.clone(), "C-IfT")
+        } else {
+            (else_expression.clone(), "C-IfF")
+        };
+        let premise =
+            RuleNode::new_eval(environment.clone(), branch_expression, continuation.clone())?;
+        let frame = Frame::If(environment, then_expression, else_expression);
+        Ok(CIfNode {
+            value,
+            continuation: continuation.push(frame),
+            rule_str: rule_str.to_string(),
+            premise: Box::new(premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_cont(
+            self.value.to_string(),
+            &self.continuation,
+            self.premise.get_val().to_string(),
+            self.rule_str,
+            Premises(Some(*self.premise), None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CLetBodyNode {
+    pub value: Value,
+    pub continuation: Continuation,
+    pub premise: Box<RuleNode>,
+}
+impl CLetBodyNode {
+    // v => {E |- let x = _ in e} >> k
+    pub fn new(
+        value: Value,
+        environment: Environment,
+        identifier: String,
+        expression: Expression,
+        continuation: Continuation,
+    ) -> Result<CLetBodyNode, String> {
+        let mut new_env = environment.clone();
+        new_env.set_val(identifier.clone(), value.clone());
+        let premise = RuleNode::new_eval(new_env, expression.clone(), continuation.clone())?;
+        Ok(CLetBodyNode {
+            value,
+            continuation: continuation.push(Frame::Let(environment, identifier, expression)),
+            premise: Box::new(premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_cont(
+            self.value.to_string(),
+            &self.continuation,
+            self.premise.get_val().to_string(),
+            "C-LetBody".to_string(),
+            Premises(Some(*self.premise), None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CEvalArgNode {
+    pub value: Value,
+    pub continuation: Continuation,
+    pub premise: Box<RuleNode>,
+}
+impl CEvalArgNode {
+    // v => {E |- _ e} >> k
+    pub fn new(
+        value: Value,
+        environment: Environment,
+        expression: Expression,
+        continuation: Continuation,
+    ) -> Result<CEvalArgNode, String> {
+        let premise = RuleNode::new_eval(
+            environment.clone(),
+            expression.clone(),
+            continuation.clone().push(Frame::App(value.clone())),
+        )?;
+        Ok(CEvalArgNode {
+            value,
+            continuation: continuation.push(Frame::EvalArg(environment, expression)),
+            premise: Box::new(premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_cont(
+            self.value.to_string(),
+            &self.continuation,
+            self.premise.get_val().to_string(),
+            "C-EvalArg".to_string(),
+            Premises(Some(*self.premise), None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CEvalFunNode {
+    pub value: Value,
+    pub continuation: Continuation,
+    pub rule_str: String,
+    pub premise: Box<RuleNode>,
+}
+impl CEvalFunNode {
+    // v2 => {v1 _} >> k
+    pub fn new(
+        function_value: Value,
+        value: Value,
+        continuation: Continuation,
+    ) -> Result<CEvalFunNode, String> {
+        let (premise, rule_str) = match function_value.clone() {
+            Value::Fun(fun_term, mut clojure_env) => {
+                clojure_env.set_val(fun_term.parameter, value.clone());
+                (
+                    RuleNode::new_eval(clojure_env, fun_term.function_body, continuation.clone())?,
+                    "C-EvalFun",
+                )
+            }
+            Value::RecFun(identifier, fun_term, mut clojure_env) => {
+                clojure_env.set_val(identifier, function_value.clone());
+                clojure_env.set_val(fun_term.parameter, value.clone());
+                (
+                    RuleNode::new_eval(clojure_env, fun_term.function_body, continuation.clone())?,
+                    "C-EvalFunR",
+                )
+            }
+            // the captured continuation replaces the current one
+            Value::Cont(captured_continuation) => (
+                RuleNode::new_cont(value.clone(), *captured_continuation)?,
+                "C-EvalFunC",
+            ),
+            _ => {
+                let expression_str = format!("{} {}", function_value, value);
+                return Err(get_no_rule_message(&expression_str));
+            }
+        };
+        Ok(CEvalFunNode {
+            value,
+            continuation: continuation.push(Frame::App(function_value)),
+            rule_str: rule_str.to_string(),
+            premise: Box::new(premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_cont(
+            self.value.to_string(),
+            &self.continuation,
+            self.premise.get_val().to_string(),
+            self.rule_str,
+            Premises(Some(*self.premise), None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CMatchNode {
+    pub value: Value,
+    pub continuation: Continuation,
+    pub rule_str: String,
+    pub premise: Box<RuleNode>,
+}
+impl CMatchNode {
+    // v => {E |- match _ with [] -> e1 | x :: y -> e2} >> k
+    pub fn new(
+        value: Value,
+        environment: Environment,
+        match_term: ListMatch,
+        continuation: Continuation,
+    ) -> Result<CMatchNode, String> {
+        let (premise, rule_str) = match value.clone() {
+            Value::Nil => (
+                RuleNode::new_eval(
+                    environment.clone(),
+                    match_term.nil_expression.clone(),
+                    continuation.clone(),
+                )?,
+                "C-MatchNil",
+            ),
+            Value::Cons(head, tail) => {
+                let mut new_env = environment.clone();
+                new_env.set_val(match_term.head_identifier.clone(), *head);
+                new_env.set_val(match_term.tail_identifier.clone(), *tail);
+                (
+                    RuleNode::new_eval(
+                        new_env,
+                        match_term.cons_expression.clone(),
+                        continuation.clone(),
+                    )?,
+                    "C-MatchCons",
+                )
+            }
+            _ => {
+                let expression_str = format!(
+                    "match {} with [] -> {} | {} :: {} -> {}",
+                    value,
+                    match_term.nil_expression,
+                    match_term.head_identifier,
+                    match_term.tail_identifier,
+                    match_term.cons_expression
+                );
+                return Err(get_no_rule_message(&expression_str));
+            }
+        };
+        Ok(CMatchNode {
+            value,
+            continuation: continuation.push(Frame::Match(environment, match_term)),
+            rule_str: rule_str.to_string(),
+            premise: Box::new(premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_cont(
+            self.value.to_string(),
+            &self.continuation,
+            self.premise.get_val().to_string(),
+            self.rule_str,
+            Premises(Some(*self.premise), None, None),
+        )
+    }
+}
+
+// the result of the arithmetic judgement `i1 op i2 is i3`
+pub fn get_bop_val(node: &BOpNode) -> Value {
+    match node.op.as_ref() {
+        "+" => Value::Num(node.i1 + node.i2),
+        "-" => Value::Num(node.i1 - node.i2),
+        "*" => Value::Num(node.i1 * node.i2),
+        "<" => Value::Bool((node.i1 < node.i2).to_string()),
+        _ => panic!("todo"),
+    }
+}
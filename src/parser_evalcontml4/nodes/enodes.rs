@@ -0,0 +1,164 @@
+use super::super::super::parser_evalml3::expression::Expression;
+use super::super::super::parser_evalml3::terms::{LetCcTerm, LetRecTerm};
+use super::super::continuation::{Continuation, Frame};
+use super::super::nodes::RuleNode;
+use super::super::value::{Environment, Value};
+use super::writer::{Premises, RuleWriter};
+
+use std::io::{self, Write};
+
+// E-Int, E-Bool, E-Nil, E-Var and E-Fun pass a value to the continuation
+#[derive(Debug, Clone)]
+pub struct EValNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub continuation: Continuation,
+    pub rule_str: String,
+    pub premise: Box<RuleNode>,
+}
+impl EValNode {
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        continuation: Continuation,
+        value: Value,
+        rule_str: &str,
+    ) -> Result<EValNode, String> {
+        let premise = RuleNode::new_cont(value, continuation.clone())?;
+        Ok(EValNode {
+            environment,
+            expression,
+            continuation,
+            rule_str: rule_str.to_string(),
+            premise: Box::new(premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_eval(
+            &self.environment,
+            self.expression.to_string(),
+            &self.continuation,
+            self.premise.get_val().to_string(),
+            self.rule_str,
+            Premises(Some(*self.premise), None, None),
+        )
+    }
+}
+
+// E-BinOp, E-Cons, E-If, E-Let, E-App and E-Match evaluate a subexpression
+// with a frame pushed onto the continuation
+#[derive(Debug, Clone)]
+pub struct EFrameNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub continuation: Continuation,
+    pub rule_str: String,
+    pub premise: Box<RuleNode>,
+}
+impl EFrameNode {
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        continuation: Continuation,
+        sub_expression: Expression,
+        frame: Frame,
+        rule_str: &str,
+    ) -> Result<EFrameNode, String> {
+        let premise = RuleNode::new_eval(
+            environment.clone(),
+            sub_expression,
+            continuation.clone().push(frame),
+        )?;
+        Ok(EFrameNode {
+            environment,
+            expression,
+            continuation,
+            rule_str: rule_str.to_string(),
+            premise: Box::new(premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_eval(
+            &self.environment,
+            self.expression.to_string(),
+            &self.continuation,
+            self.premise.get_val().to_string(),
+            self.rule_str,
+            Premises(Some(*self.premise), None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ELetRecNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub continuation: Continuation,
+    pub premise: Box<RuleNode>,
+}
+impl ELetRecNode {
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        continuation: Continuation,
+        term: LetRecTerm,
+    ) -> Result<ELetRecNode, String> {
+        let mut new_env = environment.clone();
+        let rec_fun = Value::RecFun(term.identifier.clone(), term.fun_term, environment.clone());
+        new_env.set_val(term.identifier, rec_fun);
+        let premise = RuleNode::new_eval(new_env, term.in_expression, continuation.clone())?;
+        Ok(ELetRecNode {
+            environment,
+            expression,
+            continuation,
+            premise: Box::new(premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_eval(
+            &self.environment,
+            self.expression.to_string(),
+            &self.continuation,
+            self.premise.get_val().to_string(),
+            "E-LetRec".to_string(),
+            Premises(Some(*self.premise), None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ELetCcNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub continuation: Continuation,
+    pub premise: Box<RuleNode>,
+}
+impl ELetCcNode {
+    // the current continuation is bound as a value
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        continuation: Continuation,
+        term: LetCcTerm,
+    ) -> Result<ELetCcNode, String> {
+        let mut new_env = environment.clone();
+        new_env.set_val(term.identifier, Value::Cont(Box::new(continuation.clone())));
+        let premise = RuleNode::new_eval(new_env, term.expression, continuation.clone())?;
+        Ok(ELetCcNode {
+            environment,
+            expression,
+            continuation,
+            premise: Box::new(premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_eval(
+            &self.environment,
+            self.expression.to_string(),
+            &self.continuation,
+            self.premise.get_val().to_string(),
+            "E-LetCc".to_string(),
+            Premises(Some(*self.premise), None, None),
+        )
+    }
+}
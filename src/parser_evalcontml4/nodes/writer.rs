@@ -0,0 +1,41 @@
+use super::super::super::parser_evalml3::nodes::writer;
+pub use super::super::super::parser_evalml3::nodes::writer::Premises;
+use super::super::continuation::Continuation;
+use super::super::value::Environment;
+use super::RuleNode;
+
+use std::io::{self, Write};
+
+pub type RuleWriter<W> = writer::RuleWriter<W, RuleNode>;
+
+impl<W: Write> RuleWriter<W> {
+    // E |- e >> k evalto v
+    pub fn show_eval(
+        &mut self,
+        environment: &Environment,
+        expression_str: String,
+        continuation: &Continuation,
+        evalto_str: String,
+        rule_str: String,
+        premises: Premises<RuleNode>,
+    ) -> io::Result<()> {
+        let judgement_str = format!(
+            "{}{} >> {} evalto {}",
+            environment, expression_str, continuation, evalto_str
+        );
+        self.show_judgement(judgement_str, rule_str, premises)
+    }
+
+    // v => k evalto v'
+    pub fn show_cont(
+        &mut self,
+        value_str: String,
+        continuation: &Continuation,
+        evalto_str: String,
+        rule_str: String,
+        premises: Premises<RuleNode>,
+    ) -> io::Result<()> {
+        let judgement_str = format!("{} => {} evalto {}", value_str, continuation, evalto_str);
+        self.show_judgement(judgement_str, rule_str, premises)
+    }
+}
@@ -0,0 +1,68 @@
+use super::super::parser_evalml3::environment;
+use super::super::parser_evalml3::lexer::{Token, Tokens};
+use super::super::parser_evalml3::terms::FunTerm;
+use super::continuation::Continuation;
+
+use std::fmt;
+
+pub type Environment = environment::Environment<Value>;
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Num(i32),
+    Bool(String),
+    Fun(FunTerm, Environment),
+    RecFun(String, FunTerm, Environment),
+    Nil,
+    Cons(Box<Value>, Box<Value>),
+    Cont(Box<Continuation>),
+}
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Num(num) => write!(f, "{}", num),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Fun(term, environment) => {
+                write!(f, "({})[{}]", environment.to_string_for_clojure(), term)
+            }
+            Value::RecFun(identifier, term, environment) => write!(
+                f,
+                "({})[rec {} = {}]",
+                environment.to_string_for_clojure(),
+                identifier,
+                term
+            ),
+            Value::Nil => write!(f, "[]"),
+            Value::Cons(head, tail) => match **head {
+                // `::` is right associative, so a list in the head needs parentheses
+                Value::Cons(_, _) => write!(f, "({}) :: {}", head, tail),
+                _ => write!(f, "{} :: {}", head, tail),
+            },
+            Value::Cont(continuation) => write!(f, "[{}]", continuation),
+        }
+    }
+}
+impl Value {
+    pub fn consume(tokens: &mut Tokens) -> Value {
+        let value = match tokens.peek() {
+            Some(token) => match token {
+                Token::Int(_) => Value::Num(tokens.consume_num()),
+                Token::Bool(_) => Value::Bool(tokens.consume_bool()),
+                Token::SS => {
+                    tokens.pop(); // consume [
+                    tokens.pop(); // consume ]
+                    Value::Nil
+                }
+                _ => panic!("unexpected"),
+            },
+            None => panic!("unexpected"),
+        };
+        match tokens.peek() {
+            Some(Token::Op(op)) if op == "::" => {
+                tokens.pop(); // consume ::
+                Value::Cons(Box::new(value), Box::new(Value::consume(tokens)))
+            }
+            _ => value,
+        }
+    }
+}
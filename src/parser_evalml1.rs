@@ -5,12 +5,17 @@ mod terms;
 use lexer::Lexer;
 use nodes::RuleNode;
 
-use std::io::{self, Write};
+use std::io::Write;
 
 pub fn derive<W: Write>(judgement: &str, w: &mut W) {
     let lexer = Lexer::new();
     let mut tokens = lexer.lex(judgement.to_string());
-    dbg!(&tokens);
-    let derivation_tree = RuleNode::new(&mut tokens);
-    let _ = derivation_tree.show(w, 0, true);
+    match RuleNode::new(&mut tokens) {
+        Ok(derivation_tree) => {
+            let _ = derivation_tree.show(w, 0, true);
+        }
+        Err(message) => {
+            let _ = writeln!(w, "{}", message);
+        }
+    }
 }
@@ -1,6 +1,7 @@
 use regex::Regex;
 
 #[derive(Debug, PartialEq, Clone)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Token {
     Int(String),
     Op(String),
@@ -79,14 +80,12 @@ impl Lexer {
         Lexer { re, names }
     }
     pub fn lex(&self, code: String) -> Tokens {
-        let mut code = code;
-        let tokens = self.tokenize(&mut code);
-        tokens
+        self.tokenize(&code)
     }
-    fn tokenize(&self, code: &mut String) -> Tokens {
+    fn tokenize(&self, code: &str) -> Tokens {
         let mut tokens: Vec<Token> = Vec::new();
 
-        for caps in self.re.captures_iter(&code) {
+        for caps in self.re.captures_iter(code) {
             let mut typ = String::from("nil");
             let val = String::from(&caps[0]);
             for name in &self.names {
@@ -114,14 +113,11 @@ impl Lexer {
 }
 fn make_regex(token_patterns: &Vec<(&str, &str)>) -> String {
     token_patterns
-        .into_iter()
+        .iter()
         .map(|pattern| format!("(?P<{}>{})", pattern.0, pattern.1))
         .collect::<Vec<String>>()
         .join("|")
 }
-fn get_names<'a, 'b>(token_patterns: &Vec<(&'a str, &'b str)>) -> Vec<&'a str> {
-    token_patterns
-        .into_iter()
-        .map(|pattern| pattern.0)
-        .collect()
+fn get_names<'a>(token_patterns: &Vec<(&'a str, &str)>) -> Vec<&'a str> {
+    token_patterns.iter().map(|pattern| pattern.0).collect()
 }
@@ -1,5 +1,7 @@
-use super::lexer::Tokens;
+use super::super::chapter1::util::get_not_hold_message;
+use super::lexer::{Token, Tokens};
 use super::terms::{IfTerms, Term, Terms};
+use std::fmt;
 use std::io::{self, Write};
 use std::ops::{Add, Mul, Sub};
 
@@ -12,19 +14,41 @@ pub enum RuleNode {
     EIfT(EIfTNode),
     EIfTError(EIfTErrorNode),
     EIfF(EIfFNode),
+    EIfFError(EIfFErrorNode),
     EPlus(EPlusNode),
-    EPlusErrorL(EPlusErrorLNode),
-    EPlusBoolR(EPlusBoolRNode),
-    ETimes(ETimesNode),
     EMinus(EMinusNode),
+    ETimes(ETimesNode),
     ELt(ELtNode),
-    ELtBoolR(ELtBoolRNode),
+    EBinError(EBinErrorNode),
 }
 impl RuleNode {
-    pub fn new(tokens: &mut Tokens) -> RuleNode {
+    pub fn new(tokens: &mut Tokens) -> Result<RuleNode, String> {
         let terms: Terms = Terms::new(tokens);
         let expression = Expression::new(terms.clone(), terms);
-        expression.get_rule()
+        match tokens.pop() {
+            Some(Token::Eval(_)) | None => {}
+            Some(token) => {
+                return Err(format!("syntax error: expects `evalto`, found {:?}", token))
+            }
+        }
+
+        // the value is computed when it's left as `?` or `evalto` is left off
+        let value = expression.get_val().to_string();
+        let expected = match tokens.pop() {
+            Some(Token::Int(val)) | Some(Token::Bool(val)) => Some(val),
+            Some(Token::ERR) => Some("error".to_string()),
+            _ => None,
+        };
+        if let Some(token) = tokens.pop() {
+            return Err(format!(
+                "syntax error: expects the end of the judgement, found {:?}",
+                token
+            ));
+        }
+        match expected {
+            Some(expected) if expected != value => Err(get_not_hold_message(&expected, &value)),
+            _ => Ok(expression.get_rule()),
+        }
     }
 
     pub fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
@@ -36,13 +60,12 @@ impl RuleNode {
             RuleNode::EIfT(node) => node.show(w, depth, with_newline),
             RuleNode::EIfTError(node) => node.show(w, depth, with_newline),
             RuleNode::EIfF(node) => node.show(w, depth, with_newline),
+            RuleNode::EIfFError(node) => node.show(w, depth, with_newline),
             RuleNode::EPlus(node) => node.show(w, depth, with_newline),
-            RuleNode::EPlusErrorL(node) => node.show(w, depth, with_newline),
-            RuleNode::EPlusBoolR(node) => node.show(w, depth, with_newline),
-            RuleNode::ETimes(node) => node.show(w, depth, with_newline),
             RuleNode::EMinus(node) => node.show(w, depth, with_newline),
+            RuleNode::ETimes(node) => node.show(w, depth, with_newline),
             RuleNode::ELt(node) => node.show(w, depth, with_newline),
-            RuleNode::ELtBoolR(node) => node.show(w, depth, with_newline),
+            RuleNode::EBinError(node) => node.show(w, depth, with_newline),
         }
     }
 }
@@ -109,18 +132,20 @@ impl Value {
         }
     }
 
-    fn to_string(self) -> String {
+    fn get_num(self) -> i32 {
         match self {
-            Value::Int(i) => i.to_string(),
-            Value::Bool(val) => val,
-            _ => panic!("todo"),
+            Value::Int(i) => i,
+            _ => panic!("unexpcted"),
         }
     }
+}
 
-    fn get_num(self) -> i32 {
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Value::Int(i) => i,
-            _ => panic!("unexpcted"),
+            Value::Int(i) => write!(f, "{}", i),
+            Value::Bool(val) => write!(f, "{}", val),
+            Value::Error => write!(f, "error"),
         }
     }
 }
@@ -144,10 +169,6 @@ impl Expression {
         } else {
             let (split_position, split_operator) = terms.get_split_position();
             let (former, latter) = terms.get_splitted_terms(split_position);
-            println!("================================");
-            println!("before: {:?}", former);
-            println!("after: {:?}", latter);
-            println!("================================");
             let e1 = Expression::new(former.clone(), former);
             let e2 = Expression::new(latter.clone(), latter);
             Expression::Bin(split_operator, Box::new(e1), Box::new(e2), origin_terms)
@@ -156,11 +177,17 @@ impl Expression {
 
     fn create_if(if_terms: IfTerms, origin_terms: Terms) -> Expression {
         let condition_exp = Box::new(Expression::new(
+            if_terms.condition_terms.clone(),
             if_terms.condition_terms,
-            origin_terms.clone(),
         ));
-        let if_exp = Box::new(Expression::new(if_terms.then_terms, origin_terms.clone()));
-        let else_exp = Box::new(Expression::new(if_terms.else_terms, origin_terms.clone()));
+        let if_exp = Box::new(Expression::new(
+            if_terms.then_terms.clone(),
+            if_terms.then_terms,
+        ));
+        let else_exp = Box::new(Expression::new(
+            if_terms.else_terms.clone(),
+            if_terms.else_terms,
+        ));
         Expression::If(condition_exp, if_exp, else_exp, origin_terms)
     }
     fn get_val(&self) -> Value {
@@ -184,7 +211,7 @@ impl Expression {
                         "false" => box_else_exp.get_val(),
                         _ => panic!("expects true or false"),
                     },
-                    _ => panic!("expects boolean value"),
+                    _ => Value::Error,
                 }
             }
         }
@@ -199,87 +226,92 @@ impl Expression {
             Expression::Bin(operator, box_ex1, box_ex2, _) => {
                 let e1 = *box_ex1;
                 let e2 = *box_ex2;
-                /*
-                println!("================================");
-                println!("e1: {:?}\t{:?}", e1.get_val(), e1);
-                println!("e2: {:?}\t{:?}", e2.get_val(), e2);
-                println!("================================");
-                */
-                match e1.get_val() {
-                    Value::Int(_) => match e2.get_val() {
-                        Value::Int(_) => match operator.as_ref() {
-                            "+" => RuleNode::EPlus(EPlusNode { e1, e2 }),
-                            "*" => RuleNode::ETimes(ETimesNode { e1, e2 }),
-                            "-" => RuleNode::EMinus(EMinusNode { e1, e2 }),
-                            "<" => RuleNode::ELt(ELtNode { e1, e2 }),
-                            _ => panic!("todo"),
-                        },
-                        Value::Bool(_) => match operator.as_ref() {
-                            "+" => RuleNode::EPlusBoolR(EPlusBoolRNode { e1, e2 }),
-                            "<" => RuleNode::ELtBoolR(ELtBoolRNode { e1, e2 }),
-                            _ => panic!("todo"),
-                        },
+                match (e1.get_val(), e2.get_val()) {
+                    (Value::Int(_), Value::Int(_)) => match operator.as_ref() {
+                        "+" => RuleNode::EPlus(EPlusNode { e1, e2 }),
+                        "*" => RuleNode::ETimes(ETimesNode { e1, e2 }),
+                        "-" => RuleNode::EMinus(EMinusNode { e1, e2 }),
+                        "<" => RuleNode::ELt(ELtNode { e1, e2 }),
                         _ => panic!("todo"),
                     },
-                    Value::Error => match e2.get_val() {
-                        Value::Int(_) => match operator.as_ref() {
-                            "+" => RuleNode::EPlusErrorL(EPlusErrorLNode { e1, e2 }),
+                    (v1, v2) => {
+                        // the left operand is evaluated first, so it's blamed when both are wrong
+                        let (kind, premise) = match (v1, v2) {
+                            (Value::Bool(_), _) => ("BoolL", e1.clone()),
+                            (Value::Error, _) => ("ErrorL", e1.clone()),
+                            (_, Value::Bool(_)) => ("BoolR", e2.clone()),
+                            _ => ("ErrorR", e2.clone()),
+                        };
+                        let name = match operator.as_ref() {
+                            "+" => "Plus",
+                            "*" => "Times",
+                            "-" => "Minus",
+                            "<" => "Lt",
                             _ => panic!("todo"),
-                        },
-                        _ => panic!("todo"),
-                    },
-                    _ => panic!("todo"),
+                        };
+                        RuleNode::EBinError(EBinErrorNode {
+                            rule: format!("E-{}{}", name, kind),
+                            operator,
+                            e1,
+                            e2,
+                            premise,
+                        })
+                    }
                 }
             }
-            Expression::If(box_condition_exp, box_then_exp, box_else_exp, _) => {
+            Expression::If(box_condition_exp, box_then_exp, box_else_exp, terms) => {
                 let cond_val = box_condition_exp.get_val();
                 let then_val = box_then_exp.get_val();
                 let else_val = box_else_exp.get_val();
-                /*
-                println!("cond_val:  {:?}", cond_val);
-                println!("then_val:  {:?}", then_val);
-                println!("else_val:  {:?}", else_val);
-                */
                 match cond_val {
                     Value::Bool(b) => match b.as_ref() {
                         "true" => match then_val {
-                            Value::Int(_) => RuleNode::EIfT(EIfTNode {
+                            Value::Error => RuleNode::EIfTError(EIfTErrorNode {
                                 condition_exp: *box_condition_exp,
                                 then_exp: *box_then_exp,
-                                else_exp: *box_else_exp,
+                                terms,
                             }),
-                            _ => RuleNode::EIfTError(EIfTErrorNode {
+                            _ => RuleNode::EIfT(EIfTNode {
                                 condition_exp: *box_condition_exp,
                                 then_exp: *box_then_exp,
-                                else_exp: *box_else_exp,
+                                terms,
                             }),
                         },
                         "false" => match else_val {
-                            Value::Int(_) => RuleNode::EIfF(EIfFNode {
+                            Value::Error => RuleNode::EIfFError(EIfFErrorNode {
                                 condition_exp: *box_condition_exp,
-                                then_exp: *box_then_exp,
                                 else_exp: *box_else_exp,
+                                terms,
+                            }),
+                            _ => RuleNode::EIfF(EIfFNode {
+                                condition_exp: *box_condition_exp,
+                                else_exp: *box_else_exp,
+                                terms,
                             }),
-                            _ => panic!("todo"),
                         },
                         _ => panic!("expects true or false"),
                     },
+                    Value::Error => RuleNode::EIfError(EIfErrorNode {
+                        condition_exp: *box_condition_exp,
+                        terms,
+                    }),
                     _ => RuleNode::EIfInt(EIfIntNode {
                         condition_exp: *box_condition_exp,
-                        then_exp: *box_then_exp,
-                        else_exp: *box_else_exp,
+                        terms,
                     }),
                 }
             }
         }
     }
-    fn to_string(self) -> String {
+}
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let origin_terms = match self {
             Expression::Value(_, terms) => terms,
             Expression::Bin(_, _, _, terms) => terms,
             Expression::If(_, _, _, terms) => terms,
         };
-        origin_terms.to_string()
+        write!(f, "{}", origin_terms)
     }
 }
 
@@ -322,16 +354,15 @@ impl EBoolNode {
 #[derive(Debug, Clone)]
 pub struct EIfIntNode {
     condition_exp: Expression,
-    then_exp: Expression,
-    else_exp: Expression,
+    terms: Terms,
 }
 impl EIfIntNode {
     fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
+        let _ = writeln!(
             w,
-            "{}{} evalto error by E-IfInt {{\n",
+            "{}{} evalto error by E-IfInt {{",
             get_depth_space(depth),
-            self.condition_exp.clone().to_string(),
+            self.terms.clone(),
         );
         let condition_premise = self.condition_exp.get_rule();
         let _ = condition_premise.show(w, depth + 2, true);
@@ -343,16 +374,15 @@ impl EIfIntNode {
 #[derive(Debug, Clone)]
 pub struct EIfErrorNode {
     condition_exp: Expression,
-    then_exp: Expression,
-    else_exp: Expression,
+    terms: Terms,
 }
 impl EIfErrorNode {
     fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
+        let _ = writeln!(
             w,
-            "{}{} evalto error by E-IfError {{\n",
+            "{}{} evalto error by E-IfError {{",
             get_depth_space(depth),
-            self.condition_exp.clone().to_string(),
+            self.terms.clone(),
         );
         let condition_premise = self.condition_exp.get_rule();
         let _ = condition_premise.show(w, depth + 2, true);
@@ -365,21 +395,21 @@ impl EIfErrorNode {
 pub struct EIfTNode {
     condition_exp: Expression,
     then_exp: Expression,
-    else_exp: Expression,
+    terms: Terms,
 }
 impl EIfTNode {
     fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
+        let _ = writeln!(
             w,
-            "{}{} evalto {} by E-IfT {{\n",
+            "{}{} evalto {} by E-IfT {{",
             get_depth_space(depth),
-            self.condition_exp.clone().to_string(),
-            self.then_exp.get_val().to_string(),
+            self.terms.clone(),
+            self.then_exp.get_val(),
         );
         let condition_premise = self.condition_exp.get_rule();
         let then_premise = self.then_exp.get_rule();
         let _ = condition_premise.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
+        let _ = writeln!(w, ";");
         let _ = then_premise.show(w, depth + 2, true);
         let nl = if with_newline { "\n" } else { "" };
         write!(w, "{}}}{}", get_depth_space(depth), nl)
@@ -390,20 +420,20 @@ impl EIfTNode {
 pub struct EIfTErrorNode {
     condition_exp: Expression,
     then_exp: Expression,
-    else_exp: Expression,
+    terms: Terms,
 }
 impl EIfTErrorNode {
     fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
+        let _ = writeln!(
             w,
-            "{}{} evalto error by E-IfTError {{\n",
+            "{}{} evalto error by E-IfTError {{",
             get_depth_space(depth),
-            self.condition_exp.clone().to_string(),
+            self.terms.clone(),
         );
         let condition_premise = self.condition_exp.get_rule();
         let then_premise = self.then_exp.get_rule();
         let _ = condition_premise.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
+        let _ = writeln!(w, ";");
         let _ = then_premise.show(w, depth + 2, true);
         let nl = if with_newline { "\n" } else { "" };
         write!(w, "{}}}{}", get_depth_space(depth), nl)
@@ -413,22 +443,22 @@ impl EIfTErrorNode {
 #[derive(Debug, Clone)]
 pub struct EIfFNode {
     condition_exp: Expression,
-    then_exp: Expression,
     else_exp: Expression,
+    terms: Terms,
 }
 impl EIfFNode {
     fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
+        let _ = writeln!(
             w,
-            "{}{} evalto {} by E-IfF {{\n",
+            "{}{} evalto {} by E-IfF {{",
             get_depth_space(depth),
-            self.condition_exp.clone().to_string(),
-            self.else_exp.get_val().to_string(),
+            self.terms.clone(),
+            self.else_exp.get_val(),
         );
         let condition_premise = self.condition_exp.get_rule();
         let else_premise = self.else_exp.get_rule();
         let _ = condition_premise.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
+        let _ = writeln!(w, ";");
         let _ = else_premise.show(w, depth + 2, true);
         let nl = if with_newline { "\n" } else { "" };
         write!(w, "{}}}{}", get_depth_space(depth), nl)
@@ -436,74 +466,56 @@ impl EIfFNode {
 }
 
 #[derive(Debug, Clone)]
-pub struct EPlusNode {
-    e1: Expression,
-    e2: Expression,
+pub struct EIfFErrorNode {
+    condition_exp: Expression,
+    else_exp: Expression,
+    terms: Terms,
 }
-impl EPlusNode {
+impl EIfFErrorNode {
     fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let i1 = self.e1.get_val().get_num();
-        let i2 = self.e2.get_val().get_num();
-        let _ = write!(
+        let _ = writeln!(
             w,
-            "{}{} + {} evalto {} by E-Plus {{\n",
+            "{}{} evalto error by E-IfFError {{",
             get_depth_space(depth),
-            self.e1.clone().to_string(),
-            self.e2.clone().to_string(),
-            i1 + i2
+            self.terms.clone(),
         );
-        let premise1 = self.e1.get_rule();
-        let premise2 = self.e2.get_rule();
-        let _ = premise1.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
-        let _ = premise2.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
-
-        let premise = BPlusNode { i1, i2 };
-        let _ = premise.show(w, depth + 2, true);
-
+        let condition_premise = self.condition_exp.get_rule();
+        let else_premise = self.else_exp.get_rule();
+        let _ = condition_premise.show(w, depth + 2, false);
+        let _ = writeln!(w, ";");
+        let _ = else_premise.show(w, depth + 2, true);
         let nl = if with_newline { "\n" } else { "" };
         write!(w, "{}}}{}", get_depth_space(depth), nl)
     }
 }
 
 #[derive(Debug, Clone)]
-pub struct EPlusErrorLNode {
+pub struct EPlusNode {
     e1: Expression,
     e2: Expression,
 }
-impl EPlusErrorLNode {
+impl EPlusNode {
     fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
+        let i1 = self.e1.get_val().get_num();
+        let i2 = self.e2.get_val().get_num();
+        let _ = writeln!(
             w,
-            "{}{} + {} evalto error by E-PlusErrorL {{\n",
+            "{}{} + {} evalto {} by E-Plus {{",
             get_depth_space(depth),
-            self.e1.clone().to_string(),
-            self.e2.clone().to_string(),
+            self.e1.clone(),
+            self.e2.clone(),
+            i1 + i2
         );
-        let premise = self.e1.get_rule();
-        let _ = premise.show(w, depth + 2, true);
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
-    }
-}
+        let premise1 = self.e1.get_rule();
+        let premise2 = self.e2.get_rule();
+        let _ = premise1.show(w, depth + 2, false);
+        let _ = writeln!(w, ";");
+        let _ = premise2.show(w, depth + 2, false);
+        let _ = writeln!(w, ";");
 
-#[derive(Debug, Clone)]
-pub struct EPlusBoolRNode {
-    e1: Expression,
-    e2: Expression,
-}
-impl EPlusBoolRNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
-            w,
-            "{}{} + {} evalto error by E-PlusBoolR {{\n",
-            get_depth_space(depth),
-            self.e1.clone().to_string(),
-            self.e2.clone().to_string(),
-        );
-        let premise = self.e2.get_rule();
+        let premise = BPlusNode { i1, i2 };
         let _ = premise.show(w, depth + 2, true);
+
         let nl = if with_newline { "\n" } else { "" };
         write!(w, "{}}}{}", get_depth_space(depth), nl)
     }
@@ -518,20 +530,20 @@ impl ETimesNode {
     fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
         let i1 = self.e1.get_val().get_num();
         let i2 = self.e2.get_val().get_num();
-        let _ = write!(
+        let _ = writeln!(
             w,
-            "{}{} * {} evalto {} by E-Times {{\n",
+            "{}{} * {} evalto {} by E-Times {{",
             get_depth_space(depth),
-            self.e1.clone().to_string(),
-            self.e2.clone().to_string(),
+            self.e1.clone(),
+            self.e2.clone(),
             i1 * i2
         );
         let premise1 = self.e1.get_rule();
         let premise2 = self.e2.get_rule();
         let _ = premise1.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
+        let _ = writeln!(w, ";");
         let _ = premise2.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
+        let _ = writeln!(w, ";");
 
         let premise = BTimesNode { i1, i2 };
         let _ = premise.show(w, depth + 2, true);
@@ -555,20 +567,20 @@ impl ELtNode {
             Value::Bool(val) => val,
             _ => panic!("expects a boolean value"),
         };
-        let _ = write!(
+        let _ = writeln!(
             w,
-            "{}{} < {} evalto {} by E-Lt {{\n",
+            "{}{} < {} evalto {} by E-Lt {{",
             get_depth_space(depth),
-            self.e1.clone().to_string(),
-            self.e2.clone().to_string(),
+            self.e1.clone(),
+            self.e2.clone(),
             b
         );
         let premise1 = self.e1.get_rule();
         let premise2 = self.e2.get_rule();
         let _ = premise1.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
+        let _ = writeln!(w, ";");
         let _ = premise2.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
+        let _ = writeln!(w, ";");
 
         let i1 = i1.get_num();
         let i2 = i2.get_num();
@@ -580,27 +592,6 @@ impl ELtNode {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct ELtBoolRNode {
-    e1: Expression,
-    e2: Expression,
-}
-impl ELtBoolRNode {
-    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
-        let _ = write!(
-            w,
-            "{}{} < {} evalto error by E-LtBoolR {{\n",
-            get_depth_space(depth),
-            self.e1.clone().to_string(),
-            self.e2.clone().to_string(),
-        );
-        let premise = self.e2.get_rule();
-        let _ = premise.show(w, depth + 2, true);
-        let nl = if with_newline { "\n" } else { "" };
-        write!(w, "{}}}{}", get_depth_space(depth), nl)
-    }
-}
-
 #[derive(Debug, Clone)]
 pub struct EMinusNode {
     e1: Expression,
@@ -610,20 +601,20 @@ impl EMinusNode {
     fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
         let i1 = self.e1.get_val().get_num();
         let i2 = self.e2.get_val().get_num();
-        let _ = write!(
+        let _ = writeln!(
             w,
-            "{}{} - {} evalto {} by E-Minus {{\n",
+            "{}{} - {} evalto {} by E-Minus {{",
             get_depth_space(depth),
-            self.e1.clone().to_string(),
-            self.e2.clone().to_string(),
+            self.e1.clone(),
+            self.e2.clone(),
             i1 - i2
         );
         let premise1 = self.e1.get_rule();
         let premise2 = self.e2.get_rule();
         let _ = premise1.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
+        let _ = writeln!(w, ";");
         let _ = premise2.show(w, depth + 2, false);
-        let _ = write!(w, ";\n");
+        let _ = writeln!(w, ";");
 
         let premise = BMinusNode { i1, i2 };
         let _ = premise.show(w, depth + 2, true);
@@ -710,6 +701,33 @@ impl BLtNode {
     }
 }
 
+// `e1 op e2 evalto error` by E-PlusBoolL, E-LtErrorR and so on, whose only premise is
+// the evaluation of the faulty operand
+#[derive(Debug, Clone)]
+pub struct EBinErrorNode {
+    rule: String,
+    operator: String,
+    e1: Expression,
+    e2: Expression,
+    premise: Expression,
+}
+impl EBinErrorNode {
+    fn show<W: Write>(self, w: &mut W, depth: usize, with_newline: bool) -> io::Result<()> {
+        let _ = writeln!(
+            w,
+            "{}{} {} {} evalto error by {} {{",
+            get_depth_space(depth),
+            self.e1,
+            self.operator,
+            self.e2,
+            self.rule,
+        );
+        let _ = self.premise.get_rule().show(w, depth + 2, true);
+        let nl = if with_newline { "\n" } else { "" };
+        write!(w, "{}}}{}", get_depth_space(depth), nl)
+    }
+}
+
 pub fn get_depth_space(depth: usize) -> String {
     let mut s = "".to_string();
     for _ in 0..depth {
@@ -1,6 +1,7 @@
 use super::lexer::{Token, Tokens};
 
 use std::collections::HashMap;
+use std::fmt;
 
 #[derive(Debug, Clone)]
 pub enum Term {
@@ -58,7 +59,7 @@ fn consume_paren_terms(tokens: &mut Tokens) -> Terms {
                     tokens.pop(); // consume )
                     break;
                 }
-                _ => panic!(format!("unexpected token: {:?}", token)),
+                _ => panic!("unexpected token: {:?}", token),
             },
             None => panic!("expect at least one eval token"),
         }
@@ -70,17 +71,14 @@ fn consume_expression_terms(tokens: &mut Tokens) -> Terms {
     let terms: Vec<Term> = Vec::new();
     let mut terms = Terms { terms };
     terms.push(Term::new(tokens, "".to_string()));
-    loop {
-        match tokens.peek() {
-            Some(token) => match token {
-                Token::Op(_) => {
-                    let op = tokens.consume_op();
-                    terms.push(Term::new(tokens, op));
-                }
-                Token::IF | Token::ELSE | Token::THEN | Token::Eval(_) | Token::PE => break,
-                _ => panic!(format!("unexpected token: {:?}", tokens)),
-            },
-            None => break,
+    while let Some(token) = tokens.peek() {
+        match token {
+            Token::Op(_) => {
+                let op = tokens.consume_op();
+                terms.push(Term::new(tokens, op));
+            }
+            Token::IF | Token::ELSE | Token::THEN | Token::Eval(_) | Token::PE => break,
+            _ => panic!("unexpected token: {:?}", tokens),
         }
     }
     terms
@@ -117,17 +115,14 @@ impl Terms {
         let terms: Vec<Term> = Vec::new();
         let mut terms = Terms { terms };
         terms.push(Term::new(tokens, "".to_string()));
-        loop {
-            match tokens.peek() {
-                Some(token) => match token {
-                    Token::Op(_) => {
-                        let op = tokens.consume_op();
-                        terms.push(Term::new(tokens, op));
-                    }
-                    Token::Eval(_) => break,
-                    _ => panic!(format!("unexpected token: {:?}", tokens)),
-                },
-                None => break,
+        while let Some(token) = tokens.peek() {
+            match token {
+                Token::Op(_) => {
+                    let op = tokens.consume_op();
+                    terms.push(Term::new(tokens, op));
+                }
+                Token::Eval(_) => break,
+                _ => panic!("unexpected token: {:?}", tokens),
             }
         }
         terms
@@ -158,12 +153,12 @@ impl Terms {
             if priority
                 <= *priorities
                     .get(&operator)
-                    .expect(&format!("cannot get operator priority: {}", operator))
+                    .unwrap_or_else(|| panic!("cannot get operator priority: {}", operator))
             {
                 split_position = i;
                 priority = *priorities
                     .get(&operator)
-                    .expect(&format!("cannot get operator priority: {}", operator));
+                    .unwrap_or_else(|| panic!("cannot get operator priority: {}", operator));
                 ret_op = operator;
             }
         }
@@ -186,10 +181,29 @@ impl Terms {
         latter.rm_first_operator();
         (former, latter)
     }
-    pub fn to_string(self) -> String {
+    fn rm_first_operator(&mut self) {
+        let mut new_terms: Vec<Term> = Vec::new();
+        let terms = self.terms.clone().into_iter();
+        for (i, term) in terms.enumerate() {
+            if i == 0 {
+                match term {
+                    Term::Single(_, num) => new_terms.push(Term::Single(String::from(""), num)),
+                    Term::SingleB(_, val) => new_terms.push(Term::SingleB(String::from(""), val)),
+                    Term::Paren(_, v) => new_terms.push(Term::Paren(String::from(""), v)),
+                    Term::If(_, v) => new_terms.push(Term::If(String::from(""), v)),
+                }
+            } else {
+                new_terms.push(term);
+            }
+        }
+        self.terms = new_terms;
+    }
+}
+
+impl fmt::Display for Terms {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut s = "".to_string();
-        let terms = self.terms.into_iter();
-        for term in terms {
+        for term in self.terms.clone() {
             match term {
                 Term::Single(operator, num) => {
                     s = add_op(operator, s);
@@ -209,31 +223,12 @@ impl Terms {
                     s = add_op(operator, s);
                     s += &format!(
                         "if {} then {} else {}",
-                        if_terms.condition_terms.to_string(),
-                        if_terms.then_terms.to_string(),
-                        if_terms.else_terms.to_string()
+                        if_terms.condition_terms, if_terms.then_terms, if_terms.else_terms
                     )
                 }
             }
         }
-        s
-    }
-    fn rm_first_operator(&mut self) {
-        let mut new_terms: Vec<Term> = Vec::new();
-        let terms = self.terms.clone().into_iter();
-        for (i, term) in terms.enumerate() {
-            if i == 0 {
-                match term {
-                    Term::Single(_, num) => new_terms.push(Term::Single(String::from(""), num)),
-                    Term::SingleB(_, val) => new_terms.push(Term::SingleB(String::from(""), val)),
-                    Term::Paren(_, v) => new_terms.push(Term::Paren(String::from(""), v)),
-                    Term::If(_, v) => new_terms.push(Term::If(String::from(""), v)),
-                }
-            } else {
-                new_terms.push(term);
-            }
-        }
-        self.terms = new_terms;
+        write!(f, "{}", s)
     }
 }
 
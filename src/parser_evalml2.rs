@@ -5,29 +5,42 @@ mod nodes;
 mod terms;
 mod value;
 
+use super::chapter1::util::check_stated_result;
 use environment::Environment;
 use expression::Expression;
-use lexer::Lexer;
+use lexer::{Lexer, Token};
 use nodes::writer::RuleWriter;
 use nodes::RuleNode;
 
-use std::io::{self, Write};
+use std::io::Write;
 
 pub fn derive<W: Write>(judgement: &str, w: &mut W) {
     let lexer = Lexer::new();
     let mut tokens = lexer.lex(judgement.to_string());
-    dbg!(&tokens);
 
     let environment = Environment::new(&mut tokens);
     tokens.pop(); // consume |-
     let expression = Expression::new(&mut tokens);
-    println!("++++++++++++++++++++++");
-    println!("expression: {:?}", expression);
-    println!("++++++++++++++++++++++");
+    if !tokens.consume_relation(&Token::Eval("evalto".to_string())) {
+        let _ = writeln!(
+            w,
+            "{}",
+            lexer.get_syntax_error(judgement, &tokens, "expects `evalto`")
+        );
+        return;
+    }
+
+    let value = expression.clone().get_val(environment.clone()).to_string();
+    if let Err(message) = check_stated_result(
+        &tokens.tokens,
+        &value,
+        |derivable| lexer.lex(derivable).tokens,
+        |count| lexer.get_tail(judgement, count),
+    ) {
+        let _ = writeln!(w, "{}", message);
+        return;
+    }
     let derivation_tree = RuleNode::new(environment, expression);
-    println!("++++++++++++++++++++++");
-    println!("derivation_tree: {:?}", derivation_tree);
-    println!("++++++++++++++++++++++");
     let mut writer = RuleWriter::new(w, 0);
     let _ = derivation_tree.show(&mut writer);
     writer.write_nl();
@@ -1,6 +1,8 @@
 use super::lexer::{Token, Tokens};
 use super::value::Value;
 
+use std::fmt;
+
 #[derive(Debug, Clone)]
 pub struct Environment {
     pub stack: Vec<(String, Value)>,
@@ -61,7 +63,7 @@ impl Environment {
                 return loc;
             }
         }
-        panic!(format!("variable \'{}\' is not declared", identifier));
+        panic!("variable \'{}\' is not declared", identifier);
     }
 
     pub fn get_val(&self, identifier: &String) -> Value {
@@ -70,21 +72,20 @@ impl Environment {
                 return val;
             }
         }
-        panic!(format!("variable \'{}\' is not declared", identifier));
+        panic!("variable \'{}\' is not declared", identifier);
     }
-
-    pub fn to_string(self) -> String {
-        if self.stack.len() == 0 {
-            String::from("|- ")
-        } else {
-            let mut s = "".to_string();
-            for (i, (var, val)) in self.stack.iter().enumerate() {
-                if i != 0 {
-                    s += ", ";
-                }
-                s += &format!("{} = {}", var, val.to_string());
+}
+impl fmt::Display for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (var, val)) in self.stack.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
             }
-            s + " |- "
+            write!(f, "{} = {}", var, val)?;
+        }
+        if !self.stack.is_empty() {
+            write!(f, " ")?;
         }
+        write!(f, "|- ")
     }
 }
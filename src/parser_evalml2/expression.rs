@@ -4,6 +4,7 @@ use super::terms::Term;
 use super::value::Value;
 
 use std::collections::HashMap;
+use std::fmt;
 
 #[derive(Debug, Clone)]
 pub struct LetExpression {
@@ -21,13 +22,9 @@ impl LetExpression {
         }
     }
 }
-impl LetExpression {
-    pub fn to_string(self) -> String {
-        let mut s = "".to_string();
-        s += &self.identifier;
-        s += " = ";
-        s += &self.expression.to_string();
-        s
+impl fmt::Display for LetExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.identifier, self.expression)
     }
 }
 
@@ -35,6 +32,40 @@ impl LetExpression {
 pub struct Expression {
     pub terms: Vec<(String, Term)>,
 }
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut s = "".to_string();
+        for (operator, term) in self.terms.clone() {
+            match term {
+                Term::Val(num) => {
+                    s = add_op(operator, s);
+                    s += &num.to_string()
+                }
+                Term::Var(identifier) => {
+                    s = add_op(operator, s);
+                    s += &identifier
+                }
+                Term::If(if_term) => {
+                    s = add_op(operator, s);
+                    s += &format!(
+                        "if {} then {} else {}",
+                        if_term.condition_expression,
+                        if_term.then_expression,
+                        if_term.else_expression
+                    )
+                }
+                Term::Let(let_term) => {
+                    s = add_op(operator, s);
+                    s += &format!(
+                        "let {} in {}",
+                        let_term.let_expression, let_term.in_expression
+                    )
+                }
+            }
+        }
+        write!(f, "{}", s)
+    }
+}
 impl Expression {
     pub fn new(tokens: &mut Tokens) -> Expression {
         let mut terms: Vec<(String, Term)> = Vec::new();
@@ -43,19 +74,11 @@ impl Expression {
         let term = Term::new(tokens);
         terms.push((String::from(""), term));
 
-        // consume remaining token
-        loop {
-            match tokens.peek() {
-                Some(token) => match token {
-                    Token::Op(operator) => {
-                        tokens.pop(); // consume operator
-                        let term = Term::new(tokens);
-                        terms.push((operator, term));
-                    }
-                    _ => break,
-                },
-                None => panic!("unexpected"),
-            }
+        // consume remaining token; `evalto` may be left off
+        while let Some(Token::Op(operator)) = tokens.peek() {
+            tokens.pop(); // consume operator
+            let term = Term::new(tokens);
+            terms.push((operator, term));
         }
         Expression { terms }
     }
@@ -83,42 +106,6 @@ impl Expression {
         }
     }
 
-    pub fn to_string(self) -> String {
-        let mut s = "".to_string();
-        let terms = self.terms.into_iter();
-        for t in terms {
-            let (operator, term) = t;
-            match term {
-                Term::Val(num) => {
-                    s = add_op(operator, s);
-                    s += &num.to_string()
-                }
-                Term::Var(identifier) => {
-                    s = add_op(operator, s);
-                    s += &identifier
-                }
-                Term::If(if_term) => {
-                    s = add_op(operator, s);
-                    s += &format!(
-                        "if {} then {} else {}",
-                        if_term.condition_expression.to_string(),
-                        if_term.then_expression.to_string(),
-                        if_term.else_expression.to_string()
-                    )
-                }
-                Term::Let(let_term) => {
-                    s = add_op(operator, s);
-                    s += &format!(
-                        "let {} in {}",
-                        let_term.let_expression.to_string(),
-                        let_term.in_expression.to_string()
-                    )
-                }
-            }
-        }
-        s
-    }
-
     pub fn get_split_position(&self) -> (usize, String) {
         let mut priorities: HashMap<String, usize> = HashMap::new();
         priorities.insert("".to_string(), 0);
@@ -131,16 +118,16 @@ impl Expression {
         let mut priority: usize = 0;
         let mut ret_op: String = "".to_string();
         let terms = self.terms.clone();
-        for (i, (operator, term)) in terms.into_iter().enumerate() {
+        for (i, (operator, _term)) in terms.into_iter().enumerate() {
             if priority
                 <= *priorities
                     .get(&operator)
-                    .expect(&format!("cannot get operator priority: {}", operator))
+                    .unwrap_or_else(|| panic!("cannot get operator priority: {}", operator))
             {
                 split_position = i;
                 priority = *priorities
                     .get(&operator)
-                    .expect(&format!("cannot get operator priority: {}", operator));
+                    .unwrap_or_else(|| panic!("cannot get operator priority: {}", operator));
                 ret_op = operator;
             }
         }
@@ -3,13 +3,13 @@ use super::value::Value;
 use regex::Regex;
 
 #[derive(Debug, PartialEq, Clone)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Token {
     Int(String),
     Op(String),
     Bool(String),
     Eval(String),
     Var(String),
-    IDE(String),
     ENV,
     ERR,
     COMMA,
@@ -38,6 +38,18 @@ impl Tokens {
         let mut tokens = self.clone();
         tokens.pop()
     }
+    // `evalto` between the expression and its value, which may be left off along with
+    // the value. false when anything else follows the expression
+    pub fn consume_relation(&mut self, relation: &Token) -> bool {
+        match self.peek() {
+            None => true,
+            Some(ref token) if token == relation => {
+                self.pop();
+                true
+            }
+            _ => false,
+        }
+    }
     pub fn consume_val(&mut self) -> Value {
         match self.peek() {
             Some(token) => match token {
@@ -69,13 +81,6 @@ impl Tokens {
             _ => panic!(""),
         }
     }
-    pub fn consume_op(&mut self) -> String {
-        let token = self.pop().expect("");
-        match token {
-            Token::Op(op) => op,
-            _ => panic!(""),
-        }
-    }
 }
 
 pub struct Lexer {
@@ -110,15 +115,28 @@ impl Lexer {
         let re = Regex::new(&re).expect("something went wrong making the regex");
         Lexer { re, names }
     }
+    // the text the last `count` tokens of code were lexed from, e.g. the value after evalto
+    pub fn get_tail(&self, code: &str, count: usize) -> String {
+        let starts: Vec<usize> = self.re.find_iter(code).map(|m| m.start()).collect();
+        match starts.len().checked_sub(count) {
+            Some(i) if i < starts.len() => code[starts[i]..].trim().to_string(),
+            _ => "".to_string(),
+        }
+    }
+    // a syntax error naming the text the parser stopped at, e.g. "expects `evalto`, found `8`"
+    pub fn get_syntax_error(&self, code: &str, tokens: &Tokens, expected: &str) -> String {
+        match self.get_tail(code, tokens.tokens.len()).as_ref() {
+            "" => format!("syntax error: {} at the end", expected),
+            tail => format!("syntax error: {}, found `{}`", expected, tail),
+        }
+    }
     pub fn lex(&self, code: String) -> Tokens {
-        let mut code = code;
-        let tokens = self.tokenize(&mut code);
-        tokens
+        self.tokenize(&code)
     }
-    fn tokenize(&self, code: &mut String) -> Tokens {
+    fn tokenize(&self, code: &str) -> Tokens {
         let mut tokens: Vec<Token> = Vec::new();
 
-        for caps in self.re.captures_iter(&code) {
+        for caps in self.re.captures_iter(code) {
             let mut typ = String::from("nil");
             let val = String::from(&caps[0]);
             for name in &self.names {
@@ -152,14 +170,11 @@ impl Lexer {
 }
 fn make_regex(token_patterns: &Vec<(&str, &str)>) -> String {
     token_patterns
-        .into_iter()
+        .iter()
         .map(|pattern| format!("(?P<{}>{})", pattern.0, pattern.1))
         .collect::<Vec<String>>()
         .join("|")
 }
-fn get_names<'a, 'b>(token_patterns: &Vec<(&'a str, &'b str)>) -> Vec<&'a str> {
-    token_patterns
-        .into_iter()
-        .map(|pattern| pattern.0)
-        .collect()
+fn get_names<'a>(token_patterns: &Vec<(&'a str, &str)>) -> Vec<&'a str> {
+    token_patterns.iter().map(|pattern| pattern.0).collect()
 }
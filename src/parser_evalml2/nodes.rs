@@ -10,6 +10,7 @@ pub mod enodes;
 pub mod writer;
 
 #[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
 pub enum RuleNode {
     EVar(EVarNode),
     EVal(EValNode),
@@ -1,4 +1,4 @@
-use super::writer::RuleWriter;
+use super::writer::{Premises, RuleWriter};
 use std::io::{self, Write};
 
 #[derive(Debug, Clone)]
@@ -16,9 +16,7 @@ impl BOpNode {
                 (self.i1 + self.i2).to_string(),
                 "B-Plus".to_string(),
                 true,
-                None,
-                None,
-                None,
+                Premises(None, None, None),
             ),
             "*" => writer.show_rule(
                 None,
@@ -26,9 +24,7 @@ impl BOpNode {
                 (self.i1 * self.i2).to_string(),
                 "B-Times".to_string(),
                 true,
-                None,
-                None,
-                None,
+                Premises(None, None, None),
             ),
             "-" => writer.show_rule(
                 None,
@@ -36,9 +32,7 @@ impl BOpNode {
                 (self.i1 - self.i2).to_string(),
                 "B-Minus".to_string(),
                 true,
-                None,
-                None,
-                None,
+                Premises(None, None, None),
             ),
             _ => panic!("todo"),
         }
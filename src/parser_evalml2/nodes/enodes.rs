@@ -4,7 +4,7 @@ use super::super::nodes::RuleNode;
 use super::super::terms::{IfTerm, LetTerm, Term};
 use super::super::value::Value;
 use super::bnodes::BOpNode;
-use super::writer::RuleWriter;
+use super::writer::{Premises, RuleWriter};
 
 use std::io::{self, Write};
 
@@ -35,9 +35,7 @@ impl EVarNode {
             self.environment.get_val(&identifier).to_string(),
             rule_str,
             false,
-            premise,
-            None,
-            None,
+            Premises(premise, None, None),
         )
     }
 }
@@ -62,7 +60,7 @@ impl EIfNode {
             Value::Bool(b) => b,
         };
 
-        if flag == String::from("true") {
+        if flag == "true" {
             let condition_premise = RuleNode::new(self.environment.clone(), condition_expression);
             let then_premise = RuleNode::new(self.environment.clone(), then_expression.clone());
             writer.show_rule(
@@ -71,9 +69,7 @@ impl EIfNode {
                 then_expression.get_val(self.environment).to_string(),
                 "E-IfT".to_string(),
                 false,
-                Some(condition_premise),
-                Some(then_premise),
-                None,
+                Premises(Some(condition_premise), Some(then_premise), None),
             )
         } else {
             panic!("todo")
@@ -113,9 +109,7 @@ impl EBNode {
             val_str,
             rule_str,
             false,
-            Some(premise1),
-            Some(premise2),
-            Some(premise),
+            Premises(Some(premise1), Some(premise2), Some(premise)),
         )
     }
 }
@@ -139,9 +133,7 @@ impl EValNode {
                     .to_string(),
                 "E-Int".to_string(),
                 false,
-                None,
-                None,
-                None,
+                Premises(None, None, None),
             ),
             _ => panic!("unexpected"),
         }
@@ -172,9 +164,7 @@ impl ELetNode {
             self.term.get_val(self.environment).to_string(),
             "E-Let".to_string(),
             false,
-            Some(let_premise),
-            Some(in_premise),
-            None,
+            Premises(Some(let_premise), Some(in_premise), None),
         )
     }
 }
@@ -1,3 +1,4 @@
+pub use super::super::super::parser_evalml3::nodes::writer::Premises;
 use super::super::environment::Environment;
 use super::super::nodes::{get_depth_space, RuleNode};
 use super::bnodes::BOpNode;
@@ -14,7 +15,7 @@ impl<W: Write> RuleWriter<W> {
     }
 
     pub fn write_nl(&mut self) {
-        let _ = write!(self.w, "\n");
+        let _ = writeln!(self.w);
     }
 
     fn inc_depth(&mut self) {
@@ -31,10 +32,9 @@ impl<W: Write> RuleWriter<W> {
         evalto_str: String,
         rule_str: String,
         is_bnode: bool,
-        premise1: Option<RuleNode>,
-        premise2: Option<RuleNode>,
-        premise3: Option<BOpNode>,
+        premises: Premises<RuleNode, BOpNode>,
     ) -> io::Result<()> {
+        let Premises(premise1, premise2, premise3) = premises;
         let environment_str = match environment.clone() {
             Some(env) => env.to_string(),
             None => "".to_string(),
@@ -46,7 +46,7 @@ impl<W: Write> RuleWriter<W> {
             get_depth_space(self.depth),
             environment_str,
             expression_str,
-            eq_str.to_string(),
+            eq_str,
             evalto_str,
             rule_str,
         );
@@ -55,17 +55,17 @@ impl<W: Write> RuleWriter<W> {
 
         self.inc_depth();
         if let Some(premise) = premise1 {
-            let _ = write!(self.w, "\n");
+            let _ = writeln!(self.w);
             let _ = premise.show(self);
             eol_necessity = true;
         }
         if let Some(premise) = premise2 {
-            let _ = write!(self.w, ";\n");
+            let _ = writeln!(self.w, ";");
             let _ = premise.show(self);
             eol_necessity = true;
         }
         if let Some(premise) = premise3 {
-            let _ = write!(self.w, ";\n");
+            let _ = writeln!(self.w, ";");
             let _ = premise.show(self);
             eol_necessity = true;
         }
@@ -52,7 +52,7 @@ impl Term {
         match self {
             Term::Val(num) => Value::Num(num),
             Term::Var(identifier) => environment.get_val(&identifier),
-            Term::If(_if_term) => panic!("todo"),
+            Term::If(if_term) => if_term.get_val(environment),
             Term::Let(let_term) => let_term.get_val(environment),
         }
     }
@@ -73,6 +73,18 @@ pub struct IfTerm {
     pub then_expression: Expression,
     pub else_expression: Expression,
 }
+impl IfTerm {
+    pub fn get_val(self, environment: Environment) -> Value {
+        match self.condition_expression.get_val(environment.clone()) {
+            Value::Bool(b) => match b.as_ref() {
+                "true" => self.then_expression.get_val(environment),
+                "false" => self.else_expression.get_val(environment),
+                _ => panic!("expects true or false"),
+            },
+            Value::Num(_) => panic!("unexpected"),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct LetTerm {
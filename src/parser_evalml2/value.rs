@@ -1,3 +1,4 @@
+use std::fmt;
 use std::ops::{Add, Mul, Sub};
 
 #[derive(Debug, Clone)]
@@ -5,13 +6,15 @@ pub enum Value {
     Num(i32),
     Bool(String),
 }
-impl Value {
-    pub fn to_string(&self) -> String {
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Value::Num(num) => num.to_string(),
-            Value::Bool(b) => b.to_string(),
+            Value::Num(num) => write!(f, "{}", num),
+            Value::Bool(b) => write!(f, "{}", b),
         }
     }
+}
+impl Value {
     pub fn get_num(self) -> i32 {
         match self {
             Value::Num(i) => i,
@@ -1,34 +1,47 @@
-mod environment;
-mod expression;
-mod lexer;
-mod nodes;
-mod terms;
-mod value;
+pub(crate) mod environment;
+pub(crate) mod expression;
+pub(crate) mod lexer;
+pub(crate) mod nodes;
+pub(crate) mod pattern;
+pub(crate) mod terms;
+pub(crate) mod value;
 
-use environment::Environment;
+use super::chapter1::util::check_stated_result;
 use expression::Expression;
-use lexer::Lexer;
-use nodes::writer::RuleWriter;
+use lexer::{Lexer, Token};
+use nodes::writer::{Rule, RuleWriter};
 use nodes::RuleNode;
+use value::{Environment, Value};
 
-use std::io::{self, Write};
+use std::io::Write;
 
 pub fn derive<W: Write>(judgement: &str, w: &mut W) {
     let lexer = Lexer::new();
     let mut tokens = lexer.lex(judgement.to_string());
-    dbg!(&tokens);
 
-    let environment = Environment::new(&mut tokens);
+    let environment = Environment::new(&mut tokens, Value::consume);
     tokens.pop(); // consume |-
     let expression = Expression::new(&mut tokens);
-    println!("++++++++++++++++++++++");
-    dbg!(expression.clone());
-    println!("++++++++++++++++++++++");
-    println!("++++++++++++++++++++++");
+    if !tokens.consume_relation(&Token::Eval("evalto".to_string())) {
+        let _ = writeln!(
+            w,
+            "{}",
+            lexer.get_syntax_error(judgement, &tokens, "expects `evalto`")
+        );
+        return;
+    }
+
     let derivation_tree = RuleNode::new(environment, expression);
-    println!("++++++++++++++++++++++");
-    dbg!(derivation_tree.clone());
-    println!("++++++++++++++++++++++");
+    let value = derivation_tree.get_val().to_string();
+    if let Err(message) = check_stated_result(
+        &tokens.tokens,
+        &value,
+        |derivable| lexer.lex(derivable).tokens,
+        |count| lexer.get_tail(judgement, count),
+    ) {
+        let _ = writeln!(w, "{}", message);
+        return;
+    }
     let mut writer = RuleWriter::new(w, 0);
     let _ = derivation_tree.show(&mut writer);
     writer.write_nl();
@@ -1,48 +1,73 @@
 use super::lexer::{Token, Tokens};
-use super::value::Value;
 
-#[derive(Debug, Clone)]
-pub struct Environment {
-    pub stack: Vec<(String, Value)>,
+use std::fmt::{self, Display};
+
+// each system binds variables to its own values; nameless systems bind `.`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Environment<V> {
+    pub stack: Vec<(String, V)>,
 }
-impl Environment {
-    pub fn new(tokens: &mut Tokens) -> Environment {
-        let mut stack: Vec<(String, Value)> = Vec::new();
+impl<V: Clone> Environment<V> {
+    pub fn new(
+        tokens: &mut Tokens,
+        mut consume_val: impl FnMut(&mut Tokens) -> V,
+    ) -> Environment<V> {
+        Environment::try_new(tokens, |tokens| Ok(consume_val(tokens))).expect("an environment")
+    }
 
-        if let Some(Token::ENV) = tokens.peek() {
-            return Environment { stack };
-        }
+    // the environment of a judgement whose values can be malformed, e.g. types
+    pub fn try_new(
+        tokens: &mut Tokens,
+        consume_val: impl FnMut(&mut Tokens) -> Result<V, String>,
+    ) -> Result<Environment<V>, String> {
+        Environment::try_new_until(tokens, Token::ENV, "|-", consume_val)
+    }
 
-        let var: String = match tokens.peek().expect("") {
-            Token::Var(_) => {
-                let var = tokens.consume_var();
-                tokens.pop(); // consume =
-                var
-            }
-            _ => panic!("unexpected"),
-        };
-        let val: Value = tokens.consume_val();
-        stack.push((var, val));
+    // the bindings up to `end`, which is left unconsumed: `|-` of a judgement, or `)` of a
+    // closure such as `(x=1)[fun y -> x + y]`
+    pub fn try_new_until(
+        tokens: &mut Tokens,
+        end: Token,
+        end_name: &str,
+        mut consume_val: impl FnMut(&mut Tokens) -> Result<V, String>,
+    ) -> Result<Environment<V>, String> {
+        let mut stack: Vec<(String, V)> = Vec::new();
+
+        if tokens.peek().as_ref() == Some(&end) {
+            return Ok(Environment { stack });
+        }
 
         loop {
-            match tokens.peek().expect("") {
-                Token::COMMA => {
-                    tokens.pop(); // consume ,
+            // a nameless environment lists bare values
+            let var: String = match tokens.peek() {
+                Some(Token::Var(_)) => {
                     let var = tokens.consume_var();
-                    tokens.pop(); // consume =
-                    let val = tokens.consume_val();
-                    stack.push((var, val));
+                    // `x = v`, or `x : t` in typing judgements
+                    match tokens.peek() {
+                        Some(Token::EQ) | Some(Token::COLON) => tokens.pop(),
+                        _ => return Err("expects `=`".to_string()),
+                    };
+                    var
+                }
+                _ => ".".to_string(),
+            };
+            let val: V = consume_val(tokens)?;
+            stack.push((var, val));
+
+            match tokens.peek() {
+                Some(Token::COMMA) => {
+                    tokens.pop(); // consume ,
                 }
-                Token::ENV => {
+                Some(ref token) if *token == end => {
                     break;
                 }
-                _ => panic!("unexpected"),
+                _ => return Err(format!("expects `,` or `{}`", end_name)),
             }
         }
-        Environment { stack }
+        Ok(Environment { stack })
     }
 
-    pub fn set_val(&mut self, identifier: String, value: Value) {
+    pub fn set_val(&mut self, identifier: String, value: V) {
         self.stack.push((identifier, value));
     }
 
@@ -50,31 +75,45 @@ impl Environment {
         self.stack.pop();
     }
 
-    pub fn _remove_val(&mut self, identifier: &String) {
-        let idx = self.stack.len() - 1 - self.get_match_loc(identifier);
-        self.stack.remove(idx);
-    }
-
     pub fn get_match_loc(&self, identifier: &String) -> usize {
         for (loc, (var, _val)) in self.stack.iter().rev().enumerate() {
             if var == identifier {
                 return loc;
             }
         }
-        panic!(format!("variable \'{}\' is not declared", identifier));
+        panic!("variable \'{}\' is not declared", identifier);
     }
 
-    pub fn get_val(&self, identifier: &String) -> Value {
-        for (var, val) in self.stack.clone().into_iter().rev() {
-            if &var == identifier {
-                return val;
+    pub fn get_val(&self, identifier: &String) -> V {
+        match self.find_val(identifier) {
+            Some(val) => val,
+            None => panic!("variable \'{}\' is not declared", identifier),
+        }
+    }
+
+    pub fn find_val(&self, identifier: &String) -> Option<V> {
+        for (var, val) in self.stack.iter().rev() {
+            if var == identifier {
+                return Some(val.clone());
             }
         }
-        panic!(format!("variable \'{}\' is not declared", identifier));
+        None
     }
 
-    pub fn to_string(self) -> String {
-        if self.stack.len() == 0 {
+    // #n refers to the n-th binding from the right, and None when there is no such binding
+    pub fn find_val_by_index(&self, index: usize) -> Option<V> {
+        let (_, val) = self.stack.iter().rev().nth(index.checked_sub(1)?)?;
+        Some(val.clone())
+    }
+}
+impl<V> Environment<V> {
+    // x = v, y = w |- or, in typing judgements, x : t, y : t' |-
+    pub fn to_string_by(
+        &self,
+        separator: &str,
+        mut val_to_string: impl FnMut(&V) -> String,
+    ) -> String {
+        if self.stack.is_empty() {
             String::from("|- ")
         } else {
             let mut s = "".to_string();
@@ -82,14 +121,23 @@ impl Environment {
                 if i != 0 {
                     s += ", ";
                 }
-                s += &format!("{} = {}", var, val.to_string());
+                s += &match var.as_ref() {
+                    "." => val_to_string(val),
+                    _ => format!("{}{}{}", var, separator, val_to_string(val)),
+                };
             }
             s + " |- "
         }
     }
-
-    pub fn to_string_for_clojure(self) -> String {
-        if self.stack.len() == 0 {
+}
+impl<V: Display> fmt::Display for Environment<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string_by(" = ", |val| val.to_string()))
+    }
+}
+impl<V: Display> Environment<V> {
+    pub fn to_string_for_clojure(&self) -> String {
+        if self.stack.is_empty() {
             String::from("")
         } else {
             let mut s = "".to_string();
@@ -97,9 +145,23 @@ impl Environment {
                 if i != 0 {
                     s += ", ";
                 }
-                s += &format!("{}={}", var, val.to_string());
+                s += &match var.as_ref() {
+                    "." => val.to_string(),
+                    _ => format!("{}={}", var, val),
+                };
             }
             s
         }
     }
+
+    pub fn to_string_for_bindings(&self) -> String {
+        let mut s = "".to_string();
+        for (i, (var, val)) in self.stack.iter().enumerate() {
+            if i != 0 {
+                s += ", ";
+            }
+            s += &format!("{} = {}", var, val);
+        }
+        s
+    }
 }
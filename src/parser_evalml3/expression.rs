@@ -1,9 +1,8 @@
-use super::environment::Environment;
 use super::lexer::{Token, Tokens};
 use super::terms::Term;
-use super::value::Value;
 
 use std::collections::HashMap;
+use std::fmt;
 
 #[derive(Debug, Clone)]
 pub struct LetExpression {
@@ -11,23 +10,19 @@ pub struct LetExpression {
     pub expression: Expression,
 }
 impl LetExpression {
-    pub fn new(tokens: &mut Tokens) -> LetExpression {
-        let identifier = tokens.consume_var();
-        tokens.pop(); // consume =
-        let expression = Expression::new(tokens);
-        LetExpression {
+    pub fn try_new(tokens: &mut Tokens) -> Result<LetExpression, String> {
+        let identifier = tokens.try_consume_var()?;
+        tokens.expect(Token::EQ, "=")?;
+        let expression = Expression::try_new(tokens)?;
+        Ok(LetExpression {
             identifier,
             expression,
-        }
+        })
     }
 }
-impl LetExpression {
-    pub fn to_string(&self, environment: &Environment) -> String {
-        let mut s = "".to_string();
-        s += &self.identifier;
-        s += " = ";
-        s += &self.expression.to_string(environment);
-        s
+impl fmt::Display for LetExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.identifier, self.expression)
     }
 }
 
@@ -35,62 +30,63 @@ impl LetExpression {
 pub struct Expression {
     pub terms: Vec<(String, Term)>,
 }
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut s = "".to_string();
+        for t in self.terms.clone() {
+            let (operator, term) = t;
+            s = add_op(operator, s);
+            s += &term.to_string();
+        }
+        write!(f, "{}", s)
+    }
+}
+// the terms are kept flat as written, so expressions are compared by the tree they parse to
+impl PartialEq for Expression {
+    fn eq(&self, other: &Expression) -> bool {
+        self.get_tree_string() == other.get_tree_string()
+    }
+}
 impl Expression {
     pub fn new(tokens: &mut Tokens) -> Expression {
+        Expression::try_new(tokens).expect("an expression")
+    }
+
+    pub fn try_new(tokens: &mut Tokens) -> Result<Expression, String> {
         let mut terms: Vec<(String, Term)> = Vec::new();
 
         // consume first token
-        let term = Term::new(tokens);
+        let term = Term::try_new(tokens)?;
         terms.push((String::from(""), term));
 
-        // consume remaining token
-        loop {
-            match tokens.peek() {
-                Some(token) => match token {
-                    Token::Op(operator) => {
-                        tokens.pop(); // consume operator
-                        let term = Term::new(tokens);
-                        terms.push((operator, term));
-                    }
-                    _ => break,
-                },
-                None => panic!("unexpected"),
-            }
+        // consume remaining token; `evalto` may be left off
+        while let Some(Token::Op(operator)) = tokens.peek() {
+            tokens.pop(); // consume operator
+            let term = Term::try_new(tokens)?;
+            terms.push((operator, term));
         }
-        Expression { terms }
+        Ok(Expression { terms })
     }
 
-    pub fn get_first_term(&mut self) -> Term {
-        let (_, term) = self.terms.pop().expect("");
-        term
-    }
-
-    pub fn get_val(mut self, environment: Environment) -> Value {
-        if self.terms.len() == 1 {
-            let (_, term) = self.terms.pop().expect("");
-            term.get_val(environment)
-        } else {
-            let (split_position, operator) = self.get_split_position();
-            let (former, latter) = self.get_splitted_expression(split_position);
-            let former_val = former.get_val(environment.clone());
-            let latter_val = latter.get_val(environment);
-            match operator.as_ref() {
-                "+" => former_val + latter_val,
-                "*" => former_val * latter_val,
-                "-" => former_val - latter_val,
-                _ => panic!(""),
-            }
+    // every operation parenthesized and no other parentheses, so that `(1 + 2) * 3` and
+    // `((1 + 2)) * (3)` both give `((1 + 2) * 3)`
+    pub fn get_tree_string(&self) -> String {
+        if let [(_, term)] = self.terms.as_slice() {
+            return term.get_tree_string();
         }
+        let (split_position, operator) = self.get_split_position();
+        let (former, latter) = self.get_splitted_expression(split_position);
+        format!(
+            "({} {} {})",
+            former.get_tree_string(),
+            operator,
+            latter.get_tree_string()
+        )
     }
 
-    pub fn to_string(&self, environment: &Environment) -> String {
-        let mut s = "".to_string();
-        for t in self.terms.clone() {
-            let (operator, term) = t;
-            s = add_op(operator, s);
-            s += &term.to_string(environment);
-        }
-        s
+    pub fn get_first_term(&mut self) -> Term {
+        let (_, term) = self.terms.pop().expect("");
+        term
     }
 
     pub fn get_split_position(&self) -> (usize, String) {
@@ -99,22 +95,25 @@ impl Expression {
         priorities.insert("*".to_string(), 10);
         priorities.insert("+".to_string(), 20);
         priorities.insert("-".to_string(), 20);
+        priorities.insert("::".to_string(), 25);
         priorities.insert("<".to_string(), 30);
+        priorities.insert(":=".to_string(), 40);
 
         let mut split_position = 0;
         let mut priority: usize = 0;
         let mut ret_op: String = "".to_string();
         let terms = self.terms.clone();
         for (i, (operator, _term)) in terms.into_iter().enumerate() {
-            if priority
-                <= *priorities
-                    .get(&operator)
-                    .expect(&format!("cannot get operator priority: {}", operator))
+            let operator_priority = *priorities
+                .get(&operator)
+                .unwrap_or_else(|| panic!("cannot get operator priority: {}", operator));
+            // :: and := are right associative, so they are split at their first occurrence
+            let is_right_associative = operator == "::" || operator == ":=";
+            if priority < operator_priority
+                || (priority == operator_priority && !is_right_associative)
             {
                 split_position = i;
-                priority = *priorities
-                    .get(&operator)
-                    .expect(&format!("cannot get operator priority: {}", operator));
+                priority = operator_priority;
                 ret_op = operator;
             }
         }
@@ -159,6 +158,8 @@ fn add_op(operator: String, s: String) -> String {
         "-" => s + " - ",
         "*" => s + " * ",
         "<" => s + " < ",
+        "::" => s + " :: ",
+        ":=" => s + " := ",
         _ => panic!("TODO"),
     }
 }
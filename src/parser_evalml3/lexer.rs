@@ -1,15 +1,16 @@
-use super::value::Value;
-
 use regex::Regex;
 
 #[derive(Debug, PartialEq, Clone)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Token {
     Int(String),
     Op(String),
     Bool(String),
     Eval(String),
     Var(String),
-    IDE(String),
+    Loc(String),
+    Index(String),
+    TVar(String),
     ENV,
     ERR,
     COMMA,
@@ -21,10 +22,26 @@ pub enum Token {
     IN,
     FUN,
     LET,
+    REC,
     THEN,
     ELSE,
     EQ,
+    TRANS,
     ARROW,
+    CONT,
+    PASS,
+    WILD,
+    BS,
+    BE,
+    MATCH,
+    WITH,
+    BAR,
+    REF,
+    DEREF,
+    SLASH,
+    DOT,
+    LETCC,
+    COLON,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -42,27 +59,60 @@ impl Tokens {
         let mut tokens = self.clone();
         tokens.pop()
     }
-    pub fn consume_val(&mut self) -> Value {
+    // the relation between the expression and its result, e.g. `evalto`, which may be left
+    // off along with the result. false when anything else follows the expression
+    pub fn consume_relation(&mut self, relation: &Token) -> bool {
         match self.peek() {
-            Some(token) => match token {
-                Token::Int(_) => Value::Num(self.consume_num()),
-                Token::Bool(_) => Value::Bool(self.consume_bool()),
-                _ => panic!("unexpected"),
-            },
-            None => panic!("unexpected"),
+            None => true,
+            Some(ref token) if token == relation => {
+                self.pop();
+                true
+            }
+            _ => false,
+        }
+    }
+    // the token the syntax requires next, e.g. `then` after the condition of if. it is left
+    // unconsumed on an error so that the message can name it
+    pub fn expect(&mut self, token: Token, name: &str) -> Result<(), String> {
+        if self.peek() == Some(token) {
+            self.pop();
+            Ok(())
+        } else {
+            Err(format!("expects `{}`", name))
         }
     }
     pub fn consume_num(&mut self) -> i32 {
-        let token = self.pop().expect("");
-        match token {
-            Token::Int(val) => val.parse().expect(""),
-            _ => panic!(""),
+        self.try_consume_num().expect("an integer")
+    }
+    pub fn try_consume_num(&mut self) -> Result<i32, String> {
+        match self.peek() {
+            Some(Token::Int(val)) => {
+                let num = val
+                    .parse()
+                    .map_err(|_| format!("integer `{}` is too large", val))?;
+                self.pop(); // consume the integer
+                Ok(num)
+            }
+            _ => Err("expects an integer".to_string()),
         }
     }
     pub fn consume_var(&mut self) -> String {
+        self.try_consume_var().expect("a variable")
+    }
+    pub fn try_consume_var(&mut self) -> Result<String, String> {
+        let var = match self.peek() {
+            Some(Token::Var(val)) => val,
+            // nameless binders are written as `.`
+            Some(Token::DOT) => ".".to_string(),
+            _ => return Err("expects a variable".to_string()),
+        };
+        self.pop(); // consume the variable
+        Ok(var)
+    }
+    pub fn consume_index(&mut self) -> usize {
         let token = self.pop().expect("");
         match token {
-            Token::Var(val) => val,
+            Token::Index(val) => val[1..].parse().expect(""),
             _ => panic!(""),
         }
     }
@@ -73,6 +123,13 @@ impl Tokens {
             _ => panic!(""),
         }
     }
+    pub fn consume_loc(&mut self) -> String {
+        let token = self.pop().expect("");
+        match token {
+            Token::Loc(val) => val,
+            _ => panic!(""),
+        }
+    }
     pub fn consume_op(&mut self) -> String {
         let token = self.pop().expect("");
         match token {
@@ -92,41 +149,84 @@ impl Lexer {
     pub fn new() -> Lexer {
         let token_patterns = vec![
             ("MINT", r"-[1-9][0-9]*"),
-            ("INT", r"[1-9][0-9]*"),
-            ("BOOL", r"(true|false)"),
-            ("ERR", r"error"),
+            ("INT", r"(0|[1-9][0-9]*)"),
+            ("BOOL", r"(true|false)\b"),
+            ("ERR", r"error\b"),
             ("ARROW", r"->"),
             ("ENV", r"\|-"),
-            ("OP", r"\+|-|\*|<"),
+            ("BAR", r"\|"),
+            ("OP", r"\+|-|\*|<|::|:="),
+            ("COLON", r":"),
+            ("TVAR", r"'[a-z][a-zA-Z0-9_]*"),
+            ("DEREF", r"!"),
+            ("SLASH", r"/"),
+            ("LOC", r"@[a-z][a-zA-Z0-9_']*"),
+            ("INDEX", r"#[1-9][0-9]*"),
+            ("DOT", r"\."),
             ("PS", r"\("),
             ("PE", r"\)"),
             ("SS", r"\["),
             ("SE", r"\]"),
+            ("TRANS", r"==>"),
+            ("PASS", r"=>"),
+            ("CONT", r">>"),
+            ("BS", r"\{"),
+            ("BE", r"\}"),
+            ("WILD", r"_"),
             ("EQ", r"="),
             ("COMMA", r","),
-            ("IF", r"if"),
-            ("THEN", r"then"),
-            ("FUN", r"fun"),
-            ("ELSE", r"else"),
-            ("LET", r"let"),
-            ("IN", r"in"),
-            ("VAR", r"(x|y|f|sq|sm)"),
-            ("EVAL", r"evalto"),
+            ("IF", r"if\b"),
+            ("THEN", r"then\b"),
+            ("FUN", r"fun\b"),
+            ("ELSE", r"else\b"),
+            ("LETCC", r"letcc\b"),
+            ("LET", r"let\b"),
+            ("REC", r"rec\b"),
+            ("IN", r"in\b"),
+            ("REF", r"ref\b"),
+            ("MATCH", r"match\b"),
+            ("WITH", r"with\b"),
+            ("EVAL", r"evalto\b"),
+            ("VAR", r"[a-z][a-zA-Z0-9_']*"),
         ];
         let re = make_regex(&token_patterns);
         let names = get_names(&token_patterns);
         let re = Regex::new(&re).expect("something went wrong making the regex");
         Lexer { re, names }
     }
+    // the text the last `count` tokens of code were lexed from, e.g. the value after evalto
+    pub fn get_tail(&self, code: &str, count: usize) -> String {
+        let starts: Vec<usize> = self.re.find_iter(code).map(|m| m.start()).collect();
+        match starts.len().checked_sub(count) {
+            Some(i) if i < starts.len() => code[starts[i]..].trim().to_string(),
+            _ => "".to_string(),
+        }
+    }
+    // a syntax error naming the text the parser stopped at, e.g. "expects `:`, found `evalto 3`"
+    pub fn get_syntax_error(&self, code: &str, tokens: &Tokens, expected: &str) -> String {
+        match self.get_tail(code, tokens.tokens.len()).as_ref() {
+            "" => format!("syntax error: {} at the end", expected),
+            tail => format!("syntax error: {}, found `{}`", expected, tail),
+        }
+    }
+    // the lexer skips text that no token matches, so a strict reader looks for it first
+    pub fn find_unknown(&self, code: &str) -> Option<String> {
+        let mut end = 0;
+        for m in self.re.find_iter(code) {
+            if let Some(unknown) = code[end..m.start()].split_whitespace().next() {
+                return Some(unknown.to_string());
+            }
+            end = m.end();
+        }
+        code[end..].split_whitespace().next().map(str::to_string)
+    }
     pub fn lex(&self, code: String) -> Tokens {
-        let mut code = code;
-        let tokens = self.tokenize(&mut code);
-        tokens
+        self.tokenize(&code)
     }
-    fn tokenize(&self, code: &mut String) -> Tokens {
+    fn tokenize(&self, code: &str) -> Tokens {
         let mut tokens: Vec<Token> = Vec::new();
 
-        for caps in self.re.captures_iter(&code) {
+        for caps in self.re.captures_iter(code) {
             let mut typ = String::from("nil");
             let val = String::from(&caps[0]);
             for name in &self.names {
@@ -149,13 +249,32 @@ impl Lexer {
                 "SS" => tokens.push(Token::SS),
                 "SE" => tokens.push(Token::SE),
                 "EQ" => tokens.push(Token::EQ),
+                "TRANS" => tokens.push(Token::TRANS),
                 "ARROW" => tokens.push(Token::ARROW),
+                "PASS" => tokens.push(Token::PASS),
+                "CONT" => tokens.push(Token::CONT),
+                "BS" => tokens.push(Token::BS),
+                "BE" => tokens.push(Token::BE),
+                "WILD" => tokens.push(Token::WILD),
                 "COMMA" => tokens.push(Token::COMMA),
                 "IF" => tokens.push(Token::IF),
                 "IN" => tokens.push(Token::IN),
+                "LETCC" => tokens.push(Token::LETCC),
                 "LET" => tokens.push(Token::LET),
+                "REC" => tokens.push(Token::REC),
                 "THEN" => tokens.push(Token::THEN),
                 "ELSE" => tokens.push(Token::ELSE),
+                "MATCH" => tokens.push(Token::MATCH),
+                "WITH" => tokens.push(Token::WITH),
+                "BAR" => tokens.push(Token::BAR),
+                "REF" => tokens.push(Token::REF),
+                "DEREF" => tokens.push(Token::DEREF),
+                "SLASH" => tokens.push(Token::SLASH),
+                "LOC" => tokens.push(Token::Loc(val)),
+                "TVAR" => tokens.push(Token::TVar(val)),
+                "COLON" => tokens.push(Token::COLON),
+                "INDEX" => tokens.push(Token::Index(val)),
+                "DOT" => tokens.push(Token::DOT),
                 _ => panic!("unexpected type token"),
             }
         }
@@ -164,14 +283,11 @@ impl Lexer {
 }
 fn make_regex(token_patterns: &Vec<(&str, &str)>) -> String {
     token_patterns
-        .into_iter()
+        .iter()
         .map(|pattern| format!("(?P<{}>{})", pattern.0, pattern.1))
         .collect::<Vec<String>>()
         .join("|")
 }
-fn get_names<'a, 'b>(token_patterns: &Vec<(&'a str, &'b str)>) -> Vec<&'a str> {
-    token_patterns
-        .into_iter()
-        .map(|pattern| pattern.0)
-        .collect()
+fn get_names<'a>(token_patterns: &Vec<(&'a str, &str)>) -> Vec<&'a str> {
+    token_patterns.iter().map(|pattern| pattern.0).collect()
 }
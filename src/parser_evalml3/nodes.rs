@@ -1,9 +1,11 @@
-use super::environment::Environment;
 use super::expression::Expression;
 use super::nodes::bnodes::BOpNode;
-use super::nodes::enodes::{EAppNode, EBNode, EFunNode, EIfNode, ELetNode, EValNode, EVarNode};
-use super::nodes::writer::RuleWriter;
+use super::nodes::enodes::{
+    EAppNode, EBNode, EFunNode, EIfNode, ELetNode, ELetRecNode, EValNode, EVarNode,
+};
+use super::nodes::writer::{Rule, RuleWriter};
 use super::terms::Term;
+use super::value::{Environment, Value};
 use std::io::{self, Write};
 
 pub mod bnodes;
@@ -16,6 +18,7 @@ pub enum RuleNode {
     EVal(EValNode),
     EIf(EIfNode),
     ELet(ELetNode),
+    ELetRec(ELetRecNode),
     EBNode(EBNode),
     EFun(EFunNode),
     EApp(EAppNode),
@@ -29,49 +32,58 @@ impl RuleNode {
         if len == 1 {
             let term = expression.get_first_term();
             match term {
-                Term::If(if_node) => RuleNode::EIf(EIfNode {
-                    environment,
-                    expression: original_expression,
-                    term: if_node,
-                }),
-                Term::Var(_) => RuleNode::EVar(EVarNode {
-                    environment,
-                    expression: original_expression,
-                }),
+                Term::If(if_node) => {
+                    RuleNode::EIf(EIfNode::new(environment, original_expression, if_node))
+                }
+                Term::Var(_) => RuleNode::EVar(EVarNode::new(environment, original_expression)),
                 Term::Paren(expression) => RuleNode::new(environment, expression),
-                Term::Val(_) => RuleNode::EVal(EValNode {
+                Term::Val(_) | Term::Bool(_) => {
+                    RuleNode::EVal(EValNode::new(environment, original_expression))
+                }
+                Term::Let(let_node) => {
+                    RuleNode::ELet(ELetNode::new(environment, original_expression, let_node))
+                }
+                Term::LetRec(let_rec_node) => RuleNode::ELetRec(ELetRecNode::new(
                     environment,
-                    expression: original_expression,
-                }),
-                Term::Let(let_node) => RuleNode::ELet(ELetNode {
-                    environment,
-                    expression: original_expression,
-                    term: let_node,
-                }),
+                    original_expression,
+                    let_rec_node,
+                )),
                 Term::Fun(fun_node) => RuleNode::EFun(EFunNode {
                     environment,
-                    expression: original_expression,
                     term: fun_node,
                 }),
-                Term::App(app_node) => RuleNode::EApp(EAppNode {
-                    environment,
-                    expression: original_expression,
-                    term: app_node,
-                }),
+                Term::App(app_node) => RuleNode::EApp(EAppNode::new(environment, app_node)),
+                _ => panic!("unexpected"),
             }
         } else {
-            RuleNode::EBNode(EBNode {
-                environment,
-                expression: original_expression,
-            })
+            RuleNode::EBNode(EBNode::new(environment, original_expression))
         }
     }
-    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+    // the value the judgement evaluates to, which was computed when the node was built
+    pub fn get_val(&self) -> Value {
+        match self {
+            RuleNode::EVar(node) => node.value.clone(),
+            RuleNode::EVal(node) => node.value.clone(),
+            RuleNode::EIf(node) => node.value.clone(),
+            RuleNode::ELet(node) => node.value.clone(),
+            RuleNode::ELetRec(node) => node.value.clone(),
+            RuleNode::EBNode(node) => node.value.clone(),
+            RuleNode::EFun(node) => node.get_val(),
+            RuleNode::EApp(node) => node.value.clone(),
+            RuleNode::BOp(_) => panic!("unexpected"),
+        }
+    }
+}
+impl Rule for RuleNode {
+    type Context = ();
+
+    fn show<W: Write>(self, writer: &mut RuleWriter<W, RuleNode>) -> io::Result<()> {
         match self {
             RuleNode::EVar(node) => node.show(writer),
             RuleNode::EVal(node) => node.show(writer),
             RuleNode::EIf(node) => node.show(writer),
             RuleNode::ELet(node) => node.show(writer),
+            RuleNode::ELetRec(node) => node.show(writer),
             RuleNode::EBNode(node) => node.show(writer),
             RuleNode::EFun(node) => node.show(writer),
             RuleNode::EApp(node) => node.show(writer),
@@ -1,4 +1,4 @@
-use super::writer::RuleWriter;
+use super::writer::{Premises, Rule, RuleWriter};
 use std::io::{self, Write};
 
 #[derive(Debug, Clone)]
@@ -8,37 +8,27 @@ pub struct BOpNode {
     pub op: String,
 }
 impl BOpNode {
-    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+    pub fn show<W: Write, N: Rule>(self, writer: &mut RuleWriter<W, N>) -> io::Result<()> {
         match self.op.as_ref() {
-            "+" => writer.show_rule(
-                None,
-                self.i1.to_string() + " plus " + &self.i2.to_string(),
-                (self.i1 + self.i2).to_string(),
+            "+" => writer.show_judgement(
+                format!("{} plus {} is {}", self.i1, self.i2, self.i1 + self.i2),
                 "B-Plus".to_string(),
-                true,
-                None,
-                None,
-                None,
+                Premises(None, None, None),
             ),
-            "*" => writer.show_rule(
-                None,
-                self.i1.to_string() + " times " + &self.i2.to_string(),
-                (self.i1 * self.i2).to_string(),
+            "*" => writer.show_judgement(
+                format!("{} times {} is {}", self.i1, self.i2, self.i1 * self.i2),
                 "B-Times".to_string(),
-                true,
-                None,
-                None,
-                None,
+                Premises(None, None, None),
             ),
-            "-" => writer.show_rule(
-                None,
-                self.i1.to_string() + " minus " + &self.i2.to_string(),
-                (self.i1 - self.i2).to_string(),
+            "-" => writer.show_judgement(
+                format!("{} minus {} is {}", self.i1, self.i2, self.i1 - self.i2),
                 "B-Minus".to_string(),
-                true,
-                None,
-                None,
-                None,
+                Premises(None, None, None),
+            ),
+            "<" => writer.show_judgement(
+                format!("{} less than {} is {}", self.i1, self.i2, self.i1 < self.i2),
+                "B-Lt".to_string(),
+                Premises(None, None, None),
             ),
             _ => panic!("todo"),
         }
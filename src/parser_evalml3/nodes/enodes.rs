@@ -1,43 +1,58 @@
-use super::super::environment::Environment;
 use super::super::expression::Expression;
 use super::super::nodes::RuleNode;
-use super::super::terms::{AppTerm, FunTerm, IfTerm, LetTerm, Term};
-use super::super::value::Value;
+use super::super::terms::{AppTerm, FunTerm, IfTerm, LetRecTerm, LetTerm, Term};
+use super::super::value::{Environment, Value};
 use super::bnodes::BOpNode;
-use super::writer::RuleWriter;
+use super::writer::{Premises, RuleWriter};
 
 use std::io::{self, Write};
 
+// every node is built with the derivations of its premises, and keeps its value so that
+// the parent node reuses it instead of evaluating the sub-expression again
+
 #[derive(Debug, Clone)]
 pub struct EVarNode {
     pub environment: Environment,
     pub expression: Expression,
+    pub value: Value,
+    pub premise: Option<Box<RuleNode>>,
 }
 impl EVarNode {
-    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
-        let mut terms = self.expression.terms.clone();
+    pub fn new(environment: Environment, expression: Expression) -> EVarNode {
+        let mut terms = expression.terms.clone();
         let (_, term) = terms.pop().expect("");
         let identifier = term.get_identifier();
 
-        let (premise, rule_str) = if self.environment.get_match_loc(&identifier) == 0 {
-            (None, "E-Var1".to_string())
+        let premise = if environment.get_match_loc(&identifier) == 0 {
+            None
         } else {
-            let mut new_env = self.environment.clone();
+            let mut new_env = environment.clone();
             new_env.pop_val();
-            (
-                Some(RuleNode::new(new_env, self.expression)),
-                "E-Var2".to_string(),
-            )
+            Some(Box::new(RuleNode::new(new_env, expression.clone())))
+        };
+        let value = match premise {
+            Some(ref premise) => premise.get_val(),
+            None => environment.get_val(&identifier),
+        };
+        EVarNode {
+            environment,
+            expression,
+            value,
+            premise,
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W, RuleNode>) -> io::Result<()> {
+        let rule_str = match self.premise {
+            Some(_) => "E-Var2",
+            None => "E-Var1",
         };
         writer.show_rule(
             Some(self.environment.clone()),
-            identifier.clone(),
-            self.environment.get_val(&identifier).to_string(),
-            rule_str,
+            self.expression.to_string(),
+            self.value.to_string(),
+            rule_str.to_string(),
             false,
-            premise,
-            None,
-            None,
+            Premises(self.premise.map(|premise| *premise), None, None),
         )
     }
 }
@@ -46,76 +61,143 @@ impl EVarNode {
 pub struct EIfNode {
     pub environment: Environment,
     pub expression: Expression,
-    pub term: IfTerm,
+    pub value: Value,
+    pub rule: String,
+    pub condition_premise: Box<RuleNode>,
+    pub branch_premise: Option<Box<RuleNode>>,
 }
 impl EIfNode {
-    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
-        let condition_expression = self.term.condition_expression;
-        let then_expression = self.term.then_expression;
-        let _else_expression = self.term.else_expression;
-
-        let flag: String = match condition_expression
-            .clone()
-            .get_val(self.environment.clone())
-        {
+    pub fn new(environment: Environment, expression: Expression, term: IfTerm) -> EIfNode {
+        let condition_premise = RuleNode::new(environment.clone(), term.condition_expression);
+        let condition_value = condition_premise.get_val();
+
+        let flag: String = match condition_value {
             Value::Bool(b) => b,
-            _ => panic!("unexpected"),
+            _ => {
+                return EIfNode {
+                    environment,
+                    expression,
+                    value: Value::Error,
+                    rule: format!("E-If{}", condition_value.get_kind()),
+                    condition_premise: Box::new(condition_premise),
+                    branch_premise: None,
+                }
+            }
         };
 
-        if flag == String::from("true") {
-            let condition_premise = RuleNode::new(self.environment.clone(), condition_expression);
-            let then_premise = RuleNode::new(self.environment.clone(), then_expression.clone());
-            writer.show_rule(
-                Some(self.environment.clone()),
-                self.expression.clone().to_string(&self.environment),
-                then_expression.get_val(self.environment).to_string(),
-                "E-IfT".to_string(),
-                false,
-                Some(condition_premise),
-                Some(then_premise),
-                None,
-            )
+        let (branch_expression, rule) = if flag == "true" {
+            (term.then_expression, "E-IfT".to_string())
         } else {
-            panic!("todo")
+            (term.else_expression, "E-IfF".to_string())
+        };
+        let branch_premise = RuleNode::new(environment.clone(), branch_expression);
+        let value = branch_premise.get_val();
+        let rule = match value {
+            Value::Error => rule + "Error",
+            _ => rule,
+        };
+        EIfNode {
+            environment,
+            expression,
+            value,
+            rule,
+            condition_premise: Box::new(condition_premise),
+            branch_premise: Some(Box::new(branch_premise)),
         }
     }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W, RuleNode>) -> io::Result<()> {
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.expression.to_string(),
+            self.value.to_string(),
+            self.rule,
+            false,
+            Premises(
+                Some(*self.condition_premise),
+                self.branch_premise.map(|premise| *premise),
+                None,
+            ),
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct EBNode {
     pub environment: Environment,
     pub expression: Expression,
+    pub value: Value,
+    pub rule: String,
+    pub premises: Vec<RuleNode>,
 }
 impl EBNode {
-    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
-        let (split_position, operator) = self.expression.get_split_position();
-        let (former, latter) = self.expression.get_splitted_expression(split_position);
+    pub fn new(environment: Environment, expression: Expression) -> EBNode {
+        let (split_position, operator) = expression.get_split_position();
+        let (former, latter) = expression.get_splitted_expression(split_position);
 
-        let i1 = former.clone().get_val(self.environment.clone()).get_num();
-        let i2 = latter.clone().get_val(self.environment.clone()).get_num();
-        let premise1 = RuleNode::new(self.environment.clone(), former);
-        let premise2 = RuleNode::new(self.environment.clone(), latter);
+        let premise1 = RuleNode::new(environment.clone(), former);
+        let premise2 = RuleNode::new(environment.clone(), latter);
+        let value1 = premise1.get_val();
+        let value2 = premise2.get_val();
 
+        let rule_name = match operator.as_ref() {
+            "+" => "Plus",
+            "*" => "Times",
+            "-" => "Minus",
+            "<" => "Lt",
+            _ => panic!("todo"),
+        };
+        let (i1, i2) = match (value1, value2) {
+            (Value::Num(i1), Value::Num(i2)) => (i1, i2),
+            (Value::Num(_), value2) => {
+                // the left operand is fine, so the right one causes the error
+                return EBNode {
+                    environment,
+                    expression,
+                    value: Value::Error,
+                    rule: format!("E-{}{}R", rule_name, value2.get_kind()),
+                    premises: vec![premise2],
+                };
+            }
+            (value1, _) => {
+                return EBNode {
+                    environment,
+                    expression,
+                    value: Value::Error,
+                    rule: format!("E-{}{}L", rule_name, value1.get_kind()),
+                    premises: vec![premise1],
+                };
+            }
+        };
+
+        let value = match operator.as_ref() {
+            "+" => Value::Num(i1 + i2),
+            "*" => Value::Num(i1 * i2),
+            "-" => Value::Num(i1 - i2),
+            "<" => Value::Num(i1).comp(&Value::Num(i2)),
+            _ => panic!("todo"),
+        };
         let premise = RuleNode::BOp(BOpNode {
             i1,
             i2,
-            op: operator.clone(),
+            op: operator,
         });
-        let (val_str, rule_str) = match operator.as_ref() {
-            "+" => ((i1 + i2).to_string(), "E-Plus".to_string()),
-            "*" => ((i1 * i2).to_string(), "E-Times".to_string()),
-            "-" => ((i1 - i2).to_string(), "E-Minus".to_string()),
-            _ => panic!("todo"),
-        };
+        EBNode {
+            environment,
+            expression,
+            value,
+            rule: format!("E-{}", rule_name),
+            premises: vec![premise1, premise2, premise],
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W, RuleNode>) -> io::Result<()> {
+        let mut premises = self.premises.into_iter();
         writer.show_rule(
             Some(self.environment.clone()),
-            self.expression.to_string(&self.environment),
-            val_str,
-            rule_str,
+            self.expression.to_string(),
+            self.value.to_string(),
+            self.rule,
             false,
-            Some(premise1),
-            Some(premise2),
-            Some(premise),
+            Premises(premises.next(), premises.next(), premises.next()),
         )
     }
 }
@@ -124,59 +206,125 @@ impl EBNode {
 pub struct EValNode {
     pub environment: Environment,
     pub expression: Expression,
+    pub value: Value,
 }
 impl EValNode {
-    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
-        let mut terms = self.expression.clone().terms;
+    pub fn new(environment: Environment, expression: Expression) -> EValNode {
+        let mut terms = expression.terms.clone();
         let (_, term) = terms.pop().expect("");
-        match term {
-            Term::Val(_) => writer.show_rule(
-                Some(self.environment.clone()),
-                self.expression.clone().to_string(&self.environment),
-                self.expression
-                    .get_val(self.environment)
-                    .get_num()
-                    .to_string(),
-                "E-Int".to_string(),
-                false,
-                None,
-                None,
-                None,
-            ),
+        let value = match term {
+            Term::Val(num) => Value::Num(num),
+            Term::Bool(b) => Value::Bool(b),
             _ => panic!("unexpected"),
+        };
+        EValNode {
+            environment,
+            expression,
+            value,
         }
     }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W, RuleNode>) -> io::Result<()> {
+        let rule_str = match self.value {
+            Value::Num(_) => "E-Int",
+            _ => "E-Bool",
+        };
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.expression.to_string(),
+            self.value.to_string(),
+            rule_str.to_string(),
+            false,
+            Premises(None, None, None),
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ELetNode {
     pub environment: Environment,
     pub expression: Expression,
-    pub term: LetTerm,
+    pub value: Value,
+    pub let_premise: Box<RuleNode>,
+    pub in_premise: Option<Box<RuleNode>>,
 }
 impl ELetNode {
-    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
-        let in_expression = self.term.clone().in_expression;
-        let let_expression = self.term.clone().let_expression;
-        let mut new_env = self.environment.clone();
-        let val = let_expression
-            .expression
-            .clone()
-            .get_val(self.environment.clone());
+    pub fn new(environment: Environment, expression: Expression, term: LetTerm) -> ELetNode {
+        let let_expression = term.let_expression;
+        let let_premise = RuleNode::new(environment.clone(), let_expression.expression);
+        let val = let_premise.get_val();
+        if let Value::Error = val {
+            return ELetNode {
+                environment,
+                expression,
+                value: Value::Error,
+                let_premise: Box::new(let_premise),
+                in_premise: None,
+            };
+        }
+        let mut new_env = environment.clone();
         new_env.set_val(let_expression.identifier, val);
-        let let_premise = RuleNode::new(self.environment.clone(), let_expression.expression);
-        let in_premise = RuleNode::new(new_env.clone(), in_expression.clone());
-        let val = self.term.get_val(self.environment.clone()).to_string();
+        let in_premise = RuleNode::new(new_env, term.in_expression);
+        ELetNode {
+            environment,
+            expression,
+            value: in_premise.get_val(),
+            let_premise: Box::new(let_premise),
+            in_premise: Some(Box::new(in_premise)),
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W, RuleNode>) -> io::Result<()> {
+        let rule_str = match (&self.in_premise, &self.value) {
+            (None, _) => "E-LetErrorL",
+            (Some(_), Value::Error) => "E-LetErrorR",
+            _ => "E-Let",
+        };
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.expression.to_string(),
+            self.value.to_string(),
+            rule_str.to_string(),
+            false,
+            Premises(
+                Some(*self.let_premise),
+                self.in_premise.map(|premise| *premise),
+                None,
+            ),
+        )
+    }
+}
 
+#[derive(Debug, Clone)]
+pub struct ELetRecNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub value: Value,
+    pub in_premise: Box<RuleNode>,
+}
+impl ELetRecNode {
+    pub fn new(environment: Environment, expression: Expression, term: LetRecTerm) -> ELetRecNode {
+        let mut new_env = environment.clone();
+        let rec_fun = Value::RecFun(term.identifier.clone(), term.fun_term, environment.clone());
+        new_env.set_val(term.identifier, rec_fun);
+        let in_premise = RuleNode::new(new_env, term.in_expression);
+        ELetRecNode {
+            environment,
+            expression,
+            value: in_premise.get_val(),
+            in_premise: Box::new(in_premise),
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W, RuleNode>) -> io::Result<()> {
+        let rule_str = match self.value {
+            Value::Error => "E-LetRecError",
+            _ => "E-LetRec",
+        };
         writer.show_rule(
             Some(self.environment.clone()),
-            self.expression.clone().to_string(&self.environment),
-            val,
-            "E-Let".to_string(),
+            self.expression.to_string(),
+            self.value.to_string(),
+            rule_str.to_string(),
             false,
-            Some(let_premise),
-            Some(in_premise),
-            None,
+            Premises(Some(*self.in_premise), None, None),
         )
     }
 }
@@ -184,20 +332,20 @@ impl ELetNode {
 #[derive(Debug, Clone)]
 pub struct EFunNode {
     pub environment: Environment,
-    pub expression: Expression,
     pub term: FunTerm,
 }
 impl EFunNode {
-    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+    pub fn get_val(&self) -> Value {
+        Value::Fun(self.term.clone(), self.environment.clone())
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W, RuleNode>) -> io::Result<()> {
         writer.show_rule(
             Some(self.environment.clone()),
-            self.term.clone().to_string(&self.environment),
-            self.term.get_val(self.environment).to_string(),
+            self.term.to_string(),
+            self.get_val().to_string(),
             "E-Fun".to_string(),
             false,
-            None,
-            None,
-            None,
+            Premises(None, None, None),
         )
     }
 }
@@ -205,32 +353,87 @@ impl EFunNode {
 #[derive(Debug, Clone)]
 pub struct EAppNode {
     pub environment: Environment,
-    pub expression: Expression,
     pub term: AppTerm,
+    pub value: Value,
+    pub rule: String,
+    pub premise1: Box<RuleNode>,
+    pub premise2: Option<Box<RuleNode>>,
+    pub premise3: Option<Box<RuleNode>>,
 }
 impl EAppNode {
-    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
-        let (fun_term, mut clojure_env) = self.term.get_fun_info(self.environment.clone());
-
-        let terms: Vec<(String, Term)> = vec![("".to_string(), *self.term.clone().function)];
-        let premise1 = RuleNode::new(self.environment.clone(), Expression { terms });
-
-        let premise2 = RuleNode::new(self.environment.clone(), self.term.clone().argument);
-        let parameter: String = fun_term.parameter;
-        clojure_env.set_val(
-            parameter,
-            self.term.clone().argument.get_val(self.environment.clone()),
-        );
-        let premise3 = RuleNode::new(clojure_env, fun_term.function_body);
+    pub fn new(environment: Environment, term: AppTerm) -> EAppNode {
+        let terms: Vec<(String, Term)> = vec![("".to_string(), *term.function.clone())];
+        let function_expression = Expression { terms };
+        let premise1 = RuleNode::new(environment.clone(), function_expression);
+        let function_value = premise1.get_val();
+        let error = |rule: String, premise2: Option<RuleNode>| EAppNode {
+            environment: environment.clone(),
+            term: term.clone(),
+            value: Value::Error,
+            rule,
+            premise1: Box::new(premise1.clone()),
+            premise2: premise2.map(Box::new),
+            premise3: None,
+        };
+
+        match function_value {
+            Value::Fun(_, _) | Value::RecFun(_, _, _) => (),
+            Value::Error => return error("E-AppErrorL".to_string(), None),
+            _ => return error(format!("E-App{}", function_value.get_kind()), None),
+        }
+
+        let premise2 = RuleNode::new(environment.clone(), term.argument.clone());
+        let argument_value = premise2.get_val();
+        if let Value::Error = argument_value {
+            return error("E-AppErrorR".to_string(), Some(premise2));
+        }
+        let (premise3, rule) = match function_value {
+            Value::Fun(fun_term, mut clojure_env) => {
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                (
+                    RuleNode::new(clojure_env, fun_term.function_body),
+                    "E-App".to_string(),
+                )
+            }
+            Value::RecFun(identifier, fun_term, mut clojure_env) => {
+                let rec_fun =
+                    Value::RecFun(identifier.clone(), fun_term.clone(), clojure_env.clone());
+                clojure_env.set_val(identifier, rec_fun);
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                (
+                    RuleNode::new(clojure_env, fun_term.function_body),
+                    "E-AppRec".to_string(),
+                )
+            }
+            _ => panic!("unexpected"),
+        };
+        let value = premise3.get_val();
+        let rule = match value {
+            Value::Error => rule + "Error",
+            _ => rule,
+        };
+        EAppNode {
+            environment,
+            term,
+            value,
+            rule,
+            premise1: Box::new(premise1),
+            premise2: Some(Box::new(premise2)),
+            premise3: Some(Box::new(premise3)),
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W, RuleNode>) -> io::Result<()> {
         writer.show_rule(
             Some(self.environment.clone()),
-            self.term.clone().to_string(&self.environment),
-            self.term.get_val(self.environment).to_string(),
-            "E-App".to_string(),
+            self.term.to_string(),
+            self.value.to_string(),
+            self.rule,
             false,
-            Some(premise1),
-            Some(premise2),
-            Some(premise3),
+            Premises(
+                Some(*self.premise1),
+                self.premise2.map(|premise| *premise),
+                self.premise3.map(|premise| *premise),
+            ),
         )
     }
 }
@@ -1,19 +1,39 @@
 use super::super::environment::Environment;
-use super::super::nodes::{get_depth_space, RuleNode};
+use super::get_depth_space;
 
+use std::fmt::Display;
 use std::io::{self, Write};
 
-pub struct RuleWriter<W> {
+// a node of a derivation tree, which shows itself and its premises through the writer
+pub trait Rule: Sized {
+    // what the nodes need to print their judgements besides themselves, e.g. inferred types
+    type Context;
+    fn show<W: Write>(self, writer: &mut RuleWriter<W, Self>) -> io::Result<()>;
+}
+
+// the premises of a rule application in order, as no rule has more than three. the third
+// can be a node of another kind, e.g. the arithmetic judgement of EvalML2
+pub struct Premises<N, M = N>(pub Option<N>, pub Option<N>, pub Option<M>);
+
+pub struct RuleWriter<W, N: Rule> {
     w: W,
     depth: usize,
+    pub context: N::Context,
 }
-impl<W: Write> RuleWriter<W> {
-    pub fn new(w: W, depth: usize) -> RuleWriter<W> {
-        RuleWriter { w, depth }
+impl<W: Write, N: Rule> RuleWriter<W, N> {
+    pub fn new(w: W, depth: usize) -> RuleWriter<W, N>
+    where
+        N::Context: Default,
+    {
+        RuleWriter::new_with_context(w, depth, Default::default())
+    }
+
+    pub fn new_with_context(w: W, depth: usize, context: N::Context) -> RuleWriter<W, N> {
+        RuleWriter { w, depth, context }
     }
 
     pub fn write_nl(&mut self) {
-        let _ = write!(self.w, "\n");
+        let _ = writeln!(self.w);
     }
 
     fn inc_depth(&mut self) {
@@ -23,30 +43,39 @@ impl<W: Write> RuleWriter<W> {
         self.depth -= 2;
     }
 
-    pub fn show_rule(
+    pub fn show_rule<V: Display>(
         &mut self,
-        environment: Option<Environment>,
+        environment: Option<Environment<V>>,
         expression_str: String,
         evalto_str: String,
         rule_str: String,
         is_bnode: bool,
-        premise1: Option<RuleNode>,
-        premise2: Option<RuleNode>,
-        premise3: Option<RuleNode>,
+        premises: Premises<N>,
     ) -> io::Result<()> {
-        let environment_str = match environment.clone() {
+        let environment_str = match environment {
             Some(env) => env.to_string(),
             None => "".to_string(),
         };
         let eq_str = if is_bnode { "is" } else { "evalto" };
+        let judgement_str = format!(
+            "{}{} {} {}",
+            environment_str, expression_str, eq_str, evalto_str
+        );
+        self.show_judgement(judgement_str, rule_str, premises)
+    }
+
+    pub fn show_judgement(
+        &mut self,
+        judgement_str: String,
+        rule_str: String,
+        premises: Premises<N>,
+    ) -> io::Result<()> {
+        let Premises(premise1, premise2, premise3) = premises;
         let _ = write!(
             self.w,
-            "{}{}{} {} {} by {} {{",
+            "{}{} by {} {{",
             get_depth_space(self.depth),
-            environment_str,
-            expression_str,
-            eq_str.to_string(),
-            evalto_str,
+            judgement_str,
             rule_str,
         );
 
@@ -54,17 +83,17 @@ impl<W: Write> RuleWriter<W> {
 
         self.inc_depth();
         if let Some(premise) = premise1 {
-            let _ = write!(self.w, "\n");
+            let _ = writeln!(self.w);
             let _ = premise.show(self);
             eol_necessity = true;
         }
         if let Some(premise) = premise2 {
-            let _ = write!(self.w, ";\n");
+            let _ = writeln!(self.w, ";");
             let _ = premise.show(self);
             eol_necessity = true;
         }
         if let Some(premise) = premise3 {
-            let _ = write!(self.w, ";\n");
+            let _ = writeln!(self.w, ";");
             let _ = premise.show(self);
             eol_necessity = true;
         }
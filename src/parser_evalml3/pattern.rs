@@ -0,0 +1,56 @@
+use super::lexer::{Token, Tokens};
+
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Var(String),
+    Nil,
+    Cons(Box<Pattern>, Box<Pattern>),
+    Wild,
+}
+impl Pattern {
+    pub fn try_new(tokens: &mut Tokens) -> Result<Pattern, String> {
+        let pattern = match tokens.peek() {
+            Some(Token::Var(_)) => Pattern::Var(tokens.consume_var()),
+            Some(Token::WILD) => {
+                tokens.pop(); // consume _
+                Pattern::Wild
+            }
+            Some(Token::SS) => {
+                tokens.pop(); // consume [
+                tokens.expect(Token::SE, "]")?;
+                Pattern::Nil
+            }
+            Some(Token::PS) => {
+                tokens.pop(); // consume (
+                let pattern = Pattern::try_new(tokens)?;
+                tokens.expect(Token::PE, ")")?;
+                pattern
+            }
+            _ => return Err("expects a pattern".to_string()),
+        };
+        // :: is right associative: p1 :: p2 :: p3 = p1 :: (p2 :: p3)
+        match tokens.peek() {
+            Some(Token::Op(op)) if op == "::" => {
+                tokens.pop(); // consume ::
+                let tail = Pattern::try_new(tokens)?;
+                Ok(Pattern::Cons(Box::new(pattern), Box::new(tail)))
+            }
+            _ => Ok(pattern),
+        }
+    }
+}
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Pattern::Var(identifier) => write!(f, "{}", identifier),
+            Pattern::Nil => write!(f, "[]"),
+            Pattern::Wild => write!(f, "_"),
+            Pattern::Cons(head, tail) => match **head {
+                Pattern::Cons(_, _) => write!(f, "({}) :: {}", head, tail),
+                _ => write!(f, "{} :: {}", head, tail),
+            },
+        }
+    }
+}
@@ -1,116 +1,274 @@
-use super::environment::Environment;
 use super::expression::{Expression, LetExpression};
 use super::lexer::{Token, Tokens};
-use super::value::Value;
+use super::pattern::Pattern;
+
+use std::fmt;
 
 #[derive(Debug, Clone)]
 pub enum Term {
     Val(i32),
+    Bool(String),
     Var(String),
+    Index(usize),
+    Nil,
     Paren(Expression),
     If(IfTerm),
     Let(LetTerm),
+    LetRec(LetRecTerm),
     Fun(FunTerm),
     App(AppTerm),
+    Match(MatchTerm),
+    Ref(Expression),
+    Deref(Expression),
+    LetCc(LetCcTerm),
 }
 impl Term {
-    pub fn new(tokens: &mut Tokens) -> Term {
-        match tokens.peek().expect("a token") {
-            Token::Int(_) => {
-                let num: i32 = tokens.consume_num();
-                Term::Val(num)
-            }
-            Token::Var(variable) => {
-                // assumes that function name is not (x or y)
-                match variable.as_ref() {
-                    "x" | "y" => {
-                        // not function variable
-                        let var: String = tokens.consume_var();
-                        Term::Var(var)
-                    }
-                    _ => {
-                        // function variable
-                        let function = Box::new(Term::Var(tokens.consume_var()));
-
-                        println!("function argument: {:?}", tokens);
-
-                        let term = Term::new(tokens);
-                        let terms: Vec<(String, Term)> = vec![("".to_string(), term];
-                        let argument = Expression { terms };
-
-                        Term::App(AppTerm { function, argument })
-                    }
-                }
+    // a term, or what the syntax expects where it stops, e.g. "expects `then`"
+    pub fn try_new(tokens: &mut Tokens) -> Result<Term, String> {
+        let term = match tokens.peek() {
+            // a literal in function position, e.g. `1 2`, is applied and fails at runtime
+            Some(Token::Int(_)) => {
+                let num: i32 = tokens.try_consume_num()?;
+                Term::new_application(Term::Val(num), tokens)?
+            }
+            Some(Token::Bool(_)) => {
+                let b: String = tokens.consume_bool();
+                Term::new_application(Term::Bool(b), tokens)?
             }
-            Token::PS => {
+            Some(Token::SS) => {
+                tokens.pop(); // consume [
+                tokens.expect(Token::SE, "]")?;
+                Term::new_application(Term::Nil, tokens)?
+            }
+            Some(Token::Var(_)) => {
+                let var: String = tokens.consume_var();
+                Term::new_application(Term::Var(var), tokens)?
+            }
+            Some(Token::Index(_)) => {
+                let index: usize = tokens.consume_index();
+                Term::new_application(Term::Index(index), tokens)?
+            }
+            Some(Token::PS) => {
                 tokens.pop(); // consume (
-                let expression = Expression::new(tokens);
-                tokens.pop(); // consume )
-                Term::Paren(expression)
+                let expression = Expression::try_new(tokens)?;
+                tokens.expect(Token::PE, ")")?;
+                Term::new_application(Term::Paren(expression), tokens)?
             }
-            Token::IF => {
+            Some(Token::IF) => {
                 tokens.pop(); // consume if
-                let condition_expression = Expression::new(tokens);
-                tokens.pop(); // consume then
-                let then_expression = Expression::new(tokens);
-                tokens.pop(); // consume else
-                let else_expression = Expression::new(tokens);
+                let condition_expression = Expression::try_new(tokens)?;
+                tokens.expect(Token::THEN, "then")?;
+                let then_expression = Expression::try_new(tokens)?;
+                tokens.expect(Token::ELSE, "else")?;
+                let else_expression = Expression::try_new(tokens)?;
                 Term::If(IfTerm {
                     condition_expression,
                     then_expression,
                     else_expression,
                 })
             }
-            Token::LET => {
+            Some(Token::LET) => {
                 tokens.pop(); // consume let
-                let let_expression = LetExpression::new(tokens);
-                tokens.pop(); // consume in
-                let in_expression = Expression::new(tokens);
+                if let Some(Token::REC) = tokens.peek() {
+                    tokens.pop(); // consume rec
+                    let identifier: String = tokens.try_consume_var()?;
+                    tokens.expect(Token::EQ, "=")?;
+                    let fun_term = FunTerm::try_new(tokens)?;
+                    tokens.expect(Token::IN, "in")?;
+                    let in_expression = Expression::try_new(tokens)?;
+                    return Ok(Term::LetRec(LetRecTerm {
+                        identifier,
+                        fun_term,
+                        in_expression,
+                    }));
+                }
+                let let_expression = LetExpression::try_new(tokens)?;
+                tokens.expect(Token::IN, "in")?;
+                let in_expression = Expression::try_new(tokens)?;
                 Term::Let(LetTerm {
                     let_expression,
                     in_expression,
                 })
             }
-            Token::FUN => {
-                tokens.pop(); // consume fun
-                let parameter: String = tokens.consume_var();
-                tokens.pop(); // consume ->
-                let function_body = Expression::new(tokens);
-                Term::Fun(FunTerm {
-                    parameter,
-                    function_body,
+            Some(Token::LETCC) => {
+                tokens.pop(); // consume letcc
+                let identifier: String = tokens.try_consume_var()?;
+                tokens.expect(Token::IN, "in")?;
+                let expression = Expression::try_new(tokens)?;
+                Term::LetCc(LetCcTerm {
+                    identifier,
+                    expression,
+                })
+            }
+            Some(Token::FUN) => Term::Fun(FunTerm::try_new(tokens)?),
+            Some(Token::REF) => {
+                tokens.pop(); // consume ref
+                Term::Ref(Term::new_argument(tokens)?)
+            }
+            Some(Token::DEREF) => {
+                tokens.pop(); // consume !
+                let argument = Term::new_argument(tokens)?;
+                Term::new_application(Term::Deref(argument), tokens)?
+            }
+            Some(Token::MATCH) => {
+                tokens.pop(); // consume match
+                let expression = Expression::try_new(tokens)?;
+                tokens.expect(Token::WITH, "with")?;
+                let mut clauses: Vec<(Pattern, Expression)> = Vec::new();
+                loop {
+                    let pattern = Pattern::try_new(tokens)?;
+                    tokens.expect(Token::ARROW, "->")?;
+                    clauses.push((pattern, Expression::try_new(tokens)?));
+                    match tokens.peek() {
+                        Some(Token::BAR) => {
+                            tokens.pop(); // consume |
+                        }
+                        _ => break,
+                    }
+                }
+                Term::Match(MatchTerm {
+                    expression,
+                    clauses,
                 })
             }
-            _ => panic!("todo"),
+            _ => return Err("expects an expression".to_string()),
+        };
+        Ok(term)
+    }
+
+    // function application is left associative: f x y = (f x) y
+    fn new_application(function: Term, tokens: &mut Tokens) -> Result<Term, String> {
+        let mut function = function;
+        while let Some(Token::Int(_))
+        | Some(Token::Bool(_))
+        | Some(Token::Var(_))
+        | Some(Token::Index(_))
+        | Some(Token::PS)
+        | Some(Token::SS)
+        | Some(Token::DEREF) = tokens.peek()
+        {
+            let argument = Term::new_argument(tokens)?;
+            let app_term = AppTerm {
+                function: Box::new(function),
+                argument,
+            };
+            function = Term::App(app_term);
         }
+        Ok(function)
+    }
+
+    // an operand of application, ref or !, such as 1, x, #1, [], (e) or !x
+    fn new_argument(tokens: &mut Tokens) -> Result<Expression, String> {
+        let argument_term = match tokens.peek() {
+            Some(Token::Int(_)) => Term::Val(tokens.try_consume_num()?),
+            Some(Token::Bool(_)) => Term::Bool(tokens.consume_bool()),
+            Some(Token::Var(_)) => Term::Var(tokens.consume_var()),
+            Some(Token::Index(_)) => Term::Index(tokens.consume_index()),
+            Some(Token::SS) => {
+                tokens.pop(); // consume [
+                tokens.expect(Token::SE, "]")?;
+                Term::Nil
+            }
+            Some(Token::DEREF) => {
+                tokens.pop(); // consume !
+                Term::Deref(Term::new_argument(tokens)?)
+            }
+            Some(Token::PS) => {
+                tokens.pop(); // consume (
+                let expression = Expression::try_new(tokens)?;
+                tokens.expect(Token::PE, ")")?;
+                Term::Paren(expression)
+            }
+            _ => return Err("expects an expression".to_string()),
+        };
+        let terms: Vec<(String, Term)> = vec![("".to_string(), argument_term)];
+        Ok(Expression { terms })
     }
 
-    pub fn get_val(self, environment: Environment) -> Value {
+    // see Expression::get_tree_string
+    pub fn get_tree_string(&self) -> String {
         match self {
-            Term::Val(num) => Value::Num(num),
-            Term::Var(identifier) => environment.get_val(&identifier),
-            Term::Paren(expression) => expression.get_val(environment),
-            Term::If(_if_term) => panic!("todo"),
-            Term::Let(let_term) => let_term.get_val(environment),
-            Term::Fun(fun_term) => fun_term.get_val(environment),
-            Term::App(app_term) => app_term.get_val(environment),
+            Term::Paren(expression) => expression.get_tree_string(),
+            Term::If(if_term) => format!(
+                "(if {} then {} else {})",
+                if_term.condition_expression.get_tree_string(),
+                if_term.then_expression.get_tree_string(),
+                if_term.else_expression.get_tree_string()
+            ),
+            Term::Let(let_term) => format!(
+                "(let {} = {} in {})",
+                let_term.let_expression.identifier,
+                let_term.let_expression.expression.get_tree_string(),
+                let_term.in_expression.get_tree_string()
+            ),
+            Term::LetRec(let_rec_term) => format!(
+                "(let rec {} = {} in {})",
+                let_rec_term.identifier,
+                let_rec_term.fun_term.get_tree_string(),
+                let_rec_term.in_expression.get_tree_string()
+            ),
+            Term::Fun(fun_term) => fun_term.get_tree_string(),
+            Term::App(app_term) => format!(
+                "({} {})",
+                app_term.function.get_tree_string(),
+                app_term.argument.get_tree_string()
+            ),
+            Term::Match(match_term) => {
+                let clauses: Vec<String> = match_term
+                    .clauses
+                    .iter()
+                    .map(|(pattern, expression)| {
+                        format!("{} -> {}", pattern, expression.get_tree_string())
+                    })
+                    .collect();
+                format!(
+                    "(match {} with {})",
+                    match_term.expression.get_tree_string(),
+                    clauses.join(" | ")
+                )
+            }
+            Term::Ref(expression) => format!("(ref {})", expression.get_tree_string()),
+            Term::Deref(expression) => format!("(!{})", expression.get_tree_string()),
+            Term::LetCc(let_cc_term) => format!(
+                "(letcc {} in {})",
+                let_cc_term.identifier,
+                let_cc_term.expression.get_tree_string()
+            ),
+            _ => self.to_string(),
         }
     }
+
     pub fn get_identifier(self) -> String {
         match self {
             Term::Var(identifier) => identifier,
             _ => panic!("unexpected"),
         }
     }
-    pub fn to_string(&self, environment: &Environment) -> String {
+    pub fn get_index(self) -> usize {
         match self {
-            Term::Val(num) => num.to_string(),
-            Term::Var(identifier) => identifier.clone(),
-            Term::Paren(expression) => format!("({})", expression.to_string(environment)),
-            Term::If(if_term) => if_term.to_string(environment),
-            Term::Let(let_term) => let_term.to_string(environment),
-            Term::Fun(fun_term) => fun_term.to_string(environment),
-            Term::App(app_term) => app_term.to_string(environment),
+            Term::Index(index) => index,
+            _ => panic!("unexpected"),
+        }
+    }
+}
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Term::Val(num) => write!(f, "{}", num),
+            Term::Bool(b) => write!(f, "{}", b),
+            Term::Var(identifier) => write!(f, "{}", identifier),
+            Term::Index(index) => write!(f, "#{}", index),
+            Term::Nil => write!(f, "[]"),
+            Term::Paren(expression) => write!(f, "({})", expression),
+            Term::If(if_term) => write!(f, "{}", if_term),
+            Term::Let(let_term) => write!(f, "{}", let_term),
+            Term::LetRec(let_rec_term) => write!(f, "{}", let_rec_term),
+            Term::Fun(fun_term) => write!(f, "{}", fun_term),
+            Term::App(app_term) => write!(f, "{}", app_term),
+            Term::Match(match_term) => write!(f, "{}", match_term),
+            Term::Ref(expression) => write!(f, "ref {}", expression),
+            Term::Deref(expression) => write!(f, "!{}", expression),
+            Term::LetCc(let_cc_term) => write!(f, "{}", let_cc_term),
         }
     }
 }
@@ -121,13 +279,12 @@ pub struct IfTerm {
     pub then_expression: Expression,
     pub else_expression: Expression,
 }
-impl IfTerm {
-    pub fn to_string(&self, environment: &Environment) -> String {
-        format!(
+impl fmt::Display for IfTerm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
             "if {} then {} else {}",
-            self.condition_expression.to_string(environment),
-            self.then_expression.to_string(environment),
-            self.else_expression.to_string(environment)
+            self.condition_expression, self.then_expression, self.else_expression
         )
     }
 }
@@ -137,72 +294,124 @@ pub struct LetTerm {
     pub let_expression: LetExpression,
     pub in_expression: Expression,
 }
-impl LetTerm {
-    pub fn get_val(self, environment: Environment) -> Value {
-        let mut new_env = environment.clone();
-        let new_val = self.let_expression.expression.get_val(environment);
-        new_env.set_val(self.let_expression.identifier, new_val);
-        self.in_expression.get_val(new_env)
+impl fmt::Display for LetTerm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "let {} in {}", self.let_expression, self.in_expression)
     }
-    pub fn to_string(&self, environment: &Environment) -> String {
-        format!(
-            "let {} in {}",
-            self.let_expression.to_string(&environment),
-            self.in_expression.to_string(&environment)
+}
+
+#[derive(Debug, Clone)]
+pub struct LetRecTerm {
+    pub identifier: String,
+    pub fun_term: FunTerm,
+    pub in_expression: Expression,
+}
+impl fmt::Display for LetRecTerm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "let rec {} = {} in {}",
+            self.identifier, self.fun_term, self.in_expression
         )
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct FunTerm {
     pub parameter: String,
     pub function_body: Expression,
 }
 impl FunTerm {
-    pub fn get_val(self, environment: Environment) -> Value {
-        Value::Fun(self, environment)
+    pub fn try_new(tokens: &mut Tokens) -> Result<FunTerm, String> {
+        tokens.expect(Token::FUN, "fun")?;
+        let parameter: String = tokens.try_consume_var()?;
+        tokens.expect(Token::ARROW, "->")?;
+        let function_body = Expression::try_new(tokens)?;
+        Ok(FunTerm {
+            parameter,
+            function_body,
+        })
     }
-    pub fn to_string(&self, environment: &Environment) -> String {
+    pub fn get_tree_string(&self) -> String {
         format!(
-            "fun {} -> {}",
+            "(fun {} -> {})",
             self.parameter,
-            self.function_body.to_string(environment)
+            self.function_body.get_tree_string()
         )
     }
 }
+impl fmt::Display for FunTerm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "fun {} -> {}", self.parameter, self.function_body)
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct AppTerm {
     pub function: Box<Term>,
     pub argument: Expression,
 }
-impl AppTerm {
-    pub fn get_val(self, environment: Environment) -> Value {
-        let function_name = self.function.get_identifier();
-        let value: Value = environment.get_val(&function_name);
-        match value {
-            Value::Fun(fun_term, mut clojure_env) => {
-                let parameter: String = fun_term.parameter;
-                clojure_env.set_val(parameter, self.argument.get_val(environment));
-                let expression: Expression = fun_term.function_body;
-                expression.get_val(clojure_env)
-            }
-            _ => panic!("unexpected"),
-        }
+impl fmt::Display for AppTerm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.function, self.argument)
     }
-    pub fn get_fun_info(&self, environment: Environment) -> (FunTerm, Environment) {
-        let function_name = self.function.clone().get_identifier();
-        let value: Value = environment.get_val(&function_name);
-        match value {
-            Value::Fun(fun_term, env) => (fun_term, env),
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchTerm {
+    pub expression: Expression,
+    pub clauses: Vec<(Pattern, Expression)>,
+}
+impl fmt::Display for MatchTerm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let clauses: Vec<String> = self
+            .clauses
+            .iter()
+            .map(|(pattern, expression)| format!("{} -> {}", pattern, expression))
+            .collect();
+        write!(f, "match {} with {}", self.expression, clauses.join(" | "))
+    }
+}
+
+// the `match e with [] -> e1 | x :: y -> e2` form of EvalML4 and EvalContML4
+#[derive(Debug, Clone)]
+pub struct ListMatch {
+    pub expression: Expression,
+    pub nil_expression: Expression,
+    pub head_identifier: String,
+    pub tail_identifier: String,
+    pub cons_expression: Expression,
+}
+impl ListMatch {
+    pub fn new(term: MatchTerm) -> ListMatch {
+        let mut clauses = term.clauses.into_iter();
+        match (clauses.next(), clauses.next(), clauses.next()) {
+            (
+                Some((Pattern::Nil, nil_expression)),
+                Some((Pattern::Cons(head, tail), cons_expression)),
+                None,
+            ) => match (*head, *tail) {
+                (Pattern::Var(head_identifier), Pattern::Var(tail_identifier)) => ListMatch {
+                    expression: term.expression,
+                    nil_expression,
+                    head_identifier,
+                    tail_identifier,
+                    cons_expression,
+                },
+                _ => panic!("unexpected"),
+            },
             _ => panic!("unexpected"),
         }
     }
-    pub fn to_string(&self, environment: &Environment) -> String {
-        format!(
-            "{} {}",
-            self.function.clone().get_identifier(),
-            self.argument.to_string(environment)
-        )
+}
+
+#[derive(Debug, Clone)]
+pub struct LetCcTerm {
+    pub identifier: String,
+    pub expression: Expression,
+}
+impl fmt::Display for LetCcTerm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "letcc {} in {}", self.identifier, self.expression)
     }
 }
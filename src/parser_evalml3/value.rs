@@ -1,30 +1,95 @@
-use super::environment::Environment;
+use super::environment;
+use super::lexer::{Token, Tokens};
 use super::terms::FunTerm;
 
+use std::fmt;
 use std::ops::{Add, Mul, Sub};
 
-#[derive(Debug, Clone)]
+pub type Environment = environment::Environment<Value>;
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Num(i32),
     Bool(String),
     Fun(FunTerm, Environment),
+    RecFun(String, FunTerm, Environment),
+    Error,
 }
-impl Value {
-    pub fn to_string(&self) -> String {
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Value::Num(num) => num.to_string(),
-            Value::Bool(b) => b.to_string(),
-            Value::Fun(term, environment) => format!(
-                "({})[{}]",
-                environment.clone().to_string_for_clojure(),
-                term.to_string(environment)
+            Value::Num(num) => write!(f, "{}", num),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Fun(term, environment) => {
+                write!(f, "({})[{}]", environment.to_string_for_clojure(), term)
+            }
+            Value::RecFun(identifier, term, environment) => write!(
+                f,
+                "({})[rec {} = {}]",
+                environment.to_string_for_clojure(),
+                identifier,
+                term
             ),
+            Value::Error => write!(f, "error"),
+        }
+    }
+}
+impl Value {
+    pub fn consume(tokens: &mut Tokens) -> Value {
+        Value::try_new(tokens).expect("a value")
+    }
+    // v ::= i | b | error | (E)[fun x -> e] | (E)[rec f = fun x -> e], as derivations write
+    // values, or what the syntax expects where it stops
+    pub fn try_new(tokens: &mut Tokens) -> Result<Value, String> {
+        match tokens.peek() {
+            Some(Token::Int(_)) => Ok(Value::Num(tokens.try_consume_num()?)),
+            Some(Token::Bool(_)) => Ok(Value::Bool(tokens.consume_bool())),
+            Some(Token::ERR) => {
+                tokens.pop(); // consume error
+                Ok(Value::Error)
+            }
+            Some(Token::PS) => {
+                tokens.pop(); // consume (
+                let environment =
+                    Environment::try_new_until(tokens, Token::PE, ")", Value::try_new)?;
+                tokens.expect(Token::PE, ")")?;
+                tokens.expect(Token::SS, "[")?;
+                let value = if let Some(Token::REC) = tokens.peek() {
+                    tokens.pop(); // consume rec
+                    let identifier = tokens.try_consume_var()?;
+                    tokens.expect(Token::EQ, "=")?;
+                    Value::RecFun(identifier, FunTerm::try_new(tokens)?, environment)
+                } else {
+                    Value::Fun(FunTerm::try_new(tokens)?, environment)
+                };
+                tokens.expect(Token::SE, "]")?;
+                Ok(value)
+            }
+            _ => Err("expects a value".to_string()),
+        }
+    }
+    pub fn comp(&self, other: &Value) -> Value {
+        match self {
+            Value::Num(num1) => match other {
+                Value::Num(num2) => {
+                    if num1 < num2 {
+                        Value::Bool(String::from("true"))
+                    } else {
+                        Value::Bool(String::from("false"))
+                    }
+                }
+                _ => Value::Error,
+            },
+            _ => Value::Error,
         }
     }
-    pub fn get_num(self) -> i32 {
+    // the suffix of an error rule name (E-PlusBoolL, E-IfInt, ...) caused by this value
+    pub fn get_kind(&self) -> String {
         match self {
-            Value::Num(i) => i,
-            _ => panic!("unexpcted"),
+            Value::Num(_) => "Int".to_string(),
+            Value::Bool(_) => "Bool".to_string(),
+            Value::Fun(_, _) | Value::RecFun(_, _, _) => "Fun".to_string(),
+            Value::Error => "Error".to_string(),
         }
     }
 }
@@ -35,9 +100,9 @@ impl Add for Value {
         match self {
             Value::Num(num1) => match other {
                 Value::Num(num2) => Value::Num(num1 + num2),
-                _ => panic!(""),
+                _ => Value::Error,
             },
-            _ => panic!(""),
+            _ => Value::Error,
         }
     }
 }
@@ -48,9 +113,9 @@ impl Sub for Value {
         match self {
             Value::Num(num1) => match other {
                 Value::Num(num2) => Value::Num(num1 - num2),
-                _ => panic!(""),
+                _ => Value::Error,
             },
-            _ => panic!(""),
+            _ => Value::Error,
         }
     }
 }
@@ -61,9 +126,9 @@ impl Mul for Value {
         match self {
             Value::Num(num1) => match other {
                 Value::Num(num2) => Value::Num(num1 * num2),
-                _ => panic!(""),
+                _ => Value::Error,
             },
-            _ => panic!(""),
+            _ => Value::Error,
         }
     }
 }
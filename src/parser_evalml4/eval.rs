@@ -0,0 +1,158 @@
+use super::super::parser_evalml3::expression::Expression;
+use super::super::parser_evalml3::pattern::Pattern;
+use super::super::parser_evalml3::terms::{
+    AppTerm, FunTerm, IfTerm, LetRecTerm, LetTerm, MatchTerm, Term,
+};
+use super::value::{Environment, Value};
+
+// evaluation of the syntax shared with EvalML3, to the values of EvalML4 and EvalML5
+pub trait Eval {
+    fn get_val(self, environment: Environment) -> Value;
+}
+
+impl Eval for Expression {
+    fn get_val(mut self, environment: Environment) -> Value {
+        if self.terms.len() == 1 {
+            let (_, term) = self.terms.pop().expect("");
+            term.get_val(environment)
+        } else {
+            let (split_position, operator) = self.get_split_position();
+            let (former, latter) = self.get_splitted_expression(split_position);
+            let former_val = former.get_val(environment.clone());
+            let latter_val = latter.get_val(environment);
+            match operator.as_ref() {
+                "+" => former_val + latter_val,
+                "*" => former_val * latter_val,
+                "-" => former_val - latter_val,
+                "<" => former_val.comp(&latter_val),
+                "::" => match (former_val, latter_val) {
+                    (Value::Error, _) | (_, Value::Error) => Value::Error,
+                    (former_val, latter_val) => {
+                        Value::Cons(Box::new(former_val), Box::new(latter_val))
+                    }
+                },
+                _ => panic!(""),
+            }
+        }
+    }
+}
+
+impl Eval for Term {
+    fn get_val(self, environment: Environment) -> Value {
+        match self {
+            Term::Val(num) => Value::Num(num),
+            Term::Bool(b) => Value::Bool(b),
+            Term::Nil => Value::Nil,
+            Term::Var(identifier) => environment.get_val(&identifier),
+            Term::Paren(expression) => expression.get_val(environment),
+            Term::If(if_term) => if_term.get_val(environment),
+            Term::Let(let_term) => let_term.get_val(environment),
+            Term::LetRec(let_rec_term) => let_rec_term.get_val(environment),
+            Term::Fun(fun_term) => fun_term.get_val(environment),
+            Term::App(app_term) => app_term.get_val(environment),
+            Term::Match(match_term) => match_term.get_val(environment),
+            _ => panic!("unexpected"),
+        }
+    }
+}
+
+impl Eval for IfTerm {
+    fn get_val(self, environment: Environment) -> Value {
+        match self.condition_expression.get_val(environment.clone()) {
+            Value::Bool(b) => match b.as_ref() {
+                "true" => self.then_expression.get_val(environment),
+                "false" => self.else_expression.get_val(environment),
+                _ => panic!("expects true or false"),
+            },
+            _ => Value::Error,
+        }
+    }
+}
+
+impl Eval for LetTerm {
+    fn get_val(self, environment: Environment) -> Value {
+        let mut new_env = environment.clone();
+        let new_val = self.let_expression.expression.get_val(environment);
+        if let Value::Error = new_val {
+            return Value::Error;
+        }
+        new_env.set_val(self.let_expression.identifier, new_val);
+        self.in_expression.get_val(new_env)
+    }
+}
+
+impl Eval for LetRecTerm {
+    fn get_val(self, environment: Environment) -> Value {
+        let mut new_env = environment.clone();
+        let new_val = Value::RecFun(self.identifier.clone(), self.fun_term, environment);
+        new_env.set_val(self.identifier, new_val);
+        self.in_expression.get_val(new_env)
+    }
+}
+
+impl Eval for FunTerm {
+    fn get_val(self, environment: Environment) -> Value {
+        Value::Fun(self, environment)
+    }
+}
+
+impl Eval for AppTerm {
+    fn get_val(self, environment: Environment) -> Value {
+        let value: Value = self.function.get_val(environment.clone());
+        let argument_value: Value = self.argument.get_val(environment);
+        if let Value::Error = argument_value {
+            return Value::Error;
+        }
+        match value {
+            Value::Fun(fun_term, mut clojure_env) => {
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                fun_term.function_body.get_val(clojure_env)
+            }
+            Value::RecFun(identifier, fun_term, mut clojure_env) => {
+                let rec_fun =
+                    Value::RecFun(identifier.clone(), fun_term.clone(), clojure_env.clone());
+                clojure_env.set_val(identifier, rec_fun);
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                fun_term.function_body.get_val(clojure_env)
+            }
+            _ => Value::Error,
+        }
+    }
+}
+
+impl Eval for MatchTerm {
+    fn get_val(self, environment: Environment) -> Value {
+        let value = self.expression.get_val(environment.clone());
+        if let Value::Error = value {
+            return Value::Error;
+        }
+        for (pattern, expression) in self.clauses {
+            if let Some(bindings) = get_bindings(&pattern, &value) {
+                let mut new_env = environment;
+                new_env.stack.extend(bindings.stack);
+                return expression.get_val(new_env);
+            }
+        }
+        Value::Error
+    }
+}
+
+// returns the bindings made by the pattern, or None if it doesn't match
+pub fn get_bindings(pattern: &Pattern, value: &Value) -> Option<Environment> {
+    let mut environment = Environment { stack: Vec::new() };
+    match (pattern, value) {
+        (Pattern::Var(identifier), _) => {
+            environment.set_val(identifier.clone(), value.clone());
+            Some(environment)
+        }
+        (Pattern::Wild, _) | (Pattern::Nil, Value::Nil) => Some(environment),
+        (Pattern::Cons(head, tail), Value::Cons(head_value, tail_value)) => {
+            let head_bindings = get_bindings(head, head_value)?;
+            let tail_bindings = get_bindings(tail, tail_value)?;
+            environment.stack.extend(head_bindings.stack);
+            environment.stack.extend(tail_bindings.stack);
+            Some(environment)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,108 @@
+use super::super::parser_evalml3::expression::Expression;
+use super::super::parser_evalml3::nodes::bnodes::BOpNode;
+use super::super::parser_evalml3::nodes::writer::Rule;
+use super::super::parser_evalml3::terms::Term;
+use super::nodes::enodes::{
+    EAppNode, EBNode, EConsNode, EFunNode, EIfNode, ELetNode, ELetRecNode, EMatchNode, EValNode,
+    EVarNode,
+};
+use super::nodes::writer::RuleWriter;
+use super::value::Environment;
+use std::io::{self, Write};
+
+pub mod enodes;
+pub mod writer;
+
+#[derive(Debug, Clone)]
+pub enum RuleNode {
+    EVar(EVarNode),
+    EVal(EValNode),
+    EIf(EIfNode),
+    ELet(ELetNode),
+    ELetRec(ELetRecNode),
+    EBNode(EBNode),
+    EFun(EFunNode),
+    EApp(EAppNode),
+    ECons(EConsNode),
+    EMatch(EMatchNode),
+    BOp(BOpNode),
+}
+
+impl RuleNode {
+    pub fn new(environment: Environment, mut expression: Expression) -> RuleNode {
+        let len: usize = expression.terms.len();
+        let original_expression = expression.clone();
+        if len == 1 {
+            let term = expression.get_first_term();
+            match term {
+                Term::If(if_node) => RuleNode::EIf(EIfNode {
+                    environment,
+                    expression: original_expression,
+                    term: if_node,
+                }),
+                Term::Var(_) => RuleNode::EVar(EVarNode {
+                    environment,
+                    expression: original_expression,
+                }),
+                Term::Paren(expression) => RuleNode::new(environment, expression),
+                Term::Val(_) | Term::Bool(_) | Term::Nil => RuleNode::EVal(EValNode {
+                    environment,
+                    expression: original_expression,
+                }),
+                Term::Let(let_node) => RuleNode::ELet(ELetNode {
+                    environment,
+                    expression: original_expression,
+                    term: let_node,
+                }),
+                Term::LetRec(let_rec_node) => RuleNode::ELetRec(ELetRecNode {
+                    environment,
+                    expression: original_expression,
+                    term: let_rec_node,
+                }),
+                Term::Fun(fun_node) => RuleNode::EFun(EFunNode {
+                    environment,
+                    term: fun_node,
+                }),
+                Term::App(app_node) => RuleNode::EApp(EAppNode {
+                    environment,
+                    term: app_node,
+                }),
+                Term::Match(match_node) => RuleNode::EMatch(EMatchNode {
+                    environment,
+                    expression: original_expression,
+                    term: match_node,
+                }),
+                _ => panic!("unexpected"),
+            }
+        } else if expression.get_split_position().1 == "::" {
+            RuleNode::ECons(EConsNode {
+                environment,
+                expression: original_expression,
+            })
+        } else {
+            RuleNode::EBNode(EBNode {
+                environment,
+                expression: original_expression,
+            })
+        }
+    }
+}
+impl Rule for RuleNode {
+    type Context = ();
+
+    fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        match self {
+            RuleNode::EVar(node) => node.show(writer),
+            RuleNode::EVal(node) => node.show(writer),
+            RuleNode::EIf(node) => node.show(writer),
+            RuleNode::ELet(node) => node.show(writer),
+            RuleNode::ELetRec(node) => node.show(writer),
+            RuleNode::EBNode(node) => node.show(writer),
+            RuleNode::EFun(node) => node.show(writer),
+            RuleNode::EApp(node) => node.show(writer),
+            RuleNode::ECons(node) => node.show(writer),
+            RuleNode::EMatch(node) => node.show(writer),
+            RuleNode::BOp(node) => node.show(writer),
+        }
+    }
+}
@@ -0,0 +1,477 @@
+use super::super::super::parser_evalml3::expression::Expression;
+use super::super::super::parser_evalml3::nodes::bnodes::BOpNode;
+use super::super::super::parser_evalml3::terms::{
+    AppTerm, FunTerm, IfTerm, LetRecTerm, LetTerm, ListMatch, MatchTerm, Term,
+};
+use super::super::eval::Eval;
+use super::super::nodes::RuleNode;
+use super::super::value::{Environment, Value};
+use super::writer::{Premises, RuleWriter};
+
+use std::io::{self, Write};
+
+#[derive(Debug, Clone)]
+pub struct EVarNode {
+    pub environment: Environment,
+    pub expression: Expression,
+}
+impl EVarNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let mut terms = self.expression.terms.clone();
+        let (_, term) = terms.pop().expect("");
+        let identifier = term.get_identifier();
+
+        let (premise, rule_str) = if self.environment.get_match_loc(&identifier) == 0 {
+            (None, "E-Var1".to_string())
+        } else {
+            let mut new_env = self.environment.clone();
+            new_env.pop_val();
+            (
+                Some(RuleNode::new(new_env, self.expression)),
+                "E-Var2".to_string(),
+            )
+        };
+        writer.show_rule(
+            Some(self.environment.clone()),
+            identifier.clone(),
+            self.environment.get_val(&identifier).to_string(),
+            rule_str,
+            false,
+            Premises(premise, None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EIfNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub term: IfTerm,
+}
+impl EIfNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let condition_expression = self.term.condition_expression;
+        let then_expression = self.term.then_expression;
+        let else_expression = self.term.else_expression;
+
+        let condition_value = condition_expression
+            .clone()
+            .get_val(self.environment.clone());
+        let condition_premise = RuleNode::new(self.environment.clone(), condition_expression);
+
+        let flag: String = match condition_value {
+            Value::Bool(b) => b,
+            _ => {
+                return writer.show_rule(
+                    Some(self.environment.clone()),
+                    self.expression.clone().to_string(),
+                    Value::Error.to_string(),
+                    format!("E-If{}", condition_value.get_kind()),
+                    false,
+                    Premises(Some(condition_premise), None, None),
+                )
+            }
+        };
+
+        let (branch_expression, rule_str) = if flag == "true" {
+            (then_expression, "E-IfT".to_string())
+        } else {
+            (else_expression, "E-IfF".to_string())
+        };
+        let branch_value = branch_expression.clone().get_val(self.environment.clone());
+        let rule_str = match branch_value {
+            Value::Error => rule_str + "Error",
+            _ => rule_str,
+        };
+        let branch_premise = RuleNode::new(self.environment.clone(), branch_expression);
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.expression.clone().to_string(),
+            branch_value.to_string(),
+            rule_str,
+            false,
+            Premises(Some(condition_premise), Some(branch_premise), None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EBNode {
+    pub environment: Environment,
+    pub expression: Expression,
+}
+impl EBNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let (split_position, operator) = self.expression.get_split_position();
+        let (former, latter) = self.expression.get_splitted_expression(split_position);
+
+        let value1 = former.clone().get_val(self.environment.clone());
+        let value2 = latter.clone().get_val(self.environment.clone());
+        let premise1 = RuleNode::new(self.environment.clone(), former);
+        let premise2 = RuleNode::new(self.environment.clone(), latter);
+
+        let rule_name = match operator.as_ref() {
+            "+" => "Plus",
+            "*" => "Times",
+            "-" => "Minus",
+            "<" => "Lt",
+            _ => panic!("todo"),
+        };
+        let (i1, i2) = match (value1, value2) {
+            (Value::Num(i1), Value::Num(i2)) => (i1, i2),
+            (Value::Num(_), value2) => {
+                // the left operand is fine, so the right one causes the error
+                return writer.show_rule(
+                    Some(self.environment.clone()),
+                    self.expression.to_string(),
+                    Value::Error.to_string(),
+                    format!("E-{}{}R", rule_name, value2.get_kind()),
+                    false,
+                    Premises(Some(premise2), None, None),
+                );
+            }
+            (value1, _) => {
+                return writer.show_rule(
+                    Some(self.environment.clone()),
+                    self.expression.to_string(),
+                    Value::Error.to_string(),
+                    format!("E-{}{}L", rule_name, value1.get_kind()),
+                    false,
+                    Premises(Some(premise1), None, None),
+                );
+            }
+        };
+
+        let premise = RuleNode::BOp(BOpNode {
+            i1,
+            i2,
+            op: operator.clone(),
+        });
+        let val_str = match operator.as_ref() {
+            "+" => (i1 + i2).to_string(),
+            "*" => (i1 * i2).to_string(),
+            "-" => (i1 - i2).to_string(),
+            "<" => (i1 < i2).to_string(),
+            _ => panic!("todo"),
+        };
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.expression.to_string(),
+            val_str,
+            format!("E-{}", rule_name),
+            false,
+            Premises(Some(premise1), Some(premise2), Some(premise)),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EValNode {
+    pub environment: Environment,
+    pub expression: Expression,
+}
+impl EValNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let mut terms = self.expression.clone().terms;
+        let (_, term) = terms.pop().expect("");
+        match term {
+            Term::Val(_) => writer.show_rule(
+                Some(self.environment.clone()),
+                self.expression.clone().to_string(),
+                self.expression
+                    .get_val(self.environment)
+                    .get_num()
+                    .to_string(),
+                "E-Int".to_string(),
+                false,
+                Premises(None, None, None),
+            ),
+            Term::Bool(_) => writer.show_rule(
+                Some(self.environment.clone()),
+                self.expression.clone().to_string(),
+                self.expression.get_val(self.environment).to_string(),
+                "E-Bool".to_string(),
+                false,
+                Premises(None, None, None),
+            ),
+            Term::Nil => writer.show_rule(
+                Some(self.environment.clone()),
+                self.expression.clone().to_string(),
+                self.expression.get_val(self.environment).to_string(),
+                "E-Nil".to_string(),
+                false,
+                Premises(None, None, None),
+            ),
+            _ => panic!("unexpected"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ELetNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub term: LetTerm,
+}
+impl ELetNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let in_expression = self.term.clone().in_expression;
+        let let_expression = self.term.clone().let_expression;
+        let mut new_env = self.environment.clone();
+        let val = let_expression
+            .expression
+            .clone()
+            .get_val(self.environment.clone());
+        let let_premise = RuleNode::new(self.environment.clone(), let_expression.expression);
+        if let Value::Error = val {
+            return writer.show_rule(
+                Some(self.environment.clone()),
+                self.expression.clone().to_string(),
+                Value::Error.to_string(),
+                "E-LetErrorL".to_string(),
+                false,
+                Premises(Some(let_premise), None, None),
+            );
+        }
+        new_env.set_val(let_expression.identifier, val);
+        let in_premise = RuleNode::new(new_env.clone(), in_expression.clone());
+        let val = self.term.get_val(self.environment.clone());
+        let rule_str = match val {
+            Value::Error => "E-LetErrorR".to_string(),
+            _ => "E-Let".to_string(),
+        };
+
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.expression.clone().to_string(),
+            val.to_string(),
+            rule_str,
+            false,
+            Premises(Some(let_premise), Some(in_premise), None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ELetRecNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub term: LetRecTerm,
+}
+impl ELetRecNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let let_rec_term = self.term.clone();
+        let mut new_env = self.environment.clone();
+        let rec_fun = Value::RecFun(
+            let_rec_term.identifier.clone(),
+            let_rec_term.fun_term,
+            self.environment.clone(),
+        );
+        new_env.set_val(let_rec_term.identifier, rec_fun);
+        let in_premise = RuleNode::new(new_env, let_rec_term.in_expression);
+        let val = self.term.get_val(self.environment.clone());
+        let rule_str = match val {
+            Value::Error => "E-LetRecError".to_string(),
+            _ => "E-LetRec".to_string(),
+        };
+
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.expression.clone().to_string(),
+            val.to_string(),
+            rule_str,
+            false,
+            Premises(Some(in_premise), None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EFunNode {
+    pub environment: Environment,
+    pub term: FunTerm,
+}
+impl EFunNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.term.clone().to_string(),
+            self.term.get_val(self.environment).to_string(),
+            "E-Fun".to_string(),
+            false,
+            Premises(None, None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EAppNode {
+    pub environment: Environment,
+    pub term: AppTerm,
+}
+impl EAppNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let terms: Vec<(String, Term)> = vec![("".to_string(), *self.term.clone().function)];
+        let function_expression = Expression { terms };
+        let function_value = function_expression
+            .clone()
+            .get_val(self.environment.clone());
+        let premise1 = RuleNode::new(self.environment.clone(), function_expression);
+
+        match function_value {
+            Value::Fun(_, _) | Value::RecFun(_, _, _) => (),
+            _ => {
+                let rule_str = match function_value {
+                    Value::Error => "E-AppErrorL".to_string(),
+                    _ => format!("E-App{}", function_value.get_kind()),
+                };
+                return writer.show_rule(
+                    Some(self.environment.clone()),
+                    self.term.clone().to_string(),
+                    Value::Error.to_string(),
+                    rule_str,
+                    false,
+                    Premises(Some(premise1), None, None),
+                );
+            }
+        }
+
+        let premise2 = RuleNode::new(self.environment.clone(), self.term.clone().argument);
+        let argument_value = self.term.clone().argument.get_val(self.environment.clone());
+        if let Value::Error = argument_value {
+            return writer.show_rule(
+                Some(self.environment.clone()),
+                self.term.clone().to_string(),
+                Value::Error.to_string(),
+                "E-AppErrorR".to_string(),
+                false,
+                Premises(Some(premise1), Some(premise2), None),
+            );
+        }
+        let (premise3, rule_str) = match function_value {
+            Value::Fun(fun_term, mut clojure_env) => {
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                (
+                    RuleNode::new(clojure_env, fun_term.function_body),
+                    "E-App".to_string(),
+                )
+            }
+            Value::RecFun(identifier, fun_term, mut clojure_env) => {
+                let rec_fun =
+                    Value::RecFun(identifier.clone(), fun_term.clone(), clojure_env.clone());
+                clojure_env.set_val(identifier, rec_fun);
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                (
+                    RuleNode::new(clojure_env, fun_term.function_body),
+                    "E-AppRec".to_string(),
+                )
+            }
+            _ => panic!("unexpected"),
+        };
+        let val = self.term.clone().get_val(self.environment.clone());
+        let rule_str = match val {
+            Value::Error => rule_str + "Error",
+            _ => rule_str,
+        };
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.term.clone().to_string(),
+            val.to_string(),
+            rule_str,
+            false,
+            Premises(Some(premise1), Some(premise2), Some(premise3)),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EConsNode {
+    pub environment: Environment,
+    pub expression: Expression,
+}
+impl EConsNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let (split_position, _) = self.expression.get_split_position();
+        let (former, latter) = self.expression.get_splitted_expression(split_position);
+
+        let value1 = former.clone().get_val(self.environment.clone());
+        let value2 = latter.clone().get_val(self.environment.clone());
+        let premise1 = RuleNode::new(self.environment.clone(), former);
+        let premise2 = RuleNode::new(self.environment.clone(), latter);
+
+        let (premise1, premise2, rule_str) = match (value1, value2) {
+            (Value::Error, _) => (premise1, None, "E-ConsErrorL".to_string()),
+            (_, Value::Error) => (premise2, None, "E-ConsErrorR".to_string()),
+            _ => (premise1, Some(premise2), "E-Cons".to_string()),
+        };
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.expression.to_string(),
+            self.expression
+                .clone()
+                .get_val(self.environment)
+                .to_string(),
+            rule_str,
+            false,
+            Premises(Some(premise1), premise2, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EMatchNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub term: MatchTerm,
+}
+impl EMatchNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let match_term = ListMatch::new(self.term.clone());
+        let value = match_term
+            .expression
+            .clone()
+            .get_val(self.environment.clone());
+        let premise1 = RuleNode::new(self.environment.clone(), match_term.expression);
+
+        let (premise2, rule_str) = match value {
+            Value::Nil => (
+                RuleNode::new(self.environment.clone(), match_term.nil_expression),
+                "E-MatchNil".to_string(),
+            ),
+            Value::Cons(head, tail) => {
+                let mut new_env = self.environment.clone();
+                new_env.set_val(match_term.head_identifier, *head);
+                new_env.set_val(match_term.tail_identifier, *tail);
+                (
+                    RuleNode::new(new_env, match_term.cons_expression),
+                    "E-MatchCons".to_string(),
+                )
+            }
+            _ => {
+                return writer.show_rule(
+                    Some(self.environment.clone()),
+                    self.expression.clone().to_string(),
+                    Value::Error.to_string(),
+                    format!("E-Match{}", value.get_kind()),
+                    false,
+                    Premises(Some(premise1), None, None),
+                );
+            }
+        };
+        let val = self.term.get_val(self.environment.clone());
+        let rule_str = match val {
+            Value::Error => rule_str + "Error",
+            _ => rule_str,
+        };
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.expression.clone().to_string(),
+            val.to_string(),
+            rule_str,
+            false,
+            Premises(Some(premise1), Some(premise2), None),
+        )
+    }
+}
+
+// EvalML4 only has `match e with [] -> e1 | x :: y -> e2`
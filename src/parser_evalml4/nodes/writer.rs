@@ -0,0 +1,5 @@
+use super::super::super::parser_evalml3::nodes::writer;
+pub use super::super::super::parser_evalml3::nodes::writer::Premises;
+use super::RuleNode;
+
+pub type RuleWriter<W> = writer::RuleWriter<W, RuleNode>;
@@ -0,0 +1,144 @@
+use super::super::parser_evalml3::environment;
+use super::super::parser_evalml3::lexer::{Token, Tokens};
+use super::super::parser_evalml3::terms::FunTerm;
+
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+pub type Environment = environment::Environment<Value>;
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Num(i32),
+    Bool(String),
+    Fun(FunTerm, Environment),
+    RecFun(String, FunTerm, Environment),
+    Nil,
+    Cons(Box<Value>, Box<Value>),
+    Error,
+}
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Num(num) => write!(f, "{}", num),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Fun(term, environment) => {
+                write!(f, "({})[{}]", environment.to_string_for_clojure(), term)
+            }
+            Value::RecFun(identifier, term, environment) => write!(
+                f,
+                "({})[rec {} = {}]",
+                environment.to_string_for_clojure(),
+                identifier,
+                term
+            ),
+            Value::Nil => write!(f, "[]"),
+            Value::Cons(head, tail) => match **head {
+                // `::` is right associative, so a list in the head needs parentheses
+                Value::Cons(_, _) => write!(f, "({}) :: {}", head, tail),
+                _ => write!(f, "{} :: {}", head, tail),
+            },
+            Value::Error => write!(f, "error"),
+        }
+    }
+}
+impl Value {
+    pub fn consume(tokens: &mut Tokens) -> Value {
+        let value = match tokens.peek() {
+            Some(token) => match token {
+                Token::Int(_) => Value::Num(tokens.consume_num()),
+                Token::Bool(_) => Value::Bool(tokens.consume_bool()),
+                Token::SS => {
+                    tokens.pop(); // consume [
+                    tokens.pop(); // consume ]
+                    Value::Nil
+                }
+                Token::PS => {
+                    tokens.pop(); // consume (
+                    let value = Value::consume(tokens);
+                    tokens.pop(); // consume )
+                    value
+                }
+                _ => panic!("unexpected"),
+            },
+            None => panic!("unexpected"),
+        };
+        match tokens.peek() {
+            Some(Token::Op(op)) if op == "::" => {
+                tokens.pop(); // consume ::
+                Value::Cons(Box::new(value), Box::new(Value::consume(tokens)))
+            }
+            _ => value,
+        }
+    }
+    pub fn comp(&self, other: &Value) -> Value {
+        match self {
+            Value::Num(num1) => match other {
+                Value::Num(num2) => {
+                    if num1 < num2 {
+                        Value::Bool(String::from("true"))
+                    } else {
+                        Value::Bool(String::from("false"))
+                    }
+                }
+                _ => Value::Error,
+            },
+            _ => Value::Error,
+        }
+    }
+    // the suffix of an error rule name (E-PlusBoolL, E-IfInt, ...) caused by this value
+    pub fn get_kind(&self) -> String {
+        match self {
+            Value::Num(_) => "Int".to_string(),
+            Value::Bool(_) => "Bool".to_string(),
+            Value::Fun(_, _) | Value::RecFun(_, _, _) => "Fun".to_string(),
+            Value::Nil | Value::Cons(_, _) => "List".to_string(),
+            Value::Error => "Error".to_string(),
+        }
+    }
+    pub fn get_num(self) -> i32 {
+        match self {
+            Value::Num(i) => i,
+            _ => panic!("unexpcted"),
+        }
+    }
+}
+impl Add for Value {
+    type Output = Self;
+
+    fn add(self, other: Value) -> Value {
+        match self {
+            Value::Num(num1) => match other {
+                Value::Num(num2) => Value::Num(num1 + num2),
+                _ => Value::Error,
+            },
+            _ => Value::Error,
+        }
+    }
+}
+impl Sub for Value {
+    type Output = Self;
+
+    fn sub(self, other: Value) -> Value {
+        match self {
+            Value::Num(num1) => match other {
+                Value::Num(num2) => Value::Num(num1 - num2),
+                _ => Value::Error,
+            },
+            _ => Value::Error,
+        }
+    }
+}
+impl Mul for Value {
+    type Output = Self;
+
+    fn mul(self, other: Value) -> Value {
+        match self {
+            Value::Num(num1) => match other {
+                Value::Num(num2) => Value::Num(num1 * num2),
+                _ => Value::Error,
+            },
+            _ => Value::Error,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use super::super::chapter1::util::get_no_rule_message;
+use super::super::parser_evalml3::expression::Expression;
+use super::super::parser_evalml3::nodes::bnodes::BOpNode;
+use super::super::parser_evalml3::nodes::writer::Rule;
+use super::super::parser_evalml3::terms::Term;
+use super::super::parser_evalml4::value::{Environment, Value};
+use super::nodes::enodes::{
+    EAppNode, EBNode, EConsNode, EFunNode, EIfNode, ELetNode, ELetRecNode, EMatchNode, EValNode,
+    EVarNode,
+};
+use super::nodes::mnodes::{MatchNode, NotMatchNode};
+use super::nodes::writer::RuleWriter;
+use std::io::{self, Write};
+
+pub mod enodes;
+pub mod mnodes;
+pub mod writer;
+
+#[derive(Debug, Clone)]
+pub enum RuleNode {
+    EVar(EVarNode),
+    EVal(EValNode),
+    EIf(EIfNode),
+    ELet(ELetNode),
+    ELetRec(ELetRecNode),
+    EBNode(EBNode),
+    EFun(EFunNode),
+    EApp(EAppNode),
+    ECons(EConsNode),
+    EMatch(EMatchNode),
+    BOp(BOpNode),
+    Match(MatchNode),
+    NotMatch(NotMatchNode),
+}
+
+impl RuleNode {
+    pub fn new(environment: Environment, mut expression: Expression) -> Result<RuleNode, String> {
+        let len: usize = expression.terms.len();
+        let original_expression = expression.clone();
+        if len == 1 {
+            let term = expression.get_first_term();
+            let node = match term {
+                Term::If(if_node) => {
+                    RuleNode::EIf(EIfNode::new(environment, original_expression, if_node)?)
+                }
+                Term::Var(_) => RuleNode::EVar(EVarNode::new(environment, original_expression)?),
+                Term::Paren(expression) => RuleNode::new(environment, expression)?,
+                Term::Val(_) | Term::Bool(_) | Term::Nil => {
+                    RuleNode::EVal(EValNode::new(environment, original_expression))
+                }
+                Term::Let(let_node) => {
+                    RuleNode::ELet(ELetNode::new(environment, original_expression, let_node)?)
+                }
+                Term::LetRec(let_rec_node) => RuleNode::ELetRec(ELetRecNode::new(
+                    environment,
+                    original_expression,
+                    let_rec_node,
+                )?),
+                Term::Fun(fun_node) => RuleNode::EFun(EFunNode {
+                    environment,
+                    term: fun_node,
+                }),
+                Term::App(app_node) => RuleNode::EApp(EAppNode::new(environment, app_node)?),
+                Term::Match(match_node) => RuleNode::EMatch(EMatchNode::new(
+                    environment,
+                    original_expression,
+                    match_node,
+                )?),
+                // references, continuations and indices are not part of EvalML5
+                _ => return Err(get_no_rule_message(&original_expression.to_string())),
+            };
+            Ok(node)
+        } else if expression.get_split_position().1 == "::" {
+            Ok(RuleNode::ECons(EConsNode::new(
+                environment,
+                original_expression,
+            )?))
+        } else {
+            Ok(RuleNode::EBNode(EBNode::new(
+                environment,
+                original_expression,
+            )?))
+        }
+    }
+    // the value the judgement evaluates to, which was computed when the node was built
+    pub fn get_val(&self) -> Value {
+        match self {
+            RuleNode::EVar(node) => node.value.clone(),
+            RuleNode::EVal(node) => node.value.clone(),
+            RuleNode::EIf(node) => node.value.clone(),
+            RuleNode::ELet(node) => node.value.clone(),
+            RuleNode::ELetRec(node) => node.value.clone(),
+            RuleNode::EBNode(node) => node.value.clone(),
+            RuleNode::EFun(node) => node.get_val(),
+            RuleNode::EApp(node) => node.value.clone(),
+            RuleNode::ECons(node) => node.value.clone(),
+            RuleNode::EMatch(node) => node.value.clone(),
+            RuleNode::BOp(_) | RuleNode::Match(_) | RuleNode::NotMatch(_) => {
+                panic!("unexpected")
+            }
+        }
+    }
+}
+impl Rule for RuleNode {
+    type Context = ();
+
+    fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        match self {
+            RuleNode::EVar(node) => node.show(writer),
+            RuleNode::EVal(node) => node.show(writer),
+            RuleNode::EIf(node) => node.show(writer),
+            RuleNode::ELet(node) => node.show(writer),
+            RuleNode::ELetRec(node) => node.show(writer),
+            RuleNode::EBNode(node) => node.show(writer),
+            RuleNode::EFun(node) => node.show(writer),
+            RuleNode::EApp(node) => node.show(writer),
+            RuleNode::ECons(node) => node.show(writer),
+            RuleNode::EMatch(node) => node.show(writer),
+            RuleNode::BOp(node) => node.show(writer),
+            RuleNode::Match(node) => node.show(writer),
+            RuleNode::NotMatch(node) => node.show(writer),
+        }
+    }
+}
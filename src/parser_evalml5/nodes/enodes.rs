@@ -0,0 +1,565 @@
+use super::super::super::chapter1::util::get_no_rule_message;
+use super::super::super::parser_evalml3::expression::Expression;
+use super::super::super::parser_evalml3::nodes::bnodes::BOpNode;
+use super::super::super::parser_evalml3::terms::{
+    AppTerm, FunTerm, IfTerm, LetRecTerm, LetTerm, MatchTerm, Term,
+};
+use super::super::super::parser_evalml4::eval::get_bindings;
+use super::super::super::parser_evalml4::value::{Environment, Value};
+use super::super::nodes::RuleNode;
+use super::mnodes::{MatchNode, NotMatchNode};
+use super::writer::{Premises, RuleWriter};
+
+use std::io::{self, Write};
+
+// as in EvalML3, every node is built with the derivations of its premises and keeps its
+// value, so that the parent node reuses it instead of evaluating the sub-expression again
+
+#[derive(Debug, Clone)]
+pub struct EVarNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub value: Value,
+}
+impl EVarNode {
+    pub fn new(environment: Environment, expression: Expression) -> Result<EVarNode, String> {
+        let mut terms = expression.terms.clone();
+        let (_, term) = terms.pop().expect("");
+        let identifier = term.get_identifier();
+        let value = match environment.find_val(&identifier) {
+            Some(value) => value,
+            None => return Err(get_no_rule_message(&expression.to_string())),
+        };
+        Ok(EVarNode {
+            environment,
+            expression,
+            value,
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_rule(
+            Some(self.environment),
+            self.expression.to_string(),
+            self.value.to_string(),
+            "E-Var".to_string(),
+            false,
+            Premises(None, None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EIfNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub value: Value,
+    pub rule: String,
+    pub condition_premise: Box<RuleNode>,
+    pub branch_premise: Option<Box<RuleNode>>,
+}
+impl EIfNode {
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        term: IfTerm,
+    ) -> Result<EIfNode, String> {
+        let condition_premise = RuleNode::new(environment.clone(), term.condition_expression)?;
+        let condition_value = condition_premise.get_val();
+
+        let flag: String = match condition_value {
+            Value::Bool(b) => b,
+            _ => {
+                return Ok(EIfNode {
+                    environment,
+                    expression,
+                    value: Value::Error,
+                    rule: format!("E-If{}", condition_value.get_kind()),
+                    condition_premise: Box::new(condition_premise),
+                    branch_premise: None,
+                })
+            }
+        };
+
+        let (branch_expression, rule) = if flag == "true" {
+            (term.then_expression, "E-IfT".to_string())
+        } else {
+            (term.else_expression, "E-IfF".to_string())
+        };
+        let branch_premise = RuleNode::new(environment.clone(), branch_expression)?;
+        let value = branch_premise.get_val();
+        let rule = match value {
+            Value::Error => rule + "Error",
+            _ => rule,
+        };
+        Ok(EIfNode {
+            environment,
+            expression,
+            value,
+            rule,
+            condition_premise: Box::new(condition_premise),
+            branch_premise: Some(Box::new(branch_premise)),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_rule(
+            Some(self.environment),
+            self.expression.to_string(),
+            self.value.to_string(),
+            self.rule,
+            false,
+            Premises(
+                Some(*self.condition_premise),
+                self.branch_premise.map(|premise| *premise),
+                None,
+            ),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EBNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub value: Value,
+    pub rule: String,
+    pub premises: Vec<RuleNode>,
+}
+impl EBNode {
+    pub fn new(environment: Environment, expression: Expression) -> Result<EBNode, String> {
+        let (split_position, operator) = expression.get_split_position();
+        let (former, latter) = expression.get_splitted_expression(split_position);
+
+        // := is only for references
+        let rule_name = match operator.as_ref() {
+            "+" => "Plus",
+            "*" => "Times",
+            "-" => "Minus",
+            "<" => "Lt",
+            _ => return Err(get_no_rule_message(&expression.to_string())),
+        };
+        let premise1 = RuleNode::new(environment.clone(), former)?;
+        let premise2 = RuleNode::new(environment.clone(), latter)?;
+
+        let (i1, i2) = match (premise1.get_val(), premise2.get_val()) {
+            (Value::Num(i1), Value::Num(i2)) => (i1, i2),
+            (Value::Num(_), value2) => {
+                // the left operand is fine, so the right one causes the error
+                return Ok(EBNode {
+                    environment,
+                    expression,
+                    value: Value::Error,
+                    rule: format!("E-{}{}R", rule_name, value2.get_kind()),
+                    premises: vec![premise2],
+                });
+            }
+            (value1, _) => {
+                return Ok(EBNode {
+                    environment,
+                    expression,
+                    value: Value::Error,
+                    rule: format!("E-{}{}L", rule_name, value1.get_kind()),
+                    premises: vec![premise1],
+                });
+            }
+        };
+
+        let value = match operator.as_ref() {
+            "+" => Value::Num(i1 + i2),
+            "*" => Value::Num(i1 * i2),
+            "-" => Value::Num(i1 - i2),
+            _ => Value::Num(i1).comp(&Value::Num(i2)),
+        };
+        let premise = RuleNode::BOp(BOpNode {
+            i1,
+            i2,
+            op: operator,
+        });
+        Ok(EBNode {
+            environment,
+            expression,
+            value,
+            rule: format!("E-{}", rule_name),
+            premises: vec![premise1, premise2, premise],
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let mut premises = self.premises.into_iter();
+        writer.show_rule(
+            Some(self.environment),
+            self.expression.to_string(),
+            self.value.to_string(),
+            self.rule,
+            false,
+            Premises(premises.next(), premises.next(), premises.next()),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EValNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub value: Value,
+}
+impl EValNode {
+    pub fn new(environment: Environment, expression: Expression) -> EValNode {
+        let mut terms = expression.terms.clone();
+        let (_, term) = terms.pop().expect("");
+        let value = match term {
+            Term::Val(num) => Value::Num(num),
+            Term::Bool(b) => Value::Bool(b),
+            Term::Nil => Value::Nil,
+            _ => panic!("unexpected"),
+        };
+        EValNode {
+            environment,
+            expression,
+            value,
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let rule_str = match self.value {
+            Value::Num(_) => "E-Int",
+            Value::Bool(_) => "E-Bool",
+            _ => "E-Nil",
+        };
+        writer.show_rule(
+            Some(self.environment),
+            self.expression.to_string(),
+            self.value.to_string(),
+            rule_str.to_string(),
+            false,
+            Premises(None, None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ELetNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub value: Value,
+    pub let_premise: Box<RuleNode>,
+    pub in_premise: Option<Box<RuleNode>>,
+}
+impl ELetNode {
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        term: LetTerm,
+    ) -> Result<ELetNode, String> {
+        let let_expression = term.let_expression;
+        let let_premise = RuleNode::new(environment.clone(), let_expression.expression)?;
+        let val = let_premise.get_val();
+        if let Value::Error = val {
+            return Ok(ELetNode {
+                environment,
+                expression,
+                value: Value::Error,
+                let_premise: Box::new(let_premise),
+                in_premise: None,
+            });
+        }
+        let mut new_env = environment.clone();
+        new_env.set_val(let_expression.identifier, val);
+        let in_premise = RuleNode::new(new_env, term.in_expression)?;
+        Ok(ELetNode {
+            environment,
+            expression,
+            value: in_premise.get_val(),
+            let_premise: Box::new(let_premise),
+            in_premise: Some(Box::new(in_premise)),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let rule_str = match (&self.in_premise, &self.value) {
+            (None, _) => "E-LetErrorL",
+            (Some(_), Value::Error) => "E-LetErrorR",
+            _ => "E-Let",
+        };
+        writer.show_rule(
+            Some(self.environment),
+            self.expression.to_string(),
+            self.value.to_string(),
+            rule_str.to_string(),
+            false,
+            Premises(
+                Some(*self.let_premise),
+                self.in_premise.map(|premise| *premise),
+                None,
+            ),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ELetRecNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub value: Value,
+    pub in_premise: Box<RuleNode>,
+}
+impl ELetRecNode {
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        term: LetRecTerm,
+    ) -> Result<ELetRecNode, String> {
+        let mut new_env = environment.clone();
+        let rec_fun = Value::RecFun(term.identifier.clone(), term.fun_term, environment.clone());
+        new_env.set_val(term.identifier, rec_fun);
+        let in_premise = RuleNode::new(new_env, term.in_expression)?;
+        Ok(ELetRecNode {
+            environment,
+            expression,
+            value: in_premise.get_val(),
+            in_premise: Box::new(in_premise),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let rule_str = match self.value {
+            Value::Error => "E-LetRecError",
+            _ => "E-LetRec",
+        };
+        writer.show_rule(
+            Some(self.environment),
+            self.expression.to_string(),
+            self.value.to_string(),
+            rule_str.to_string(),
+            false,
+            Premises(Some(*self.in_premise), None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EFunNode {
+    pub environment: Environment,
+    pub term: FunTerm,
+}
+impl EFunNode {
+    pub fn get_val(&self) -> Value {
+        Value::Fun(self.term.clone(), self.environment.clone())
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.term.to_string(),
+            self.get_val().to_string(),
+            "E-Fun".to_string(),
+            false,
+            Premises(None, None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EAppNode {
+    pub environment: Environment,
+    pub term: AppTerm,
+    pub value: Value,
+    pub rule: String,
+    pub premise1: Box<RuleNode>,
+    pub premise2: Option<Box<RuleNode>>,
+    pub premise3: Option<Box<RuleNode>>,
+}
+impl EAppNode {
+    pub fn new(environment: Environment, term: AppTerm) -> Result<EAppNode, String> {
+        let terms: Vec<(String, Term)> = vec![("".to_string(), *term.function.clone())];
+        let function_expression = Expression { terms };
+        let premise1 = RuleNode::new(environment.clone(), function_expression)?;
+        let function_value = premise1.get_val();
+        let error = |rule: String, premise2: Option<RuleNode>| EAppNode {
+            environment: environment.clone(),
+            term: term.clone(),
+            value: Value::Error,
+            rule,
+            premise1: Box::new(premise1.clone()),
+            premise2: premise2.map(Box::new),
+            premise3: None,
+        };
+
+        match function_value {
+            Value::Fun(_, _) | Value::RecFun(_, _, _) => (),
+            Value::Error => return Ok(error("E-AppErrorL".to_string(), None)),
+            _ => return Ok(error(format!("E-App{}", function_value.get_kind()), None)),
+        }
+
+        let premise2 = RuleNode::new(environment.clone(), term.argument.clone())?;
+        let argument_value = premise2.get_val();
+        if let Value::Error = argument_value {
+            return Ok(error("E-AppErrorR".to_string(), Some(premise2)));
+        }
+        let (premise3, rule) = match function_value {
+            Value::Fun(fun_term, mut clojure_env) => {
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                (
+                    RuleNode::new(clojure_env, fun_term.function_body)?,
+                    "E-App".to_string(),
+                )
+            }
+            Value::RecFun(identifier, fun_term, mut clojure_env) => {
+                let rec_fun =
+                    Value::RecFun(identifier.clone(), fun_term.clone(), clojure_env.clone());
+                clojure_env.set_val(identifier, rec_fun);
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                (
+                    RuleNode::new(clojure_env, fun_term.function_body)?,
+                    "E-AppRec".to_string(),
+                )
+            }
+            _ => panic!("unexpected"),
+        };
+        let value = premise3.get_val();
+        let rule = match value {
+            Value::Error => rule + "Error",
+            _ => rule,
+        };
+        Ok(EAppNode {
+            environment,
+            term,
+            value,
+            rule,
+            premise1: Box::new(premise1),
+            premise2: Some(Box::new(premise2)),
+            premise3: Some(Box::new(premise3)),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_rule(
+            Some(self.environment),
+            self.term.to_string(),
+            self.value.to_string(),
+            self.rule,
+            false,
+            Premises(
+                Some(*self.premise1),
+                self.premise2.map(|premise| *premise),
+                self.premise3.map(|premise| *premise),
+            ),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EConsNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub value: Value,
+    pub rule: String,
+    pub premises: Vec<RuleNode>,
+}
+impl EConsNode {
+    pub fn new(environment: Environment, expression: Expression) -> Result<EConsNode, String> {
+        let (split_position, _) = expression.get_split_position();
+        let (former, latter) = expression.get_splitted_expression(split_position);
+
+        let premise1 = RuleNode::new(environment.clone(), former)?;
+        let premise2 = RuleNode::new(environment.clone(), latter)?;
+        let (value, rule, premises) = match (premise1.get_val(), premise2.get_val()) {
+            (Value::Error, _) => (Value::Error, "E-ConsErrorL", vec![premise1]),
+            (_, Value::Error) => (Value::Error, "E-ConsErrorR", vec![premise2]),
+            (value1, value2) => (
+                Value::Cons(Box::new(value1), Box::new(value2)),
+                "E-Cons",
+                vec![premise1, premise2],
+            ),
+        };
+        Ok(EConsNode {
+            environment,
+            expression,
+            value,
+            rule: rule.to_string(),
+            premises,
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let mut premises = self.premises.into_iter();
+        writer.show_rule(
+            Some(self.environment),
+            self.expression.to_string(),
+            self.value.to_string(),
+            self.rule,
+            false,
+            Premises(premises.next(), premises.next(), None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EMatchNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub value: Value,
+    pub rule: String,
+    pub premise1: Box<RuleNode>,
+    pub premise2: Box<RuleNode>,
+    pub premise3: Box<RuleNode>,
+}
+impl EMatchNode {
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        mut term: MatchTerm,
+    ) -> Result<EMatchNode, String> {
+        let premise1 = RuleNode::new(environment.clone(), term.expression.clone())?;
+        let value = premise1.get_val();
+        if let Value::Error = value {
+            return Err(get_no_rule_message(&expression.to_string()));
+        }
+
+        let (pattern, clause_expression) = term.clauses.remove(0);
+        let (premise2, premise3, rule) = match get_bindings(&pattern, &value) {
+            Some(bindings) => {
+                let mut new_env = environment.clone();
+                new_env.stack.extend(bindings.stack);
+                let rule = if term.clauses.is_empty() {
+                    "E-MatchM1"
+                } else {
+                    "E-MatchM2"
+                };
+                (
+                    RuleNode::Match(MatchNode { pattern, value }),
+                    RuleNode::new(new_env, clause_expression)?,
+                    rule,
+                )
+            }
+            None => {
+                // no clause is left to try
+                if term.clauses.is_empty() {
+                    return Err(get_no_rule_message(&expression.to_string()));
+                }
+                // the remaining clauses are tried against the same expression
+                let terms: Vec<(String, Term)> = vec![("".to_string(), Term::Match(term))];
+                (
+                    RuleNode::NotMatch(NotMatchNode { pattern, value }),
+                    RuleNode::new(environment.clone(), Expression { terms })?,
+                    "E-MatchN",
+                )
+            }
+        };
+        Ok(EMatchNode {
+            environment,
+            expression,
+            value: premise3.get_val(),
+            rule: rule.to_string(),
+            premise1: Box::new(premise1),
+            premise2: Box::new(premise2),
+            premise3: Box::new(premise3),
+        })
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_rule(
+            Some(self.environment),
+            self.expression.to_string(),
+            self.value.to_string(),
+            self.rule,
+            false,
+            Premises(
+                Some(*self.premise1),
+                Some(*self.premise2),
+                Some(*self.premise3),
+            ),
+        )
+    }
+}
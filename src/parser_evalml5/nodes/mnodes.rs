@@ -0,0 +1,101 @@
+use super::super::super::parser_evalml3::pattern::Pattern;
+use super::super::super::parser_evalml4::eval::get_bindings;
+use super::super::super::parser_evalml4::value::Value;
+use super::super::nodes::RuleNode;
+use super::writer::{Premises, RuleWriter};
+use std::io::{self, Write};
+
+#[derive(Debug, Clone)]
+pub struct MatchNode {
+    pub pattern: Pattern,
+    pub value: Value,
+}
+impl MatchNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let bindings =
+            get_bindings(&self.pattern, &self.value).expect("the pattern matches the value");
+        let judgement_str = format!(
+            "{} matches {} when ({})",
+            self.pattern,
+            self.value,
+            bindings.to_string_for_bindings()
+        );
+        match (self.pattern, self.value) {
+            (Pattern::Var(_), _) => writer.show_judgement(
+                judgement_str,
+                "M-Var".to_string(),
+                Premises(None, None, None),
+            ),
+            (Pattern::Nil, _) => writer.show_judgement(
+                judgement_str,
+                "M-Nil".to_string(),
+                Premises(None, None, None),
+            ),
+            (Pattern::Wild, _) => writer.show_judgement(
+                judgement_str,
+                "M-Wild".to_string(),
+                Premises(None, None, None),
+            ),
+            (Pattern::Cons(head, tail), Value::Cons(head_value, tail_value)) => {
+                let premise1 = RuleNode::Match(MatchNode {
+                    pattern: *head,
+                    value: *head_value,
+                });
+                let premise2 = RuleNode::Match(MatchNode {
+                    pattern: *tail,
+                    value: *tail_value,
+                });
+                writer.show_judgement(
+                    judgement_str,
+                    "M-Cons".to_string(),
+                    Premises(Some(premise1), Some(premise2), None),
+                )
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NotMatchNode {
+    pub pattern: Pattern,
+    pub value: Value,
+}
+impl NotMatchNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let judgement_str = format!("{} doesn't match {}", self.pattern, self.value);
+        match (self.pattern, self.value) {
+            (Pattern::Cons(_, _), Value::Nil) => writer.show_judgement(
+                judgement_str,
+                "NM-ConsNil".to_string(),
+                Premises(None, None, None),
+            ),
+            (Pattern::Nil, Value::Cons(_, _)) => writer.show_judgement(
+                judgement_str,
+                "NM-NilCons".to_string(),
+                Premises(None, None, None),
+            ),
+            (Pattern::Cons(head, tail), Value::Cons(head_value, tail_value)) => {
+                // the head is checked first, so NM-ConsConsR is used only when the head matches
+                let (premise, rule_str) = match get_bindings(&head, &head_value) {
+                    None => (
+                        RuleNode::NotMatch(NotMatchNode {
+                            pattern: *head,
+                            value: *head_value,
+                        }),
+                        "NM-ConsConsL".to_string(),
+                    ),
+                    Some(_) => (
+                        RuleNode::NotMatch(NotMatchNode {
+                            pattern: *tail,
+                            value: *tail_value,
+                        }),
+                        "NM-ConsConsR".to_string(),
+                    ),
+                };
+                writer.show_judgement(judgement_str, rule_str, Premises(Some(premise), None, None))
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+mod eval;
+mod nodes;
+mod value;
+
+use super::chapter1::util::check_stated_result;
+use super::parser_evalml3::expression::Expression;
+use super::parser_evalml3::lexer::{Lexer, Token};
+use super::parser_evalml3::nodes::writer::Rule;
+use eval::Eval;
+use nodes::writer::RuleWriter;
+use nodes::RuleNode;
+use value::{Environment, Value};
+
+use std::io::Write;
+
+pub fn derive<W: Write>(judgement: &str, w: &mut W) {
+    let lexer = Lexer::new();
+    let mut tokens = lexer.lex(judgement.to_string());
+
+    let environment = Environment::new(&mut tokens, Value::consume);
+    tokens.pop(); // consume |-
+    let expression = Expression::new(&mut tokens);
+    if !tokens.consume_relation(&Token::Eval("evalto".to_string())) {
+        let _ = writeln!(
+            w,
+            "{}",
+            lexer.get_syntax_error(judgement, &tokens, "expects `evalto`")
+        );
+        return;
+    }
+
+    let value = match expression.clone().get_val(environment.clone()) {
+        Ok(value) => value.to_string(),
+        Err(message) => {
+            let _ = writeln!(w, "{}", message);
+            return;
+        }
+    };
+    if let Err(message) = check_stated_result(
+        &tokens.tokens,
+        &value,
+        |derivable| lexer.lex(derivable).tokens,
+        |count| lexer.get_tail(judgement, count),
+    ) {
+        let _ = writeln!(w, "{}", message);
+        return;
+    }
+    let derivation_tree = RuleNode::new(environment, expression);
+    let mut writer = RuleWriter::new(w, 0);
+    let _ = derivation_tree.show(&mut writer);
+    writer.write_nl();
+}
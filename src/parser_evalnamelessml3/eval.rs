@@ -0,0 +1,114 @@
+use super::super::chapter1::util::get_no_rule_message;
+use super::super::parser_evalml3::expression::Expression;
+use super::super::parser_evalml3::terms::{AppTerm, FunTerm, IfTerm, LetRecTerm, LetTerm, Term};
+use super::value::{Environment, Value};
+
+// evaluation of the nameless syntax, where #n looks up the n-th binding from the right.
+// an ill-typed expression or an index out of the environment is one no rule applies to
+pub trait Eval {
+    fn get_val(self, environment: Environment) -> Result<Value, String>;
+
+    // the derivation is built only after the whole judgement evaluated, so its parts do too
+    fn evaluate(self, environment: Environment) -> Value
+    where
+        Self: Sized,
+    {
+        self.get_val(environment)
+            .expect("expects an evaluated judgement")
+    }
+}
+
+impl Eval for Expression {
+    fn get_val(mut self, environment: Environment) -> Result<Value, String> {
+        if self.terms.len() == 1 {
+            let (_, term) = self.terms.pop().expect("");
+            term.get_val(environment)
+        } else {
+            let (split_position, operator) = self.get_split_position();
+            let (former, latter) = self.get_splitted_expression(split_position);
+            let former_val = former.get_val(environment.clone())?;
+            let latter_val = latter.get_val(environment)?;
+            match former_val.calc(&operator, latter_val) {
+                Some(value) => Ok(value),
+                None => Err(get_no_rule_message(&self.to_string())),
+            }
+        }
+    }
+}
+
+impl Eval for Term {
+    fn get_val(self, environment: Environment) -> Result<Value, String> {
+        match self {
+            Term::Val(num) => Ok(Value::Num(num)),
+            Term::Bool(b) => Ok(Value::Bool(b)),
+            Term::Index(index) => match environment.find_val_by_index(index) {
+                Some(value) => Ok(value),
+                None => Err(get_no_rule_message(&self.to_string())),
+            },
+            Term::Paren(expression) => expression.get_val(environment),
+            Term::If(if_term) => if_term.get_val(environment),
+            Term::Let(let_term) => let_term.get_val(environment),
+            Term::LetRec(let_rec_term) => let_rec_term.get_val(environment),
+            Term::Fun(fun_term) => fun_term.get_val(environment),
+            Term::App(app_term) => app_term.get_val(environment),
+            _ => Err(get_no_rule_message(&self.to_string())),
+        }
+    }
+}
+
+impl Eval for IfTerm {
+    fn get_val(self, environment: Environment) -> Result<Value, String> {
+        let expression_str = self.to_string();
+        match self.condition_expression.get_val(environment.clone())? {
+            Value::Bool(b) => match b.as_ref() {
+                "true" => self.then_expression.get_val(environment),
+                _ => self.else_expression.get_val(environment),
+            },
+            _ => Err(get_no_rule_message(&expression_str)),
+        }
+    }
+}
+
+impl Eval for LetTerm {
+    fn get_val(self, environment: Environment) -> Result<Value, String> {
+        let mut new_env = environment.clone();
+        let new_val = self.let_expression.expression.get_val(environment)?;
+        new_env.set_val(self.let_expression.identifier, new_val);
+        self.in_expression.get_val(new_env)
+    }
+}
+
+impl Eval for LetRecTerm {
+    fn get_val(self, environment: Environment) -> Result<Value, String> {
+        let mut new_env = environment.clone();
+        new_env.set_val(self.identifier, Value::RecFun(self.fun_term, environment));
+        self.in_expression.get_val(new_env)
+    }
+}
+
+impl Eval for FunTerm {
+    fn get_val(self, environment: Environment) -> Result<Value, String> {
+        Ok(Value::Fun(self, environment))
+    }
+}
+
+impl Eval for AppTerm {
+    fn get_val(self, environment: Environment) -> Result<Value, String> {
+        let expression_str = self.to_string();
+        let value = self.function.get_val(environment.clone())?;
+        let argument_value = self.argument.get_val(environment)?;
+        match value {
+            Value::Fun(fun_term, mut clojure_env) => {
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                fun_term.function_body.get_val(clojure_env)
+            }
+            Value::RecFun(fun_term, mut clojure_env) => {
+                let rec_fun = Value::RecFun(fun_term.clone(), clojure_env.clone());
+                clojure_env.set_val(".".to_string(), rec_fun);
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                fun_term.function_body.get_val(clojure_env)
+            }
+            _ => Err(get_no_rule_message(&expression_str)),
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use super::super::super::parser_evalml3::expression::Expression;
+use super::super::super::parser_evalml3::nodes::bnodes::BOpNode;
+use super::super::super::parser_evalml3::terms::{
+    AppTerm, FunTerm, IfTerm, LetRecTerm, LetTerm, Term,
+};
+use super::super::eval::Eval;
+use super::super::nodes::RuleNode;
+use super::super::value::{Environment, Value};
+use super::writer::{Premises, RuleWriter};
+
+use std::io::{self, Write};
+
+#[derive(Debug, Clone)]
+pub struct EVarNode {
+    pub environment: Environment,
+    pub expression: Expression,
+}
+impl EVarNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let mut terms = self.expression.terms.clone();
+        let (_, term) = terms.pop().expect("");
+        let index = term.get_index();
+        writer.show_rule(
+            Some(self.environment.clone()),
+            format!("#{}", index),
+            self.environment
+                .find_val_by_index(index)
+                .expect("expects an index in the environment")
+                .to_string(),
+            "E-Var".to_string(),
+            false,
+            Premises(None, None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EIfNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub term: IfTerm,
+}
+impl EIfNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let condition_expression = self.term.condition_expression;
+        let then_expression = self.term.then_expression;
+        let else_expression = self.term.else_expression;
+
+        let condition_value = condition_expression
+            .clone()
+            .evaluate(self.environment.clone());
+        let condition_premise = RuleNode::new(self.environment.clone(), condition_expression);
+
+        let (branch_expression, rule_str) = match condition_value {
+            Value::Bool(ref b) if b == "true" => (then_expression, "E-IfT".to_string()),
+            Value::Bool(_) => (else_expression, "E-IfF".to_string()),
+            _ => panic!("unexpected"),
+        };
+        let branch_value = branch_expression.clone().evaluate(self.environment.clone());
+        let branch_premise = RuleNode::new(self.environment.clone(), branch_expression);
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.expression.to_string(),
+            branch_value.to_string(),
+            rule_str,
+            false,
+            Premises(Some(condition_premise), Some(branch_premise), None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EBNode {
+    pub environment: Environment,
+    pub expression: Expression,
+}
+impl EBNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let (split_position, operator) = self.expression.get_split_position();
+        let (former, latter) = self.expression.get_splitted_expression(split_position);
+
+        let value1 = former.clone().evaluate(self.environment.clone());
+        let value2 = latter.clone().evaluate(self.environment.clone());
+        let premise1 = RuleNode::new(self.environment.clone(), former);
+        let premise2 = RuleNode::new(self.environment.clone(), latter);
+
+        let (i1, i2) = match (value1, value2) {
+            (Value::Num(i1), Value::Num(i2)) => (i1, i2),
+            _ => panic!("unexpected"),
+        };
+        let (rule_str, val_str) = match operator.as_ref() {
+            "+" => ("E-Plus", (i1 + i2).to_string()),
+            "*" => ("E-Times", (i1 * i2).to_string()),
+            "-" => ("E-Minus", (i1 - i2).to_string()),
+            "<" => ("E-Lt", (i1 < i2).to_string()),
+            _ => panic!("todo"),
+        };
+        let premise = RuleNode::BOp(BOpNode {
+            i1,
+            i2,
+            op: operator.clone(),
+        });
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.expression.to_string(),
+            val_str,
+            rule_str.to_string(),
+            false,
+            Premises(Some(premise1), Some(premise2), Some(premise)),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EValNode {
+    pub environment: Environment,
+    pub expression: Expression,
+}
+impl EValNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let mut terms = self.expression.clone().terms;
+        let (_, term) = terms.pop().expect("");
+        let rule_str = match term {
+            Term::Val(_) => "E-Int".to_string(),
+            Term::Bool(_) => "E-Bool".to_string(),
+            _ => panic!("unexpected"),
+        };
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.expression.to_string(),
+            self.expression.evaluate(self.environment).to_string(),
+            rule_str,
+            false,
+            Premises(None, None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ELetNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub term: LetTerm,
+}
+impl ELetNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let let_expression = self.term.clone().let_expression;
+        let mut new_env = self.environment.clone();
+        new_env.set_val(
+            let_expression.identifier,
+            let_expression
+                .expression
+                .clone()
+                .evaluate(self.environment.clone()),
+        );
+        let let_premise = RuleNode::new(self.environment.clone(), let_expression.expression);
+        let in_premise = RuleNode::new(new_env, self.term.clone().in_expression);
+
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.expression.to_string(),
+            self.term.evaluate(self.environment).to_string(),
+            "E-Let".to_string(),
+            false,
+            Premises(Some(let_premise), Some(in_premise), None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ELetRecNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub term: LetRecTerm,
+}
+impl ELetRecNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let let_rec_term = self.term.clone();
+        let mut new_env = self.environment.clone();
+        new_env.set_val(
+            let_rec_term.identifier,
+            Value::RecFun(let_rec_term.fun_term, self.environment.clone()),
+        );
+        let in_premise = RuleNode::new(new_env, let_rec_term.in_expression);
+
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.expression.to_string(),
+            self.term.evaluate(self.environment).to_string(),
+            "E-LetRec".to_string(),
+            false,
+            Premises(Some(in_premise), None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EFunNode {
+    pub environment: Environment,
+    pub term: FunTerm,
+}
+impl EFunNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.term.to_string(),
+            self.term.evaluate(self.environment).to_string(),
+            "E-Fun".to_string(),
+            false,
+            Premises(None, None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EAppNode {
+    pub environment: Environment,
+    pub term: AppTerm,
+}
+impl EAppNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let terms: Vec<(String, Term)> = vec![("".to_string(), *self.term.clone().function)];
+        let function_expression = Expression { terms };
+        let function_value = function_expression
+            .clone()
+            .evaluate(self.environment.clone());
+        let premise1 = RuleNode::new(self.environment.clone(), function_expression);
+
+        let argument_value = self
+            .term
+            .clone()
+            .argument
+            .evaluate(self.environment.clone());
+        let premise2 = RuleNode::new(self.environment.clone(), self.term.clone().argument);
+        let (premise3, rule_str) = match function_value {
+            Value::Fun(fun_term, mut clojure_env) => {
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                (
+                    RuleNode::new(clojure_env, fun_term.function_body),
+                    "E-App".to_string(),
+                )
+            }
+            Value::RecFun(fun_term, mut clojure_env) => {
+                let rec_fun = Value::RecFun(fun_term.clone(), clojure_env.clone());
+                clojure_env.set_val(".".to_string(), rec_fun);
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                (
+                    RuleNode::new(clojure_env, fun_term.function_body),
+                    "E-AppRec".to_string(),
+                )
+            }
+            _ => panic!("unexpected"),
+        };
+        writer.show_rule(
+            Some(self.environment.clone()),
+            self.term.to_string(),
+            self.term.clone().evaluate(self.environment).to_string(),
+            rule_str,
+            false,
+            Premises(Some(premise1), Some(premise2), Some(premise3)),
+        )
+    }
+}
@@ -0,0 +1,59 @@
+use super::super::parser_evalml3::environment;
+use super::super::parser_evalml3::lexer::{Token, Tokens};
+use super::super::parser_evalml3::terms::FunTerm;
+
+use std::fmt;
+
+// the values are bound to `.`, so the environment prints them without names
+pub type Environment = environment::Environment<Value>;
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Num(i32),
+    Bool(String),
+    Fun(FunTerm, Environment),
+    RecFun(FunTerm, Environment),
+}
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Num(num) => write!(f, "{}", num),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Fun(term, environment) => {
+                write!(f, "({})[{}]", environment.to_string_for_clojure(), term)
+            }
+            Value::RecFun(term, environment) => write!(
+                f,
+                "({})[rec . = {}]",
+                environment.to_string_for_clojure(),
+                term
+            ),
+        }
+    }
+}
+impl Value {
+    pub fn consume(tokens: &mut Tokens) -> Value {
+        match tokens.peek() {
+            Some(token) => match token {
+                Token::Int(_) => Value::Num(tokens.consume_num()),
+                Token::Bool(_) => Value::Bool(tokens.consume_bool()),
+                _ => panic!("unexpected"),
+            },
+            None => panic!("unexpected"),
+        }
+    }
+    // the result of a binary operation, which only integers have
+    pub fn calc(self, operator: &str, other: Value) -> Option<Value> {
+        let (i1, i2) = match (self, other) {
+            (Value::Num(i1), Value::Num(i2)) => (i1, i2),
+            _ => return None,
+        };
+        match operator {
+            "+" => Some(Value::Num(i1 + i2)),
+            "*" => Some(Value::Num(i1 * i2)),
+            "-" => Some(Value::Num(i1 - i2)),
+            "<" => Some(Value::Bool((i1 < i2).to_string())),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use super::super::chapter1::util::get_no_rule_message;
+use super::super::parser_evalml3::expression::Expression;
+use super::super::parser_evalml3::terms::{AppTerm, FunTerm, IfTerm, LetRecTerm, LetTerm, Term};
+use super::store::Store;
+use super::value::{Environment, Value};
+
+// evaluation of the syntax shared with EvalML3, which passes the store along. EvalRefML3 has
+// no error rules, so an ill-typed expression is reported as one no rule applies to
+pub trait Eval {
+    fn get_val(self, environment: Environment, store: Store) -> Result<(Value, Store), String>;
+
+    // the derivation is built only after the whole judgement evaluated, so its parts do too
+    fn evaluate(self, environment: Environment, store: Store) -> (Value, Store)
+    where
+        Self: Sized,
+    {
+        self.get_val(environment, store)
+            .expect("expects an evaluated judgement")
+    }
+}
+
+impl Eval for Expression {
+    fn get_val(mut self, environment: Environment, store: Store) -> Result<(Value, Store), String> {
+        if self.terms.len() == 1 {
+            let (_, term) = self.terms.pop().expect("");
+            term.get_val(environment, store)
+        } else {
+            let (split_position, operator) = self.get_split_position();
+            let (former, latter) = self.get_splitted_expression(split_position);
+            // the store produced by the left operand is passed to the right one
+            let (former_val, store) = former.get_val(environment.clone(), store)?;
+            let (latter_val, mut store) = latter.get_val(environment, store)?;
+            match (operator.as_ref(), former_val) {
+                (":=", Value::Loc(location)) => {
+                    store.set_val(&location, latter_val.clone());
+                    Ok((latter_val, store))
+                }
+                (_, former_val) => match former_val.calc(&operator, latter_val) {
+                    Some(value) => Ok((value, store)),
+                    None => Err(get_no_rule_message(&self.to_string())),
+                },
+            }
+        }
+    }
+}
+
+impl Eval for Term {
+    fn get_val(self, environment: Environment, store: Store) -> Result<(Value, Store), String> {
+        match self {
+            Term::Val(num) => Ok((Value::Num(num), store)),
+            Term::Bool(b) => Ok((Value::Bool(b), store)),
+            Term::Var(ref identifier) => match environment.find_val(identifier) {
+                Some(value) => Ok((value, store)),
+                None => Err(get_no_rule_message(&self.to_string())),
+            },
+            Term::Paren(expression) => expression.get_val(environment, store),
+            Term::If(if_term) => if_term.get_val(environment, store),
+            Term::Let(let_term) => let_term.get_val(environment, store),
+            Term::LetRec(let_rec_term) => let_rec_term.get_val(environment, store),
+            Term::Fun(fun_term) => fun_term.get_val(environment, store),
+            Term::App(app_term) => app_term.get_val(environment, store),
+            Term::Ref(expression) => {
+                let (value, mut store) = expression.get_val(environment, store)?;
+                let location = store.alloc(value);
+                Ok((Value::Loc(location), store))
+            }
+            Term::Deref(ref expression) => match expression.clone().get_val(environment, store)? {
+                (Value::Loc(location), store) => Ok((store.get_val(&location), store)),
+                _ => Err(get_no_rule_message(&self.to_string())),
+            },
+            _ => Err(get_no_rule_message(&self.to_string())),
+        }
+    }
+}
+
+impl Eval for IfTerm {
+    fn get_val(self, environment: Environment, store: Store) -> Result<(Value, Store), String> {
+        let expression_str = self.to_string();
+        match self
+            .condition_expression
+            .get_val(environment.clone(), store)?
+        {
+            (Value::Bool(b), store) => match b.as_ref() {
+                "true" => self.then_expression.get_val(environment, store),
+                _ => self.else_expression.get_val(environment, store),
+            },
+            _ => Err(get_no_rule_message(&expression_str)),
+        }
+    }
+}
+
+impl Eval for LetTerm {
+    fn get_val(self, environment: Environment, store: Store) -> Result<(Value, Store), String> {
+        let mut new_env = environment.clone();
+        let (new_val, store) = self.let_expression.expression.get_val(environment, store)?;
+        new_env.set_val(self.let_expression.identifier, new_val);
+        self.in_expression.get_val(new_env, store)
+    }
+}
+
+impl Eval for LetRecTerm {
+    fn get_val(self, environment: Environment, store: Store) -> Result<(Value, Store), String> {
+        let mut new_env = environment.clone();
+        let new_val = Value::RecFun(self.identifier.clone(), self.fun_term, environment);
+        new_env.set_val(self.identifier, new_val);
+        self.in_expression.get_val(new_env, store)
+    }
+}
+
+impl Eval for FunTerm {
+    fn get_val(self, environment: Environment, store: Store) -> Result<(Value, Store), String> {
+        Ok((Value::Fun(self, environment), store))
+    }
+}
+
+impl Eval for AppTerm {
+    fn get_val(self, environment: Environment, store: Store) -> Result<(Value, Store), String> {
+        let expression_str = self.to_string();
+        let (value, store) = self.function.get_val(environment.clone(), store)?;
+        let (argument_value, store) = self.argument.get_val(environment, store)?;
+        match value {
+            Value::Fun(fun_term, mut clojure_env) => {
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                fun_term.function_body.get_val(clojure_env, store)
+            }
+            Value::RecFun(identifier, fun_term, mut clojure_env) => {
+                let rec_fun =
+                    Value::RecFun(identifier.clone(), fun_term.clone(), clojure_env.clone());
+                clojure_env.set_val(identifier, rec_fun);
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                fun_term.function_body.get_val(clojure_env, store)
+            }
+            _ => Err(get_no_rule_message(&expression_str)),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use super::super::parser_evalml3::expression::Expression;
+use super::super::parser_evalml3::nodes::bnodes::BOpNode;
+use super::super::parser_evalml3::nodes::writer::Rule;
+use super::super::parser_evalml3::terms::Term;
+use super::nodes::enodes::{
+    EAppNode, EAssignNode, EBNode, EDerefNode, EFunNode, EIfNode, ELetNode, ELetRecNode, ERefNode,
+    EValNode, EVarNode,
+};
+use super::nodes::writer::RuleWriter;
+use super::store::Store;
+use super::value::Environment;
+use std::io::{self, Write};
+
+pub mod enodes;
+pub mod writer;
+
+#[derive(Debug, Clone)]
+pub enum RuleNode {
+    EVar(EVarNode),
+    EVal(EValNode),
+    EIf(EIfNode),
+    ELet(ELetNode),
+    ELetRec(ELetRecNode),
+    EBNode(EBNode),
+    EFun(EFunNode),
+    EApp(EAppNode),
+    ERef(ERefNode),
+    EDeref(EDerefNode),
+    EAssign(EAssignNode),
+    BOp(BOpNode),
+}
+
+impl RuleNode {
+    pub fn new(environment: Environment, store: Store, mut expression: Expression) -> RuleNode {
+        let len: usize = expression.terms.len();
+        let original_expression = expression.clone();
+        if len == 1 {
+            let term = expression.get_first_term();
+            match term {
+                Term::If(if_node) => RuleNode::EIf(EIfNode {
+                    environment,
+                    store,
+                    expression: original_expression,
+                    term: if_node,
+                }),
+                Term::Var(_) => RuleNode::EVar(EVarNode {
+                    environment,
+                    store,
+                    expression: original_expression,
+                }),
+                Term::Paren(expression) => RuleNode::new(environment, store, expression),
+                Term::Val(_) | Term::Bool(_) => RuleNode::EVal(EValNode {
+                    environment,
+                    store,
+                    expression: original_expression,
+                }),
+                Term::Let(let_node) => RuleNode::ELet(ELetNode {
+                    environment,
+                    store,
+                    expression: original_expression,
+                    term: let_node,
+                }),
+                Term::LetRec(let_rec_node) => RuleNode::ELetRec(ELetRecNode {
+                    environment,
+                    store,
+                    expression: original_expression,
+                    term: let_rec_node,
+                }),
+                Term::Fun(fun_node) => RuleNode::EFun(EFunNode {
+                    environment,
+                    store,
+                    term: fun_node,
+                }),
+                Term::App(app_node) => RuleNode::EApp(EAppNode {
+                    environment,
+                    store,
+                    term: app_node,
+                }),
+                Term::Ref(argument) => RuleNode::ERef(ERefNode {
+                    environment,
+                    store,
+                    expression: original_expression,
+                    argument,
+                }),
+                Term::Deref(argument) => RuleNode::EDeref(EDerefNode {
+                    environment,
+                    store,
+                    expression: original_expression,
+                    argument,
+                }),
+                _ => panic!("unexpected"),
+            }
+        } else if expression.get_split_position().1 == ":=" {
+            RuleNode::EAssign(EAssignNode {
+                environment,
+                store,
+                expression: original_expression,
+            })
+        } else {
+            RuleNode::EBNode(EBNode {
+                environment,
+                store,
+                expression: original_expression,
+            })
+        }
+    }
+}
+
+impl Rule for RuleNode {
+    type Context = ();
+
+    fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        match self {
+            RuleNode::EVar(node) => node.show(writer),
+            RuleNode::EVal(node) => node.show(writer),
+            RuleNode::EIf(node) => node.show(writer),
+            RuleNode::ELet(node) => node.show(writer),
+            RuleNode::ELetRec(node) => node.show(writer),
+            RuleNode::EBNode(node) => node.show(writer),
+            RuleNode::EFun(node) => node.show(writer),
+            RuleNode::EApp(node) => node.show(writer),
+            RuleNode::ERef(node) => node.show(writer),
+            RuleNode::EDeref(node) => node.show(writer),
+            RuleNode::EAssign(node) => node.show(writer),
+            RuleNode::BOp(node) => node.show(writer),
+        }
+    }
+}
@@ -0,0 +1,393 @@
+use super::super::super::parser_evalml3::expression::Expression;
+use super::super::super::parser_evalml3::nodes::bnodes::BOpNode;
+use super::super::super::parser_evalml3::terms::{
+    AppTerm, FunTerm, IfTerm, LetRecTerm, LetTerm, Term,
+};
+use super::super::eval::Eval;
+use super::super::nodes::RuleNode;
+use super::super::store::Store;
+use super::super::value::{Environment, Value};
+use super::writer::{Premises, RuleWriter};
+
+use std::io::{self, Write};
+
+#[derive(Debug, Clone)]
+pub struct EVarNode {
+    pub environment: Environment,
+    pub store: Store,
+    pub expression: Expression,
+}
+impl EVarNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let mut terms = self.expression.terms.clone();
+        let (_, term) = terms.pop().expect("");
+        let identifier = term.get_identifier();
+        writer.show_store_rule(
+            self.environment.clone(),
+            (self.store.clone(), self.store),
+            identifier.clone(),
+            self.environment.get_val(&identifier).to_string(),
+            "E-Var".to_string(),
+            Premises(None, None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EIfNode {
+    pub environment: Environment,
+    pub store: Store,
+    pub expression: Expression,
+    pub term: IfTerm,
+}
+impl EIfNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let condition_expression = self.term.condition_expression;
+        let then_expression = self.term.then_expression;
+        let else_expression = self.term.else_expression;
+
+        let (condition_value, condition_store) = condition_expression
+            .clone()
+            .evaluate(self.environment.clone(), self.store.clone());
+        let condition_premise = RuleNode::new(
+            self.environment.clone(),
+            self.store.clone(),
+            condition_expression,
+        );
+
+        let (branch_expression, rule_str) = match condition_value {
+            Value::Bool(ref b) if b == "true" => (then_expression, "E-IfT".to_string()),
+            Value::Bool(_) => (else_expression, "E-IfF".to_string()),
+            _ => panic!("unexpected"),
+        };
+        let (branch_value, branch_store) = branch_expression
+            .clone()
+            .evaluate(self.environment.clone(), condition_store.clone());
+        let branch_premise =
+            RuleNode::new(self.environment.clone(), condition_store, branch_expression);
+        writer.show_store_rule(
+            self.environment.clone(),
+            (self.store, branch_store),
+            self.expression.clone().to_string(),
+            branch_value.to_string(),
+            rule_str,
+            Premises(Some(condition_premise), Some(branch_premise), None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EBNode {
+    pub environment: Environment,
+    pub store: Store,
+    pub expression: Expression,
+}
+impl EBNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let (split_position, operator) = self.expression.get_split_position();
+        let (former, latter) = self.expression.get_splitted_expression(split_position);
+
+        let (value1, store1) = former
+            .clone()
+            .evaluate(self.environment.clone(), self.store.clone());
+        let (value2, store2) = latter
+            .clone()
+            .evaluate(self.environment.clone(), store1.clone());
+        let premise1 = RuleNode::new(self.environment.clone(), self.store.clone(), former);
+        let premise2 = RuleNode::new(self.environment.clone(), store1, latter);
+
+        let (i1, i2) = match (value1, value2) {
+            (Value::Num(i1), Value::Num(i2)) => (i1, i2),
+            _ => panic!("unexpected"),
+        };
+        let (rule_str, val_str) = match operator.as_ref() {
+            "+" => ("E-Plus", (i1 + i2).to_string()),
+            "*" => ("E-Times", (i1 * i2).to_string()),
+            "-" => ("E-Minus", (i1 - i2).to_string()),
+            "<" => ("E-Lt", (i1 < i2).to_string()),
+            _ => panic!("todo"),
+        };
+        let premise = RuleNode::BOp(BOpNode {
+            i1,
+            i2,
+            op: operator.clone(),
+        });
+        writer.show_store_rule(
+            self.environment.clone(),
+            (self.store, store2),
+            self.expression.to_string(),
+            val_str,
+            rule_str.to_string(),
+            Premises(Some(premise1), Some(premise2), Some(premise)),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EValNode {
+    pub environment: Environment,
+    pub store: Store,
+    pub expression: Expression,
+}
+impl EValNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let mut terms = self.expression.clone().terms;
+        let (_, term) = terms.pop().expect("");
+        let rule_str = match term {
+            Term::Val(_) => "E-Int".to_string(),
+            Term::Bool(_) => "E-Bool".to_string(),
+            _ => panic!("unexpected"),
+        };
+        let (value, _) = self
+            .expression
+            .clone()
+            .evaluate(self.environment.clone(), self.store.clone());
+        writer.show_store_rule(
+            self.environment.clone(),
+            (self.store.clone(), self.store),
+            self.expression.to_string(),
+            value.to_string(),
+            rule_str,
+            Premises(None, None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ELetNode {
+    pub environment: Environment,
+    pub store: Store,
+    pub expression: Expression,
+    pub term: LetTerm,
+}
+impl ELetNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let in_expression = self.term.clone().in_expression;
+        let let_expression = self.term.clone().let_expression;
+        let mut new_env = self.environment.clone();
+        let (val, let_store) = let_expression
+            .expression
+            .clone()
+            .evaluate(self.environment.clone(), self.store.clone());
+        let let_premise = RuleNode::new(
+            self.environment.clone(),
+            self.store.clone(),
+            let_expression.expression,
+        );
+        new_env.set_val(let_expression.identifier, val);
+        let in_premise = RuleNode::new(new_env, let_store, in_expression);
+        let (val, store) = self
+            .term
+            .evaluate(self.environment.clone(), self.store.clone());
+
+        writer.show_store_rule(
+            self.environment.clone(),
+            (self.store, store),
+            self.expression.clone().to_string(),
+            val.to_string(),
+            "E-Let".to_string(),
+            Premises(Some(let_premise), Some(in_premise), None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ELetRecNode {
+    pub environment: Environment,
+    pub store: Store,
+    pub expression: Expression,
+    pub term: LetRecTerm,
+}
+impl ELetRecNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let let_rec_term = self.term.clone();
+        let mut new_env = self.environment.clone();
+        let rec_fun = Value::RecFun(
+            let_rec_term.identifier.clone(),
+            let_rec_term.fun_term,
+            self.environment.clone(),
+        );
+        new_env.set_val(let_rec_term.identifier, rec_fun);
+        let in_premise = RuleNode::new(new_env, self.store.clone(), let_rec_term.in_expression);
+        let (val, store) = self
+            .term
+            .evaluate(self.environment.clone(), self.store.clone());
+
+        writer.show_store_rule(
+            self.environment.clone(),
+            (self.store, store),
+            self.expression.clone().to_string(),
+            val.to_string(),
+            "E-LetRec".to_string(),
+            Premises(Some(in_premise), None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EFunNode {
+    pub environment: Environment,
+    pub store: Store,
+    pub term: FunTerm,
+}
+impl EFunNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_store_rule(
+            self.environment.clone(),
+            (self.store.clone(), self.store.clone()),
+            self.term.clone().to_string(),
+            self.term
+                .evaluate(self.environment, self.store)
+                .0
+                .to_string(),
+            "E-Fun".to_string(),
+            Premises(None, None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EAppNode {
+    pub environment: Environment,
+    pub store: Store,
+    pub term: AppTerm,
+}
+impl EAppNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let terms: Vec<(String, Term)> = vec![("".to_string(), *self.term.clone().function)];
+        let function_expression = Expression { terms };
+        let (function_value, function_store) = function_expression
+            .clone()
+            .evaluate(self.environment.clone(), self.store.clone());
+        let premise1 = RuleNode::new(
+            self.environment.clone(),
+            self.store.clone(),
+            function_expression,
+        );
+
+        let (argument_value, argument_store) = self
+            .term
+            .clone()
+            .argument
+            .evaluate(self.environment.clone(), function_store.clone());
+        let premise2 = RuleNode::new(
+            self.environment.clone(),
+            function_store,
+            self.term.clone().argument,
+        );
+        let (premise3, rule_str) = match function_value {
+            Value::Fun(fun_term, mut clojure_env) => {
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                (
+                    RuleNode::new(clojure_env, argument_store, fun_term.function_body),
+                    "E-App".to_string(),
+                )
+            }
+            Value::RecFun(identifier, fun_term, mut clojure_env) => {
+                let rec_fun =
+                    Value::RecFun(identifier.clone(), fun_term.clone(), clojure_env.clone());
+                clojure_env.set_val(identifier, rec_fun);
+                clojure_env.set_val(fun_term.parameter, argument_value);
+                (
+                    RuleNode::new(clojure_env, argument_store, fun_term.function_body),
+                    "E-AppRec".to_string(),
+                )
+            }
+            _ => panic!("unexpected"),
+        };
+        let (val, store) = self
+            .term
+            .clone()
+            .evaluate(self.environment.clone(), self.store.clone());
+        writer.show_store_rule(
+            self.environment.clone(),
+            (self.store, store),
+            self.term.clone().to_string(),
+            val.to_string(),
+            rule_str,
+            Premises(Some(premise1), Some(premise2), Some(premise3)),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ERefNode {
+    pub environment: Environment,
+    pub store: Store,
+    pub expression: Expression,
+    pub argument: Expression,
+}
+impl ERefNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let premise = RuleNode::new(self.environment.clone(), self.store.clone(), self.argument);
+        let (val, store) = self
+            .expression
+            .clone()
+            .evaluate(self.environment.clone(), self.store.clone());
+        writer.show_store_rule(
+            self.environment.clone(),
+            (self.store, store),
+            self.expression.to_string(),
+            val.to_string(),
+            "E-Ref".to_string(),
+            Premises(Some(premise), None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EDerefNode {
+    pub environment: Environment,
+    pub store: Store,
+    pub expression: Expression,
+    pub argument: Expression,
+}
+impl EDerefNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let premise = RuleNode::new(self.environment.clone(), self.store.clone(), self.argument);
+        let (val, store) = self
+            .expression
+            .clone()
+            .evaluate(self.environment.clone(), self.store.clone());
+        writer.show_store_rule(
+            self.environment.clone(),
+            (self.store, store),
+            self.expression.to_string(),
+            val.to_string(),
+            "E-Deref".to_string(),
+            Premises(Some(premise), None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EAssignNode {
+    pub environment: Environment,
+    pub store: Store,
+    pub expression: Expression,
+}
+impl EAssignNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let (split_position, _) = self.expression.get_split_position();
+        let (former, latter) = self.expression.get_splitted_expression(split_position);
+
+        let (_, location_store) = former
+            .clone()
+            .evaluate(self.environment.clone(), self.store.clone());
+        let premise1 = RuleNode::new(self.environment.clone(), self.store.clone(), former);
+        let premise2 = RuleNode::new(self.environment.clone(), location_store, latter);
+
+        let (val, store) = self
+            .expression
+            .clone()
+            .evaluate(self.environment.clone(), self.store.clone());
+        writer.show_store_rule(
+            self.environment.clone(),
+            (self.store, store),
+            self.expression.to_string(),
+            val.to_string(),
+            "E-Assign".to_string(),
+            Premises(Some(premise1), Some(premise2), None),
+        )
+    }
+}
@@ -0,0 +1,39 @@
+use super::super::super::parser_evalml3::nodes::writer;
+pub use super::super::super::parser_evalml3::nodes::writer::Premises;
+use super::super::store::Store;
+use super::super::value::Environment;
+use super::RuleNode;
+
+use std::io::{self, Write};
+
+pub type RuleWriter<W> = writer::RuleWriter<W, RuleNode>;
+
+impl<W: Write> RuleWriter<W> {
+    pub fn show_store_rule(
+        &mut self,
+        environment: Environment,
+        stores: (Store, Store),
+        expression_str: String,
+        evalto_str: String,
+        rule_str: String,
+        premises: Premises<RuleNode>,
+    ) -> io::Result<()> {
+        // an empty store is omitted: S1 / E |- e evalto v / S2
+        let (store, evalto_store) = stores;
+        let store_str = if store.cells.is_empty() {
+            "".to_string()
+        } else {
+            format!("{} / ", store)
+        };
+        let evalto_store_str = if evalto_store.cells.is_empty() {
+            "".to_string()
+        } else {
+            format!(" / {}", evalto_store)
+        };
+        let judgement_str = format!(
+            "{}{}{} evalto {}{}",
+            store_str, environment, expression_str, evalto_str, evalto_store_str
+        );
+        self.show_judgement(judgement_str, rule_str, premises)
+    }
+}
@@ -0,0 +1,95 @@
+use super::super::parser_evalml3::lexer::{Token, Tokens};
+use super::value::Value;
+
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct Store {
+    pub cells: Vec<(String, Value)>,
+    // the names given to new locations, taken from the store after `evalto`
+    pub names: Vec<String>,
+}
+impl Store {
+    pub fn new(tokens: &mut Tokens) -> Store {
+        let mut cells: Vec<(String, Value)> = Vec::new();
+
+        if let Some(Token::Loc(_)) = tokens.peek() {
+            loop {
+                let location = tokens.consume_loc();
+                tokens.pop(); // consume =
+                let val = Value::consume(tokens);
+                cells.push((location, val));
+                match tokens.pop().expect("") {
+                    Token::COMMA => (),
+                    Token::SLASH => break,
+                    _ => panic!("unexpected"),
+                }
+            }
+        }
+        Store {
+            cells,
+            names: Vec::new(),
+        }
+    }
+
+    // collects the locations defined in the store after `evalto`, in order
+    pub fn set_names(&mut self, tokens: &Tokens) {
+        let mut iter = tokens
+            .tokens
+            .iter()
+            .skip_while(|token| **token != Token::SLASH)
+            .peekable();
+        while let Some(token) = iter.next() {
+            if let (Token::Loc(location), Some(Token::EQ)) = (token, iter.peek()) {
+                self.names.push(location.clone());
+            }
+        }
+    }
+
+    // allocates a fresh location holding the value, named after the judgement if possible
+    // and otherwise @l, @m, ..., @z, @l1, @l2, ...
+    pub fn alloc(&mut self, value: Value) -> String {
+        let default_names = (b'l'..=b'z')
+            .map(|c| format!("@{}", c as char))
+            .chain((1..).map(|i| format!("@l{}", i)));
+        let location = self
+            .names
+            .iter()
+            .cloned()
+            .chain(default_names)
+            .find(|name| self.cells.iter().all(|(loc, _)| loc != name))
+            .expect("a fresh location");
+        self.cells.push((location.clone(), value));
+        location
+    }
+
+    pub fn get_val(&self, location: &String) -> Value {
+        for (loc, val) in self.cells.iter() {
+            if loc == location {
+                return val.clone();
+            }
+        }
+        panic!("location \'{}\' is not allocated", location);
+    }
+
+    pub fn set_val(&mut self, location: &String, value: Value) {
+        for (loc, val) in self.cells.iter_mut() {
+            if loc == location {
+                *val = value;
+                return;
+            }
+        }
+        panic!("location \'{}\' is not allocated", location);
+    }
+}
+impl fmt::Display for Store {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (loc, val)) in self.cells.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} = {}", loc, val)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+mod nameless;
+mod nodes;
+mod variables;
+
+use super::chapter1::util::check_stated_result;
+use super::parser_evalml3::expression::Expression;
+use super::parser_evalml3::lexer::{Lexer, Token};
+use nameless::NamelessExpression;
+use nodes::writer::RuleWriter;
+use nodes::RuleNode;
+use variables::Variables;
+
+use std::io::Write;
+
+pub fn derive<W: Write>(judgement: &str, w: &mut W) {
+    let lexer = Lexer::new();
+    let mut tokens = lexer.lex(judgement.to_string());
+
+    let variables = Variables::new(&mut tokens);
+    tokens.pop(); // consume |-
+    let expression = Expression::new(&mut tokens);
+
+    if !tokens.consume_relation(&Token::TRANS) {
+        let _ = writeln!(
+            w,
+            "{}",
+            lexer.get_syntax_error(judgement, &tokens, "expects `==>`")
+        );
+        return;
+    }
+
+    // the translation is computed when it's left as `?` or `==>` is left off
+    let nameless = match NamelessExpression::new(&variables, &expression) {
+        Ok(nameless) => nameless.to_string(),
+        Err(message) => {
+            let _ = writeln!(w, "{}", message);
+            return;
+        }
+    };
+    if let Err(message) = check_stated_result(
+        &tokens.tokens,
+        &nameless,
+        |derivable| lexer.lex(derivable).tokens,
+        |count| lexer.get_tail(judgement, count),
+    ) {
+        let _ = writeln!(w, "{}", message);
+        return;
+    }
+    let derivation_tree = RuleNode::new(variables, expression);
+    let mut writer = RuleWriter::new(w, 0);
+    let _ = derivation_tree.show(&mut writer);
+    writer.write_nl();
+}
@@ -0,0 +1,129 @@
+use super::super::chapter1::util::get_no_rule_message;
+use super::super::parser_evalml3::expression::Expression;
+use super::super::parser_evalml3::terms::Term;
+use super::variables::Variables;
+
+use std::fmt;
+
+// the nameless counterpart of parser_evalml3::expression::Expression,
+// keeping the same operators and parentheses
+#[derive(Debug, Clone)]
+pub struct NamelessExpression {
+    pub terms: Vec<(String, NamelessTerm)>,
+}
+impl NamelessExpression {
+    // fails when a variable is not in the variable list, as no rule translates it
+    pub fn new(
+        variables: &Variables,
+        expression: &Expression,
+    ) -> Result<NamelessExpression, String> {
+        let mut terms = Vec::new();
+        for (operator, term) in expression.terms.iter() {
+            terms.push((operator.clone(), NamelessTerm::new(variables, term)?));
+        }
+        Ok(NamelessExpression { terms })
+    }
+}
+impl fmt::Display for NamelessExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (operator, term) in self.terms.iter() {
+            if !operator.is_empty() {
+                write!(f, " {} ", operator)?;
+            }
+            write!(f, "{}", term)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum NamelessTerm {
+    Val(i32),
+    Bool(String),
+    Index(usize),
+    Paren(NamelessExpression),
+    If(NamelessExpression, NamelessExpression, NamelessExpression),
+    Let(NamelessExpression, NamelessExpression),
+    LetRec(NamelessExpression, NamelessExpression),
+    Fun(NamelessExpression),
+    App(Box<NamelessTerm>, NamelessExpression),
+}
+impl NamelessTerm {
+    pub fn new(variables: &Variables, term: &Term) -> Result<NamelessTerm, String> {
+        let nameless = match term {
+            Term::Val(num) => NamelessTerm::Val(*num),
+            Term::Bool(b) => NamelessTerm::Bool(b.clone()),
+            Term::Var(identifier) => match variables.get_index(identifier) {
+                Some(index) => NamelessTerm::Index(index),
+                None => return Err(get_no_rule_message(identifier)),
+            },
+            Term::Paren(expression) => {
+                NamelessTerm::Paren(NamelessExpression::new(variables, expression)?)
+            }
+            Term::If(if_term) => NamelessTerm::If(
+                NamelessExpression::new(variables, &if_term.condition_expression)?,
+                NamelessExpression::new(variables, &if_term.then_expression)?,
+                NamelessExpression::new(variables, &if_term.else_expression)?,
+            ),
+            Term::Let(let_term) => {
+                let mut new_variables = variables.clone();
+                new_variables.set_var(let_term.let_expression.identifier.clone());
+                NamelessTerm::Let(
+                    NamelessExpression::new(variables, &let_term.let_expression.expression)?,
+                    NamelessExpression::new(&new_variables, &let_term.in_expression)?,
+                )
+            }
+            Term::LetRec(let_rec_term) => {
+                let mut in_variables = variables.clone();
+                in_variables.set_var(let_rec_term.identifier.clone());
+                let mut body_variables = in_variables.clone();
+                body_variables.set_var(let_rec_term.fun_term.parameter.clone());
+                NamelessTerm::LetRec(
+                    NamelessExpression::new(&body_variables, &let_rec_term.fun_term.function_body)?,
+                    NamelessExpression::new(&in_variables, &let_rec_term.in_expression)?,
+                )
+            }
+            Term::Fun(fun_term) => {
+                let mut new_variables = variables.clone();
+                new_variables.set_var(fun_term.parameter.clone());
+                NamelessTerm::Fun(NamelessExpression::new(
+                    &new_variables,
+                    &fun_term.function_body,
+                )?)
+            }
+            Term::App(app_term) => NamelessTerm::App(
+                Box::new(NamelessTerm::new(variables, &app_term.function)?),
+                NamelessExpression::new(variables, &app_term.argument)?,
+            ),
+            _ => return Err(get_no_rule_message(&term.to_string())),
+        };
+        Ok(nameless)
+    }
+}
+impl fmt::Display for NamelessTerm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NamelessTerm::Val(num) => write!(f, "{}", num),
+            NamelessTerm::Bool(b) => write!(f, "{}", b),
+            NamelessTerm::Index(index) => write!(f, "#{}", index),
+            NamelessTerm::Paren(expression) => write!(f, "({})", expression),
+            NamelessTerm::If(condition, then_expression, else_expression) => write!(
+                f,
+                "if {} then {} else {}",
+                condition, then_expression, else_expression
+            ),
+            NamelessTerm::Let(let_expression, in_expression) => {
+                write!(f, "let . = {} in {}", let_expression, in_expression)
+            }
+            NamelessTerm::LetRec(function_body, in_expression) => write!(
+                f,
+                "let rec . = fun . -> {} in {}",
+                function_body, in_expression
+            ),
+            NamelessTerm::Fun(function_body) => write!(f, "fun . -> {}", function_body),
+            NamelessTerm::App(function, argument) => {
+                write!(f, "{} {}", function, argument)
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use super::super::parser_evalml3::expression::Expression;
+use super::super::parser_evalml3::terms::Term;
+use super::nodes::trnodes::{
+    TrAppNode, TrBOpNode, TrFunNode, TrIfNode, TrLetNode, TrLetRecNode, TrValNode, TrVarNode,
+};
+use super::nodes::writer::RuleWriter;
+use super::variables::Variables;
+use std::io::{self, Write};
+
+pub mod trnodes;
+pub mod writer;
+
+#[derive(Debug, Clone)]
+pub enum RuleNode {
+    Val(TrValNode),
+    Var(TrVarNode),
+    If(TrIfNode),
+    BOp(TrBOpNode),
+    Let(TrLetNode),
+    LetRec(TrLetRecNode),
+    Fun(TrFunNode),
+    App(TrAppNode),
+}
+
+impl RuleNode {
+    pub fn new(variables: Variables, mut expression: Expression) -> RuleNode {
+        let len: usize = expression.terms.len();
+        let original_expression = expression.clone();
+        if len == 1 {
+            let term = expression.get_first_term();
+            match term {
+                Term::Val(_) | Term::Bool(_) => RuleNode::Val(TrValNode {
+                    variables,
+                    expression: original_expression,
+                }),
+                Term::Var(identifier) => RuleNode::Var(TrVarNode {
+                    variables,
+                    identifier,
+                }),
+                Term::Paren(expression) => RuleNode::new(variables, expression),
+                Term::If(if_term) => RuleNode::If(TrIfNode {
+                    variables,
+                    expression: original_expression,
+                    term: if_term,
+                }),
+                Term::Let(let_term) => RuleNode::Let(TrLetNode {
+                    variables,
+                    expression: original_expression,
+                    term: let_term,
+                }),
+                Term::LetRec(let_rec_term) => RuleNode::LetRec(TrLetRecNode {
+                    variables,
+                    expression: original_expression,
+                    term: let_rec_term,
+                }),
+                Term::Fun(fun_term) => RuleNode::Fun(TrFunNode {
+                    variables,
+                    expression: original_expression,
+                    term: fun_term,
+                }),
+                Term::App(app_term) => RuleNode::App(TrAppNode {
+                    variables,
+                    expression: original_expression,
+                    term: app_term,
+                }),
+                _ => panic!("unexpected"),
+            }
+        } else {
+            RuleNode::BOp(TrBOpNode {
+                variables,
+                expression: original_expression,
+            })
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        match self {
+            RuleNode::Val(node) => node.show(writer),
+            RuleNode::Var(node) => node.show(writer),
+            RuleNode::If(node) => node.show(writer),
+            RuleNode::BOp(node) => node.show(writer),
+            RuleNode::Let(node) => node.show(writer),
+            RuleNode::LetRec(node) => node.show(writer),
+            RuleNode::Fun(node) => node.show(writer),
+            RuleNode::App(node) => node.show(writer),
+        }
+    }
+}
+
+pub fn get_depth_space(depth: usize) -> String {
+    let mut s = "".to_string();
+    for _ in 0..depth {
+        s += " ";
+    }
+    s
+}
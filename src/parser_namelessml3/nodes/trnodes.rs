@@ -0,0 +1,218 @@
+use super::super::super::parser_evalml3::expression::Expression;
+use super::super::super::parser_evalml3::terms::{
+    AppTerm, FunTerm, IfTerm, LetRecTerm, LetTerm, Term,
+};
+use super::super::nameless::NamelessExpression;
+use super::super::nodes::RuleNode;
+use super::super::variables::Variables;
+use super::writer::{Premises, RuleWriter};
+
+use std::io::{self, Write};
+
+// the named side never contains values, so it is printed under an empty environment
+fn show_expression(expression: &Expression) -> String {
+    expression.to_string()
+}
+
+// the whole judgement is translated before its derivation is built, so its parts are too
+fn show_nameless(variables: &Variables, expression: &Expression) -> String {
+    NamelessExpression::new(variables, expression)
+        .expect("expects a translated judgement")
+        .to_string()
+}
+
+#[derive(Debug, Clone)]
+pub struct TrValNode {
+    pub variables: Variables,
+    pub expression: Expression,
+}
+impl TrValNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let rule_str = match self.expression.terms[0].1 {
+            Term::Val(_) => "Tr-Int",
+            Term::Bool(_) => "Tr-Bool",
+            _ => panic!("unexpected"),
+        };
+        writer.show_rule(
+            self.variables.clone(),
+            show_expression(&self.expression),
+            show_nameless(&self.variables, &self.expression),
+            rule_str.to_string(),
+            Premises(None, None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrVarNode {
+    pub variables: Variables,
+    pub identifier: String,
+}
+impl TrVarNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let index = self
+            .variables
+            .get_index(&self.identifier)
+            .expect("expects a declared variable");
+        let (premise, rule_str) = if index == 1 {
+            (None, "Tr-Var1".to_string())
+        } else {
+            let mut new_variables = self.variables.clone();
+            new_variables.pop_var();
+            (
+                Some(RuleNode::Var(TrVarNode {
+                    variables: new_variables,
+                    identifier: self.identifier.clone(),
+                })),
+                "Tr-Var2".to_string(),
+            )
+        };
+        writer.show_rule(
+            self.variables,
+            self.identifier,
+            format!("#{}", index),
+            rule_str,
+            Premises(premise, None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrIfNode {
+    pub variables: Variables,
+    pub expression: Expression,
+    pub term: IfTerm,
+}
+impl TrIfNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let premise1 = RuleNode::new(self.variables.clone(), self.term.condition_expression);
+        let premise2 = RuleNode::new(self.variables.clone(), self.term.then_expression);
+        let premise3 = RuleNode::new(self.variables.clone(), self.term.else_expression);
+        writer.show_rule(
+            self.variables.clone(),
+            show_expression(&self.expression),
+            show_nameless(&self.variables, &self.expression),
+            "Tr-If".to_string(),
+            Premises(Some(premise1), Some(premise2), Some(premise3)),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrBOpNode {
+    pub variables: Variables,
+    pub expression: Expression,
+}
+impl TrBOpNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let (split_position, operator) = self.expression.get_split_position();
+        let (former, latter) = self.expression.get_splitted_expression(split_position);
+        let rule_str = match operator.as_ref() {
+            "+" => "Tr-Plus",
+            "-" => "Tr-Minus",
+            "*" => "Tr-Times",
+            "<" => "Tr-Lt",
+            _ => panic!("todo"),
+        };
+        let premise1 = RuleNode::new(self.variables.clone(), former);
+        let premise2 = RuleNode::new(self.variables.clone(), latter);
+        writer.show_rule(
+            self.variables.clone(),
+            show_expression(&self.expression),
+            show_nameless(&self.variables, &self.expression),
+            rule_str.to_string(),
+            Premises(Some(premise1), Some(premise2), None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrLetNode {
+    pub variables: Variables,
+    pub expression: Expression,
+    pub term: LetTerm,
+}
+impl TrLetNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let let_expression = self.term.let_expression;
+        let mut new_variables = self.variables.clone();
+        new_variables.set_var(let_expression.identifier);
+        let premise1 = RuleNode::new(self.variables.clone(), let_expression.expression);
+        let premise2 = RuleNode::new(new_variables, self.term.in_expression);
+        writer.show_rule(
+            self.variables.clone(),
+            show_expression(&self.expression),
+            show_nameless(&self.variables, &self.expression),
+            "Tr-Let".to_string(),
+            Premises(Some(premise1), Some(premise2), None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrLetRecNode {
+    pub variables: Variables,
+    pub expression: Expression,
+    pub term: LetRecTerm,
+}
+impl TrLetRecNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let fun_term = self.term.fun_term;
+        let mut in_variables = self.variables.clone();
+        in_variables.set_var(self.term.identifier);
+        let mut body_variables = in_variables.clone();
+        body_variables.set_var(fun_term.parameter);
+        let premise1 = RuleNode::new(body_variables, fun_term.function_body);
+        let premise2 = RuleNode::new(in_variables, self.term.in_expression);
+        writer.show_rule(
+            self.variables.clone(),
+            show_expression(&self.expression),
+            show_nameless(&self.variables, &self.expression),
+            "Tr-LetRec".to_string(),
+            Premises(Some(premise1), Some(premise2), None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrFunNode {
+    pub variables: Variables,
+    pub expression: Expression,
+    pub term: FunTerm,
+}
+impl TrFunNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let mut new_variables = self.variables.clone();
+        new_variables.set_var(self.term.parameter);
+        let premise = RuleNode::new(new_variables, self.term.function_body);
+        writer.show_rule(
+            self.variables.clone(),
+            show_expression(&self.expression),
+            show_nameless(&self.variables, &self.expression),
+            "Tr-Fun".to_string(),
+            Premises(Some(premise), None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrAppNode {
+    pub variables: Variables,
+    pub expression: Expression,
+    pub term: AppTerm,
+}
+impl TrAppNode {
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let terms: Vec<(String, Term)> = vec![("".to_string(), *self.term.function)];
+        let function_expression = Expression { terms };
+        let premise1 = RuleNode::new(self.variables.clone(), function_expression);
+        let premise2 = RuleNode::new(self.variables.clone(), self.term.argument);
+        writer.show_rule(
+            self.variables.clone(),
+            show_expression(&self.expression),
+            show_nameless(&self.variables, &self.expression),
+            "Tr-App".to_string(),
+            Premises(Some(premise1), Some(premise2), None),
+        )
+    }
+}
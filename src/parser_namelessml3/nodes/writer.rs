@@ -0,0 +1,62 @@
+pub use super::super::super::parser_evalml3::nodes::writer::Premises;
+use super::super::nodes::{get_depth_space, RuleNode};
+use super::super::variables::Variables;
+
+use std::io::{self, Write};
+
+pub struct RuleWriter<W> {
+    w: W,
+    depth: usize,
+}
+impl<W: Write> RuleWriter<W> {
+    pub fn new(w: W, depth: usize) -> RuleWriter<W> {
+        RuleWriter { w, depth }
+    }
+
+    pub fn write_nl(&mut self) {
+        let _ = writeln!(self.w);
+    }
+
+    fn inc_depth(&mut self) {
+        self.depth += 2;
+    }
+    fn dec_depth(&mut self) {
+        self.depth -= 2;
+    }
+
+    pub fn show_rule(
+        &mut self,
+        variables: Variables,
+        expression_str: String,
+        nameless_str: String,
+        rule_str: String,
+        premises: Premises<RuleNode>,
+    ) -> io::Result<()> {
+        let Premises(premise1, premise2, premise3) = premises;
+        let _ = write!(
+            self.w,
+            "{}{}{} ==> {} by {} {{",
+            get_depth_space(self.depth),
+            variables,
+            expression_str,
+            nameless_str,
+            rule_str,
+        );
+
+        let mut eol_necessity = false;
+
+        self.inc_depth();
+        for premise in IntoIterator::into_iter([premise1, premise2, premise3]).flatten() {
+            let separator = if eol_necessity { ";\n" } else { "\n" };
+            let _ = write!(self.w, "{}", separator);
+            let _ = premise.show(self);
+            eol_necessity = true;
+        }
+        self.dec_depth();
+        if eol_necessity {
+            write!(self.w, "\n{}}}", get_depth_space(self.depth))
+        } else {
+            write!(self.w, "}}")
+        }
+    }
+}
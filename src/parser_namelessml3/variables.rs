@@ -0,0 +1,60 @@
+use super::super::parser_evalml3::lexer::{Token, Tokens};
+
+use std::fmt;
+
+// the variable list X of a judgement X |- e ==> d
+#[derive(Debug, Clone)]
+pub struct Variables {
+    pub stack: Vec<String>,
+}
+impl Variables {
+    pub fn new(tokens: &mut Tokens) -> Variables {
+        let mut stack: Vec<String> = Vec::new();
+
+        if let Some(Token::ENV) = tokens.peek() {
+            return Variables { stack };
+        }
+
+        stack.push(tokens.consume_var());
+        loop {
+            match tokens.peek().expect("") {
+                Token::COMMA => {
+                    tokens.pop(); // consume ,
+                    stack.push(tokens.consume_var());
+                }
+                Token::ENV => {
+                    break;
+                }
+                _ => panic!("unexpected"),
+            }
+        }
+        Variables { stack }
+    }
+
+    pub fn set_var(&mut self, identifier: String) {
+        self.stack.push(identifier);
+    }
+
+    pub fn pop_var(&mut self) {
+        self.stack.pop();
+    }
+
+    // de Bruijn index of the variable, counted from the right starting at 1
+    pub fn get_index(&self, identifier: &str) -> Option<usize> {
+        for (loc, var) in self.stack.iter().rev().enumerate() {
+            if var == identifier {
+                return Some(loc + 1);
+            }
+        }
+        None
+    }
+}
+impl fmt::Display for Variables {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.stack.is_empty() {
+            write!(f, "|- ")
+        } else {
+            write!(f, "{} |- ", self.stack.join(", "))
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use super::super::parser_evalml3::environment;
+use super::super::parser_evalml3::lexer::{Token, Tokens};
+use super::super::typing_ml4::types::{Substitution, Type, TypeVarNames};
+
+// 'a 'b. 'a -> 'b
+#[derive(Debug, Clone)]
+pub struct Scheme {
+    pub vars: Vec<usize>,
+    pub ty: Type,
+}
+impl Scheme {
+    pub fn new(tokens: &mut Tokens, substitution: &mut Substitution) -> Result<Scheme, String> {
+        let mut vars = Vec::new();
+        if Scheme::has_quantifier(tokens) {
+            while let Some(Token::TVar(name)) = tokens.pop() {
+                if let Type::Var(index) = substitution.get_named(name) {
+                    vars.push(index);
+                }
+                if let Some(Token::DOT) = tokens.peek() {
+                    tokens.pop(); // consume .
+                    break;
+                }
+            }
+        }
+        let ty = Type::new(tokens, substitution)?;
+        Ok(Scheme { vars, ty })
+    }
+    // 'a 'b. is followed by a dot, while 'a -> 'b is not
+    fn has_quantifier(tokens: &Tokens) -> bool {
+        for token in tokens.tokens.iter() {
+            match token {
+                Token::TVar(_) => continue,
+                Token::DOT => return true,
+                _ => return false,
+            }
+        }
+        false
+    }
+
+    // replaces the bound type variables with fresh ones
+    pub fn instantiate(&self, substitution: &mut Substitution) -> Type {
+        let mapping = self
+            .vars
+            .iter()
+            .map(|index| (*index, substitution.fresh()))
+            .collect();
+        substitution.resolve(&self.ty).replace_vars(&mapping)
+    }
+
+    pub fn get_free_vars(&self, substitution: &Substitution) -> Vec<usize> {
+        let mut vars = Vec::new();
+        substitution.resolve(&self.ty).get_vars(&mut vars);
+        vars.retain(|index| !self.vars.contains(index));
+        vars
+    }
+
+    pub fn to_string(&self, substitution: &Substitution, names: &mut TypeVarNames) -> String {
+        let ty_str_prefix = self
+            .vars
+            .iter()
+            .map(|index| names.get_name(*index))
+            .collect::<Vec<String>>()
+            .join(" ");
+        let ty_str = names.get_type_str(&substitution.resolve(&self.ty));
+        if self.vars.is_empty() {
+            ty_str
+        } else {
+            format!("{}. {}", ty_str_prefix, ty_str)
+        }
+    }
+}
+
+// f : 'a 'b. 'a -> 'b, x : int |-
+pub type Environment = environment::Environment<Scheme>;
+impl Environment {
+    pub fn set_type(&mut self, identifier: String, ty: Type) {
+        let vars = Vec::new();
+        self.set_val(identifier, Scheme { vars, ty });
+    }
+
+    // quantifies the type variables of ty that are not free in the environment
+    pub fn generalize(&self, ty: &Type, substitution: &Substitution) -> Scheme {
+        let ty = substitution.resolve(ty);
+        let mut vars = Vec::new();
+        ty.get_vars(&mut vars);
+        for (_, scheme) in self.stack.iter() {
+            let free_vars = scheme.get_free_vars(substitution);
+            vars.retain(|index| !free_vars.contains(index));
+        }
+        Scheme { vars, ty }
+    }
+}
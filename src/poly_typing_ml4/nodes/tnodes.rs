@@ -0,0 +1,396 @@
+use super::super::super::parser_evalml3::expression::Expression;
+use super::super::super::parser_evalml3::terms::{
+    AppTerm, FunTerm, IfTerm, LetRecTerm, LetTerm, ListMatch, Term,
+};
+use super::super::super::typing_ml4::types::{Substitution, Type};
+use super::super::environment::Environment;
+use super::super::nodes::RuleNode;
+use super::writer::{Premises, RuleWriter};
+
+use std::io::{self, Write};
+
+// T-Int, T-Bool, T-Nil and T-Var have no premises
+#[derive(Debug, Clone)]
+pub struct TValNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub ty: Type,
+    pub rule_str: String,
+}
+impl TValNode {
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        ty: Type,
+        rule_str: &str,
+    ) -> TValNode {
+        TValNode {
+            environment,
+            expression,
+            ty,
+            rule_str: rule_str.to_string(),
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_type_rule(
+            &self.environment,
+            self.expression.to_string(),
+            &self.ty,
+            &self.rule_str,
+            Premises(None, None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TIfNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub ty: Type,
+    pub premises: Box<(RuleNode, RuleNode, RuleNode)>,
+}
+impl TIfNode {
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        ty: Type,
+        term: IfTerm,
+        substitution: &mut Substitution,
+    ) -> TIfNode {
+        let premise1 = RuleNode::new(
+            environment.clone(),
+            term.condition_expression,
+            Type::Bool,
+            substitution,
+        );
+        let premise2 = RuleNode::new(
+            environment.clone(),
+            term.then_expression,
+            ty.clone(),
+            substitution,
+        );
+        let premise3 = RuleNode::new(
+            environment.clone(),
+            term.else_expression,
+            ty.clone(),
+            substitution,
+        );
+        TIfNode {
+            environment,
+            expression,
+            ty,
+            premises: Box::new((premise1, premise2, premise3)),
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let (premise1, premise2, premise3) = *self.premises;
+        writer.show_type_rule(
+            &self.environment,
+            self.expression.to_string(),
+            &self.ty,
+            "T-If",
+            Premises(Some(premise1), Some(premise2), Some(premise3)),
+        )
+    }
+}
+
+// T-Plus, T-Minus, T-Times, T-Lt and T-Cons
+#[derive(Debug, Clone)]
+pub struct TBOpNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub ty: Type,
+    pub rule_str: String,
+    pub premises: Box<(RuleNode, RuleNode)>,
+}
+impl TBOpNode {
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        ty: Type,
+        substitution: &mut Substitution,
+    ) -> TBOpNode {
+        let (split_position, operator) = expression.get_split_position();
+        let (former, latter) = expression.get_splitted_expression(split_position);
+        let (rule_str, former_type, latter_type, result_type) = match operator.as_ref() {
+            "+" => ("T-Plus", Type::Int, Type::Int, Type::Int),
+            "-" => ("T-Minus", Type::Int, Type::Int, Type::Int),
+            "*" => ("T-Times", Type::Int, Type::Int, Type::Int),
+            "<" => ("T-Lt", Type::Int, Type::Int, Type::Bool),
+            "::" => {
+                let element = substitution.fresh();
+                let list = Type::List(Box::new(element.clone()));
+                ("T-Cons", element, list.clone(), list)
+            }
+            // e.g. `:=`, which no rule types. the derivation is not shown after an error
+            _ => {
+                substitution.set_no_rule(expression.to_string());
+                let operand = substitution.fresh();
+                ("", operand.clone(), operand.clone(), operand)
+            }
+        };
+        substitution.unify(&ty, &result_type, expression.to_string(), rule_str);
+        let premise1 = RuleNode::new(environment.clone(), former, former_type, substitution);
+        let premise2 = RuleNode::new(environment.clone(), latter, latter_type, substitution);
+        TBOpNode {
+            environment,
+            expression,
+            ty,
+            rule_str: rule_str.to_string(),
+            premises: Box::new((premise1, premise2)),
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let (premise1, premise2) = *self.premises;
+        writer.show_type_rule(
+            &self.environment,
+            self.expression.to_string(),
+            &self.ty,
+            &self.rule_str,
+            Premises(Some(premise1), Some(premise2), None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TLetNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub ty: Type,
+    pub premises: Box<(RuleNode, RuleNode)>,
+}
+impl TLetNode {
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        ty: Type,
+        term: LetTerm,
+        substitution: &mut Substitution,
+    ) -> TLetNode {
+        let let_type = substitution.fresh();
+        let premise1 = RuleNode::new(
+            environment.clone(),
+            term.let_expression.expression,
+            let_type.clone(),
+            substitution,
+        );
+        let scheme = environment.generalize(&let_type, substitution);
+        let mut new_env = environment.clone();
+        new_env.set_val(term.let_expression.identifier, scheme);
+        let premise2 = RuleNode::new(new_env, term.in_expression, ty.clone(), substitution);
+        TLetNode {
+            environment,
+            expression,
+            ty,
+            premises: Box::new((premise1, premise2)),
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let (premise1, premise2) = *self.premises;
+        writer.show_type_rule(
+            &self.environment,
+            self.expression.to_string(),
+            &self.ty,
+            "T-Let",
+            Premises(Some(premise1), Some(premise2), None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TFunNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub ty: Type,
+    pub premise: Box<RuleNode>,
+}
+impl TFunNode {
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        ty: Type,
+        term: FunTerm,
+        substitution: &mut Substitution,
+    ) -> TFunNode {
+        let parameter_type = substitution.fresh();
+        let body_type = substitution.fresh();
+        let function_type = Type::Fun(
+            Box::new(parameter_type.clone()),
+            Box::new(body_type.clone()),
+        );
+        substitution.unify(&ty, &function_type, expression.to_string(), "T-Fun");
+        let mut new_env = environment.clone();
+        new_env.set_type(term.parameter, parameter_type);
+        let premise = RuleNode::new(new_env, term.function_body, body_type, substitution);
+        TFunNode {
+            environment,
+            expression,
+            ty,
+            premise: Box::new(premise),
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        writer.show_type_rule(
+            &self.environment,
+            self.expression.to_string(),
+            &self.ty,
+            "T-Fun",
+            Premises(Some(*self.premise), None, None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TAppNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub ty: Type,
+    pub premises: Box<(RuleNode, RuleNode)>,
+}
+impl TAppNode {
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        ty: Type,
+        term: AppTerm,
+        substitution: &mut Substitution,
+    ) -> TAppNode {
+        let terms: Vec<(String, Term)> = vec![("".to_string(), *term.function)];
+        let function_expression = Expression { terms };
+        let argument_type = substitution.fresh();
+        let function_type = Type::Fun(Box::new(argument_type.clone()), Box::new(ty.clone()));
+        let premise1 = RuleNode::new(
+            environment.clone(),
+            function_expression,
+            function_type,
+            substitution,
+        );
+        let premise2 = RuleNode::new(
+            environment.clone(),
+            term.argument,
+            argument_type,
+            substitution,
+        );
+        TAppNode {
+            environment,
+            expression,
+            ty,
+            premises: Box::new((premise1, premise2)),
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let (premise1, premise2) = *self.premises;
+        writer.show_type_rule(
+            &self.environment,
+            self.expression.to_string(),
+            &self.ty,
+            "T-App",
+            Premises(Some(premise1), Some(premise2), None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TLetRecNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub ty: Type,
+    pub premises: Box<(RuleNode, RuleNode)>,
+}
+impl TLetRecNode {
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        ty: Type,
+        term: LetRecTerm,
+        substitution: &mut Substitution,
+    ) -> TLetRecNode {
+        let parameter_type = substitution.fresh();
+        let body_type = substitution.fresh();
+        let function_type = Type::Fun(
+            Box::new(parameter_type.clone()),
+            Box::new(body_type.clone()),
+        );
+        let mut body_env = environment.clone();
+        body_env.set_type(term.identifier.clone(), function_type.clone());
+        body_env.set_type(term.fun_term.parameter, parameter_type);
+        let premise1 = RuleNode::new(
+            body_env,
+            term.fun_term.function_body,
+            body_type,
+            substitution,
+        );
+        let scheme = environment.generalize(&function_type, substitution);
+        let mut new_env = environment.clone();
+        new_env.set_val(term.identifier, scheme);
+        let premise2 = RuleNode::new(new_env, term.in_expression, ty.clone(), substitution);
+        TLetRecNode {
+            environment,
+            expression,
+            ty,
+            premises: Box::new((premise1, premise2)),
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let (premise1, premise2) = *self.premises;
+        writer.show_type_rule(
+            &self.environment,
+            self.expression.to_string(),
+            &self.ty,
+            "T-LetRec",
+            Premises(Some(premise1), Some(premise2), None),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TMatchNode {
+    pub environment: Environment,
+    pub expression: Expression,
+    pub ty: Type,
+    pub premises: Box<(RuleNode, RuleNode, RuleNode)>,
+}
+impl TMatchNode {
+    pub fn new(
+        environment: Environment,
+        expression: Expression,
+        ty: Type,
+        term: ListMatch,
+        substitution: &mut Substitution,
+    ) -> TMatchNode {
+        let element_type = substitution.fresh();
+        let list_type = Type::List(Box::new(element_type.clone()));
+        let premise1 = RuleNode::new(
+            environment.clone(),
+            term.expression,
+            list_type.clone(),
+            substitution,
+        );
+        let premise2 = RuleNode::new(
+            environment.clone(),
+            term.nil_expression,
+            ty.clone(),
+            substitution,
+        );
+        let mut new_env = environment.clone();
+        new_env.set_type(term.head_identifier, element_type);
+        new_env.set_type(term.tail_identifier, list_type);
+        let premise3 = RuleNode::new(new_env, term.cons_expression, ty.clone(), substitution);
+        TMatchNode {
+            environment,
+            expression,
+            ty,
+            premises: Box::new((premise1, premise2, premise3)),
+        }
+    }
+    pub fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        let (premise1, premise2, premise3) = *self.premises;
+        writer.show_type_rule(
+            &self.environment,
+            self.expression.to_string(),
+            &self.ty,
+            "T-Match",
+            Premises(Some(premise1), Some(premise2), Some(premise3)),
+        )
+    }
+}
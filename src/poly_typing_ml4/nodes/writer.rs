@@ -0,0 +1,28 @@
+use super::super::super::parser_evalml3::nodes::writer;
+pub use super::super::super::parser_evalml3::nodes::writer::Premises;
+use super::super::super::typing_ml4::types::{Substitution, Type, TypeVarNames};
+use super::super::environment::Environment;
+use super::RuleNode;
+
+use std::io::{self, Write};
+
+pub type RuleWriter<W> = writer::RuleWriter<W, RuleNode>;
+
+impl<W: Write> RuleWriter<W> {
+    // type variables are printed as the types inferred for them
+    pub fn show_type_rule(
+        &mut self,
+        environment: &Environment,
+        expression_str: String,
+        ty: &Type,
+        rule_str: &str,
+        premises: Premises<RuleNode>,
+    ) -> io::Result<()> {
+        let (substitution, names): &mut (Substitution, TypeVarNames) = &mut self.context;
+        let environment_str =
+            environment.to_string_by(" : ", |scheme| scheme.to_string(substitution, names));
+        let type_str = names.get_type_str(&substitution.resolve(ty));
+        let judgement_str = format!("{}{} : {}", environment_str, expression_str, type_str);
+        self.show_judgement(judgement_str, rule_str.to_string(), premises)
+    }
+}
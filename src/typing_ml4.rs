@@ -0,0 +1,54 @@
+mod nodes;
+pub(crate) mod types;
+
+use super::parser_evalml3::expression::Expression;
+use super::parser_evalml3::lexer::Lexer;
+use super::parser_evalml3::nodes::writer::Rule;
+use nodes::writer::RuleWriter;
+use nodes::RuleNode;
+use types::{Environment, Substitution, Type, TypeVarNames};
+
+use std::io::Write;
+
+pub fn derive<W: Write>(judgement: &str, w: &mut W) {
+    let lexer = Lexer::new();
+    let mut tokens = lexer.lex(judgement.to_string());
+
+    let mut substitution = Substitution::new();
+    let environment =
+        match Environment::try_new(&mut tokens, |tokens| Type::new(tokens, &mut substitution)) {
+            Ok(environment) => environment,
+            Err(message) => {
+                let _ = writeln!(
+                    w,
+                    "{}",
+                    lexer.get_syntax_error(judgement, &tokens, &message)
+                );
+                return;
+            }
+        };
+    tokens.pop(); // consume |-
+    let expression = Expression::new(&mut tokens);
+    let ty = match Type::new_annotation(&mut tokens, &mut substitution) {
+        Ok(ty) => ty,
+        Err(message) => {
+            let _ = writeln!(
+                w,
+                "{}",
+                lexer.get_syntax_error(judgement, &tokens, &message)
+            );
+            return;
+        }
+    };
+
+    let derivation_tree = RuleNode::new(environment, expression, ty, &mut substitution);
+    // explain why the judgement doesn't hold instead of showing a broken derivation
+    if let Some(error) = substitution.get_error() {
+        let _ = write!(w, "{}", error);
+        return;
+    }
+    substitution.default_unsolved();
+    let mut writer = RuleWriter::new_with_context(w, 0, (substitution, TypeVarNames::new()));
+    let _ = derivation_tree.show(&mut writer);
+    writer.write_nl();
+}
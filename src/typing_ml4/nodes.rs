@@ -0,0 +1,142 @@
+use super::super::parser_evalml3::expression::Expression;
+use super::super::parser_evalml3::nodes::writer::Rule;
+use super::super::parser_evalml3::terms::{ListMatch, Term};
+use super::nodes::tnodes::{
+    TAppNode, TBOpNode, TFunNode, TIfNode, TLetNode, TLetRecNode, TMatchNode, TValNode,
+};
+use super::nodes::writer::RuleWriter;
+use super::types::{Environment, Substitution, Type, TypeVarNames};
+use std::io::{self, Write};
+
+pub mod tnodes;
+pub mod writer;
+
+#[derive(Debug, Clone)]
+pub enum RuleNode {
+    Val(TValNode),
+    If(TIfNode),
+    BOp(TBOpNode),
+    Let(TLetNode),
+    Fun(TFunNode),
+    App(TAppNode),
+    LetRec(TLetRecNode),
+    Match(TMatchNode),
+}
+
+impl RuleNode {
+    // derives `environment |- expression : ty`, adding the equations the rules
+    // impose on ty to the substitution
+    pub fn new(
+        environment: Environment,
+        mut expression: Expression,
+        ty: Type,
+        substitution: &mut Substitution,
+    ) -> RuleNode {
+        let original_expression = expression.clone();
+        if expression.terms.len() != 1 {
+            return RuleNode::BOp(TBOpNode::new(
+                environment,
+                original_expression,
+                ty,
+                substitution,
+            ));
+        }
+        match expression.get_first_term() {
+            Term::Val(_) => {
+                substitution.unify(&ty, &Type::Int, original_expression.to_string(), "T-Int");
+                RuleNode::Val(TValNode::new(environment, original_expression, ty, "T-Int"))
+            }
+            Term::Bool(_) => {
+                substitution.unify(&ty, &Type::Bool, original_expression.to_string(), "T-Bool");
+                RuleNode::Val(TValNode::new(
+                    environment,
+                    original_expression,
+                    ty,
+                    "T-Bool",
+                ))
+            }
+            Term::Nil => {
+                let element = substitution.fresh();
+                substitution.unify(
+                    &ty,
+                    &Type::List(Box::new(element)),
+                    original_expression.to_string(),
+                    "T-Nil",
+                );
+                RuleNode::Val(TValNode::new(environment, original_expression, ty, "T-Nil"))
+            }
+            Term::Var(identifier) => {
+                match environment.find_val(&identifier) {
+                    Some(var_type) => substitution.unify(&ty, &var_type, identifier, "T-Var"),
+                    None => substitution.set_undeclared(identifier),
+                }
+                RuleNode::Val(TValNode::new(environment, original_expression, ty, "T-Var"))
+            }
+            Term::Paren(expression) => RuleNode::new(environment, expression, ty, substitution),
+            Term::If(if_term) => RuleNode::If(TIfNode::new(
+                environment,
+                original_expression,
+                ty,
+                if_term,
+                substitution,
+            )),
+            Term::Let(let_term) => RuleNode::Let(TLetNode::new(
+                environment,
+                original_expression,
+                ty,
+                let_term,
+                substitution,
+            )),
+            Term::Fun(fun_term) => RuleNode::Fun(TFunNode::new(
+                environment,
+                original_expression,
+                ty,
+                fun_term,
+                substitution,
+            )),
+            Term::App(app_term) => RuleNode::App(TAppNode::new(
+                environment,
+                original_expression,
+                ty,
+                app_term,
+                substitution,
+            )),
+            Term::LetRec(let_rec_term) => RuleNode::LetRec(TLetRecNode::new(
+                environment,
+                original_expression,
+                ty,
+                let_rec_term,
+                substitution,
+            )),
+            Term::Match(match_term) => RuleNode::Match(TMatchNode::new(
+                environment,
+                original_expression,
+                ty,
+                ListMatch::new(match_term),
+                substitution,
+            )),
+            _ => {
+                substitution.set_no_rule(original_expression.to_string());
+                RuleNode::Val(TValNode::new(environment, original_expression, ty, ""))
+            }
+        }
+    }
+}
+
+impl Rule for RuleNode {
+    // the substitution solving the type equations and the names of the type variables
+    type Context = (Substitution, TypeVarNames);
+
+    fn show<W: Write>(self, writer: &mut RuleWriter<W>) -> io::Result<()> {
+        match self {
+            RuleNode::Val(node) => node.show(writer),
+            RuleNode::If(node) => node.show(writer),
+            RuleNode::BOp(node) => node.show(writer),
+            RuleNode::Let(node) => node.show(writer),
+            RuleNode::Fun(node) => node.show(writer),
+            RuleNode::App(node) => node.show(writer),
+            RuleNode::LetRec(node) => node.show(writer),
+            RuleNode::Match(node) => node.show(writer),
+        }
+    }
+}
@@ -0,0 +1,434 @@
+use super::super::parser_evalml3::environment;
+use super::super::parser_evalml3::lexer::{Token, Tokens};
+
+use std::fmt;
+
+// x : int, y : bool |-
+pub type Environment = environment::Environment<Type>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Int,
+    Bool,
+    Fun(Box<Type>, Box<Type>),
+    List(Box<Type>),
+    // a type variable introduced during inference
+    Var(usize),
+}
+impl Type {
+    // `: t` closing a typing judgement, where the type is inferred when it's left as `?`
+    pub fn new_annotation(
+        tokens: &mut Tokens,
+        substitution: &mut Substitution,
+    ) -> Result<Type, String> {
+        match tokens.peek() {
+            Some(Token::COLON) => tokens.pop(), // consume :
+            _ => return Err("expects `:`".to_string()),
+        };
+        if tokens.tokens.is_empty() {
+            return Ok(substitution.fresh());
+        }
+        let ty = Type::new(tokens, substitution)?;
+        match tokens.peek() {
+            Some(_) => Err("expects the end of the judgement".to_string()),
+            None => Ok(ty),
+        }
+    }
+
+    // t ::= t' -> t | t'
+    pub fn new(tokens: &mut Tokens, substitution: &mut Substitution) -> Result<Type, String> {
+        let ty = Type::new_list(tokens, substitution)?;
+        match tokens.peek() {
+            Some(Token::ARROW) => {
+                tokens.pop(); // consume ->
+                let result = Type::new(tokens, substitution)?;
+                Ok(Type::Fun(Box::new(ty), Box::new(result)))
+            }
+            _ => Ok(ty),
+        }
+    }
+    // t' ::= t' list | int | bool | 'a | (t)
+    fn new_list(tokens: &mut Tokens, substitution: &mut Substitution) -> Result<Type, String> {
+        // the token is left unconsumed on an error, so that the error can point at it
+        let mut ty = match tokens.peek() {
+            Some(Token::Var(ref name)) if name == "int" => Type::Int,
+            Some(Token::Var(ref name)) if name == "bool" => Type::Bool,
+            Some(Token::TVar(name)) => substitution.get_named(name),
+            Some(Token::PS) => {
+                tokens.pop(); // consume (
+                let ty = Type::new(tokens, substitution)?;
+                match tokens.peek() {
+                    Some(Token::PE) => ty,
+                    _ => return Err("expects `)`".to_string()),
+                }
+            }
+            _ => return Err("expects a type".to_string()),
+        };
+        tokens.pop(); // consume the last token of the type
+        while let Some(Token::Var(name)) = tokens.peek() {
+            if name != "list" {
+                break;
+            }
+            tokens.pop(); // consume list
+            ty = Type::List(Box::new(ty));
+        }
+        Ok(ty)
+    }
+
+    // the type variables in order of their first appearance
+    pub fn get_vars(&self, vars: &mut Vec<usize>) {
+        match self {
+            Type::Int | Type::Bool => (),
+            Type::Fun(argument, result) => {
+                argument.get_vars(vars);
+                result.get_vars(vars);
+            }
+            Type::List(element) => element.get_vars(vars),
+            Type::Var(index) => {
+                if !vars.contains(index) {
+                    vars.push(*index);
+                }
+            }
+        }
+    }
+
+    // replaces the type variables by the types they are mapped to
+    pub fn replace_vars(&self, mapping: &Vec<(usize, Type)>) -> Type {
+        match self {
+            Type::Int | Type::Bool => self.clone(),
+            Type::Fun(argument, result) => Type::Fun(
+                Box::new(argument.replace_vars(mapping)),
+                Box::new(result.replace_vars(mapping)),
+            ),
+            Type::List(element) => Type::List(Box::new(element.replace_vars(mapping))),
+            Type::Var(index) => match mapping.iter().find(|(i, _)| i == index) {
+                Some((_, ty)) => ty.clone(),
+                None => self.clone(),
+            },
+        }
+    }
+
+    fn contains(&self, index: usize) -> bool {
+        match self {
+            Type::Int | Type::Bool => false,
+            Type::Fun(argument, result) => argument.contains(index) || result.contains(index),
+            Type::List(element) => element.contains(index),
+            Type::Var(i) => *i == index,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Type::Int => write!(f, "int"),
+            Type::Bool => write!(f, "bool"),
+            // -> is right associative
+            Type::Fun(argument, result) => match **argument {
+                Type::Fun(_, _) => write!(f, "({}) -> {}", argument, result),
+                _ => write!(f, "{} -> {}", argument, result),
+            },
+            Type::List(element) => match **element {
+                Type::Fun(_, _) => write!(f, "({}) list", element),
+                _ => write!(f, "{} list", element),
+            },
+            Type::Var(index) => write!(f, "{}", get_var_name(*index)),
+        }
+    }
+}
+
+// 'a, 'b, ..., 'z, 'a1, 'b1, ...
+fn get_var_name(index: usize) -> String {
+    let letter = (b'a' + (index % 26) as u8) as char;
+    if index < 26 {
+        format!("'{}", letter)
+    } else {
+        format!("'{}{}", letter, index / 26)
+    }
+}
+
+// an equation `expected = actual` imposed by a rule on the type of an expression
+#[derive(Debug, Clone)]
+pub struct Constraint {
+    pub expression: String,
+    pub rule: String,
+    pub expected: Type,
+    pub actual: Type,
+}
+impl Constraint {
+    fn get_vars(&self) -> Vec<usize> {
+        let mut vars = Vec::new();
+        self.expected.get_vars(&mut vars);
+        self.actual.get_vars(&mut vars);
+        vars
+    }
+
+    // a constraint that was solved states the one type it gave the expression
+    fn to_string(&self, names: &mut TypeVarNames) -> String {
+        if self.expected == self.actual {
+            return format!(
+                "`{}` by {}: {}",
+                self.expression,
+                self.rule,
+                names.get_type_str(&self.actual)
+            );
+        }
+        format!(
+            "`{}` by {}: expected {}, found {}",
+            self.expression,
+            self.rule,
+            names.get_type_str(&self.expected),
+            names.get_type_str(&self.actual)
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum TypeError {
+    // unification failed on the clashing types at the given constraint;
+    // chain holds the earlier constraints that determined its type variables
+    Mismatch {
+        clash: (Type, Type),
+        constraint: Constraint,
+        chain: Vec<Constraint>,
+    },
+    Undeclared(String),
+    // no typing rule has the expression as its conclusion, e.g. `r := 1`
+    NoRule(String),
+}
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut names = TypeVarNames::new();
+        match self {
+            TypeError::Mismatch {
+                clash,
+                constraint,
+                chain,
+            } => {
+                let (expected, actual) = clash;
+                writeln!(
+                    f,
+                    "type error: cannot unify {} with {}",
+                    names.get_type_str(expected),
+                    names.get_type_str(actual)
+                )?;
+                writeln!(f, "  in {}", constraint.to_string(&mut names))?;
+                if !chain.is_empty() {
+                    writeln!(f, "because of the constraints:")?;
+                    for c in chain.iter() {
+                        writeln!(f, "  {}", c.to_string(&mut names))?;
+                    }
+                }
+                Ok(())
+            }
+            TypeError::Undeclared(identifier) => {
+                writeln!(f, "type error: variable '{}' is not declared", identifier)
+            }
+            TypeError::NoRule(expression) => {
+                writeln!(f, "type error: no rule applies to {}", expression)
+            }
+        }
+    }
+}
+
+// the solution of the type equations collected so far
+#[derive(Debug, Clone)]
+pub struct Substitution {
+    types: Vec<Option<Type>>,
+    // type variables written in the judgement, e.g. 'a
+    names: Vec<(String, Type)>,
+    constraints: Vec<Constraint>,
+    // inference goes on after a failure, but only the first error is reported
+    error: Option<TypeError>,
+}
+impl Substitution {
+    pub fn new() -> Substitution {
+        Substitution {
+            types: Vec::new(),
+            names: Vec::new(),
+            constraints: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn fresh(&mut self) -> Type {
+        self.types.push(None);
+        Type::Var(self.types.len() - 1)
+    }
+
+    pub fn get_named(&mut self, name: String) -> Type {
+        if let Some((_, ty)) = self.names.iter().find(|(n, _)| n == &name) {
+            return ty.clone();
+        }
+        let ty = self.fresh();
+        self.names.push((name, ty.clone()));
+        ty
+    }
+
+    // TypingML4 has no type variables of its own, so the ones left unconstrained
+    // are instantiated with int, except those written in the judgement
+    pub fn default_unsolved(&mut self) {
+        let mut named_vars = Vec::new();
+        for (_, ty) in self.names.iter() {
+            self.resolve(ty).get_vars(&mut named_vars);
+        }
+        for index in 0..self.types.len() {
+            if self.types[index].is_none() && !named_vars.contains(&index) {
+                self.types[index] = Some(Type::Int);
+            }
+        }
+    }
+
+    pub fn get_error(&self) -> Option<TypeError> {
+        self.error.clone()
+    }
+
+    pub fn set_undeclared(&mut self, identifier: String) {
+        if self.error.is_none() {
+            self.error = Some(TypeError::Undeclared(identifier));
+        }
+    }
+
+    pub fn set_no_rule(&mut self, expression: String) {
+        if self.error.is_none() {
+            self.error = Some(TypeError::NoRule(expression));
+        }
+    }
+
+    pub fn resolve(&self, ty: &Type) -> Type {
+        match ty {
+            Type::Int | Type::Bool => ty.clone(),
+            Type::Fun(argument, result) => Type::Fun(
+                Box::new(self.resolve(argument)),
+                Box::new(self.resolve(result)),
+            ),
+            Type::List(element) => Type::List(Box::new(self.resolve(element))),
+            Type::Var(index) => match &self.types[*index] {
+                Some(ty) => self.resolve(ty),
+                None => ty.clone(),
+            },
+        }
+    }
+
+    // `expression` is required to have the type `expected` by its context and
+    // the type `actual` by `rule`
+    pub fn unify(&mut self, expected: &Type, actual: &Type, expression: String, rule: &str) {
+        if self.error.is_some() {
+            return;
+        }
+        let constraint = Constraint {
+            expression,
+            rule: rule.to_string(),
+            expected: expected.clone(),
+            actual: actual.clone(),
+        };
+        if let Err(clash) = self.unify_types(expected, actual) {
+            // the types are shown as far as they are solved, so that each constraint
+            // names the concrete types in conflict rather than type variables
+            let chain = self
+                .get_chain(&constraint)
+                .iter()
+                .map(|c| self.resolve_constraint(c))
+                .collect();
+            self.error = Some(TypeError::Mismatch {
+                clash,
+                constraint: self.resolve_constraint(&constraint),
+                chain,
+            });
+            return;
+        }
+        self.constraints.push(constraint);
+    }
+
+    fn resolve_constraint(&self, constraint: &Constraint) -> Constraint {
+        Constraint {
+            expected: self.resolve(&constraint.expected),
+            actual: self.resolve(&constraint.actual),
+            ..constraint.clone()
+        }
+    }
+
+    // the earlier constraints sharing type variables with the constraint,
+    // directly or through other constraints
+    fn get_chain(&self, constraint: &Constraint) -> Vec<Constraint> {
+        let mut vars = constraint.get_vars();
+        let mut in_chain = vec![false; self.constraints.len()];
+        let mut updated = true;
+        while updated {
+            updated = false;
+            for (i, c) in self.constraints.iter().enumerate() {
+                let c_vars = c.get_vars();
+                if !in_chain[i] && c_vars.iter().any(|index| vars.contains(index)) {
+                    vars.extend(c_vars);
+                    in_chain[i] = true;
+                    updated = true;
+                }
+            }
+        }
+        self.constraints
+            .iter()
+            .zip(in_chain)
+            .filter(|(_, in_chain)| *in_chain)
+            .map(|(c, _)| c.clone())
+            .collect()
+    }
+
+    fn unify_types(&mut self, ty1: &Type, ty2: &Type) -> Result<(), (Type, Type)> {
+        let ty1 = self.resolve(ty1);
+        let ty2 = self.resolve(ty2);
+        match (&ty1, &ty2) {
+            (Type::Int, Type::Int) | (Type::Bool, Type::Bool) => Ok(()),
+            (Type::Var(index1), Type::Var(index2)) if index1 == index2 => Ok(()),
+            (Type::Var(index), ty) | (ty, Type::Var(index)) => {
+                // 'a = 'a -> int has no finite solution
+                if ty.contains(*index) {
+                    return Err((ty1.clone(), ty2.clone()));
+                }
+                self.types[*index] = Some(ty.clone());
+                Ok(())
+            }
+            (Type::Fun(argument1, result1), Type::Fun(argument2, result2)) => {
+                self.unify_types(argument1, argument2)?;
+                self.unify_types(result1, result2)
+            }
+            (Type::List(element1), Type::List(element2)) => self.unify_types(element1, element2),
+            _ => Err((ty1.clone(), ty2.clone())),
+        }
+    }
+}
+
+// names the type variables left after inference 'a, 'b, ... in the order they
+// are printed, so that the same variable always gets the same name
+#[derive(Debug, Clone)]
+pub struct TypeVarNames {
+    vars: Vec<usize>,
+}
+impl TypeVarNames {
+    pub fn new() -> TypeVarNames {
+        TypeVarNames { vars: Vec::new() }
+    }
+
+    fn get_position(&mut self, index: usize) -> usize {
+        match self.vars.iter().position(|i| *i == index) {
+            Some(position) => position,
+            None => {
+                self.vars.push(index);
+                self.vars.len() - 1
+            }
+        }
+    }
+
+    pub fn get_name(&mut self, index: usize) -> String {
+        get_var_name(self.get_position(index))
+    }
+
+    pub fn get_type_str(&mut self, ty: &Type) -> String {
+        let mut vars = Vec::new();
+        ty.get_vars(&mut vars);
+        let mapping = vars
+            .into_iter()
+            .map(|index| (index, Type::Var(self.get_position(index))))
+            .collect();
+        ty.replace_vars(&mapping).to_string()
+    }
+}